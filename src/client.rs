@@ -9,64 +9,42 @@ pub struct Client {
 
 // used singleton from https://stackoverflow.com/questions/27791532/how-do-i-create-a-global-mutable-singleton
 impl Client {
+    /// returns the client of the active login
+    ///
+    /// after a reset the client is created again from the current settings
     pub fn get() -> Option<submarine::Client> {
-        static CLIENT: OnceLock<Mutex<Client>> = OnceLock::new();
-        if let Some(client) = CLIENT.get() {
-            client.lock().unwrap().inner.clone()
-        } else {
-            let settings = Settings::get().lock().unwrap();
-            if let (Some(uri), Some(user), Some(hash), Some(salt)) = (
-                &settings.login_uri,
-                &settings.login_username,
-                &settings.login_hash,
-                &settings.login_salt,
-            ) {
-                let auth = submarine::auth::Auth {
-                    user: user.clone(),
-                    version: String::from("0.16.1"),
-                    client_name: String::from(config::APP_NAME),
-                    hash: hash.clone(),
-                    salt: salt.clone(),
-                };
-                let client = Client {
-                    inner: Some(submarine::Client::new(uri, auth)),
-                };
-                let client = CLIENT.get_or_init(|| Mutex::new(client));
-                client.lock().unwrap().inner.clone()
-            } else {
-                tracing::warn!("no login config found");
-                // no settings for login found; show LoginForm
-                None
-            }
+        let mut client = Self::get_mut().lock().unwrap();
+        if client.inner.is_none() {
+            client.inner = Self::from_settings();
         }
+        client.inner.clone()
     }
 
     pub fn get_mut() -> &'static Mutex<Client> {
         static CLIENT: OnceLock<Mutex<Client>> = OnceLock::new();
-        if let Some(client) = CLIENT.get() {
-            client
+        CLIENT.get_or_init(|| Mutex::new(Client::default()))
+    }
+
+    fn from_settings() -> Option<submarine::Client> {
+        let settings = Settings::get().lock().unwrap();
+        if let (Some(uri), Some(user), Some(hash), Some(salt)) = (
+            &settings.login_uri,
+            &settings.login_username,
+            &settings.login_hash,
+            &settings.login_salt,
+        ) {
+            let auth = submarine::auth::Auth {
+                user: user.clone(),
+                version: String::from("0.16.1"),
+                client_name: String::from(config::APP_NAME),
+                hash: hash.clone(),
+                salt: salt.clone(),
+            };
+            Some(submarine::Client::new(uri, auth))
         } else {
-            let settings = Settings::get().lock().unwrap();
-            if let (Some(uri), Some(user), Some(hash), Some(salt)) = (
-                &settings.login_uri,
-                &settings.login_username,
-                &settings.login_hash,
-                &settings.login_salt,
-            ) {
-                let auth = submarine::auth::Auth {
-                    user: user.clone(),
-                    version: String::from("0.16.1"),
-                    client_name: String::from(config::APP_NAME),
-                    hash: hash.clone(),
-                    salt: salt.clone(),
-                };
-                let client = Client {
-                    inner: Some(submarine::Client::new(uri, auth)),
-                };
-                CLIENT.get_or_init(|| Mutex::new(client))
-            } else {
-                CLIENT.get_or_init(|| Mutex::new(Client::default()))
-            }
+            tracing::warn!("no login config found");
+            // no settings for login found; show LoginForm
+            None
         }
     }
 
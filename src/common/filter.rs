@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextRelation {
     Contains,
     ContainsNot,
@@ -8,20 +10,31 @@ pub enum TextRelation {
     Exact,
 }
 
-#[derive(Debug, Clone)]
+/// serde does not support Ordering, so it is mirrored here
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Ordering")]
+enum OrderingDef {
+    Less,
+    Equal,
+    Greater,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Filter {
     Favorite(Option<bool>),
     Title(TextRelation, String),
-    Year(Ordering, i32),
-    Cd(Ordering, i32),
-    TrackNumber(Ordering, usize),
+    Year(#[serde(with = "OrderingDef")] Ordering, i32),
+    Cd(#[serde(with = "OrderingDef")] Ordering, i32),
+    TrackNumber(#[serde(with = "OrderingDef")] Ordering, usize),
     Artist(TextRelation, String),
     Album(TextRelation, String),
     Genre(TextRelation, String),
-    BitRate(Ordering, usize),
-    DurationSec(Ordering, i32),
-    DurationMin(Ordering, i32),
-    AlbumCount(Ordering, i32),
+    BitRate(#[serde(with = "OrderingDef")] Ordering, usize),
+    DurationSec(#[serde(with = "OrderingDef")] Ordering, i32),
+    DurationMin(#[serde(with = "OrderingDef")] Ordering, i32),
+    AlbumCount(#[serde(with = "OrderingDef")] Ordering, i32),
+    Rating(#[serde(with = "OrderingDef")] Ordering, i32),
 }
 
 impl Filter {
@@ -160,6 +173,12 @@ impl Filter {
                     return false;
                 }
             }
+            Filter::Rating(order, value) => {
+                // unrated items have a rating of 0
+                if album.user_rating.unwrap_or(0).cmp(value) != *order {
+                    return false;
+                }
+            }
             _ => unreachable!("there are filters that shouldnt be"),
         }
         true
@@ -293,6 +312,12 @@ impl Filter {
                     return false;
                 }
             }
+            Filter::Rating(order, value) => {
+                // unrated items have a rating of 0
+                if track.user_rating.unwrap_or(0).cmp(value) != *order {
+                    return false;
+                }
+            }
             _ => unreachable!("there are filters that shouldnt be"),
         }
 
@@ -17,6 +17,7 @@ pub enum Category {
     DurationSec,
     BitRate,
     AlbumCount,
+    Rating,
 }
 
 impl Category {
@@ -34,6 +35,7 @@ impl Category {
             Self::DurationSec => gettext("Length (sec)"),
             Self::BitRate => gettext("Bit Rate"),
             Self::AlbumCount => gettext("Album Count"),
+            Self::Rating => gettext("Rating"),
         }
     }
 }
@@ -53,6 +55,7 @@ impl std::fmt::Display for Category {
             Self::DurationSec => write!(f, "Length (sec)"),
             Self::BitRate => write!(f, "Bit Rate"),
             Self::AlbumCount => write!(f, "Album Count"),
+            Self::Rating => write!(f, "Rating"),
         }
     }
 }
@@ -74,6 +77,7 @@ impl TryFrom<String> for Category {
             "Length (sec)" => Ok(Self::DurationSec),
             "Bit Rate" => Ok(Self::BitRate),
             "Album Count" => Ok(Self::AlbumCount),
+            "Rating" => Ok(Self::Rating),
             e => Err(format!("\"{e}\" is not a State")),
         }
     }
@@ -91,6 +95,7 @@ impl Category {
             Self::Genre,
             Self::DurationSec,
             Self::BitRate,
+            Self::Rating,
         ];
         gtk::gio::ListStore::from_slice(&categories)
     }
@@ -108,6 +113,7 @@ impl Category {
             Self::Cd,
             Self::Genre,
             Self::DurationMin,
+            Self::Rating,
         ];
         gtk::gio::ListStore::from_slice(&categories)
     }
@@ -168,5 +174,6 @@ mod tests {
         test_self(Category::DurationSec);
         test_self(Category::DurationMin);
         test_self(Category::BitRate);
+        test_self(Category::Rating);
     }
 }
@@ -0,0 +1,160 @@
+/// a single line of lyrics
+#[derive(Debug, Clone, PartialEq)]
+pub struct LyricsLine {
+    /// time in ms the line starts at; None for unsynced lyrics
+    pub start: Option<i64>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lyrics {
+    lines: Vec<LyricsLine>,
+}
+
+impl Lyrics {
+    pub fn lines(&self) -> &[LyricsLine] {
+        &self.lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.text.trim().is_empty())
+    }
+
+    /// lyrics are synced when every line has a start time
+    pub fn is_synced(&self) -> bool {
+        !self.lines.is_empty() && self.lines.iter().all(|line| line.start.is_some())
+    }
+
+    /// returns the index of the line that is sung at position in ms
+    pub fn current_line(&self, position: i64) -> Option<usize> {
+        if !self.is_synced() {
+            return None;
+        }
+        self.lines
+            .iter()
+            .rposition(|line| line.start.is_some_and(|start| start <= position))
+    }
+
+    /// lyrics without timestamps like returned from getLyrics
+    pub fn from_plain(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .map(|line| LyricsLine {
+                start: None,
+                text: line.trim().to_string(),
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// lyrics from getLyricsBySongId
+    pub fn from_structured(lyrics: &submarine::data::StructuredLyrics) -> Self {
+        // positive offsets show lines earlier
+        let offset = i64::from(lyrics.offset.unwrap_or(0));
+        let lines = lyrics
+            .line
+            .iter()
+            .map(|line| LyricsLine {
+                start: match lyrics.synced {
+                    true => line.start.map(|start| (i64::from(start) - offset).max(0)),
+                    false => None,
+                },
+                text: line.value.clone(),
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// parses the content of a .lrc file
+    ///
+    /// lines can have multiple timestamps; metadata tags besides offset are ignored
+    pub fn from_lrc(content: &str) -> Self {
+        let mut offset = 0;
+        let mut lines = vec![];
+        let mut unsynced = vec![];
+
+        for line in content.lines() {
+            let mut rest = line.trim();
+            let mut starts = vec![];
+            while let Some(tag) = rest.strip_prefix('[') {
+                let Some((tag, remaining)) = tag.split_once(']') else {
+                    break;
+                };
+                rest = remaining;
+                if let Some(start) = parse_timestamp(tag) {
+                    starts.push(start);
+                } else if let Some(value) = tag.strip_prefix("offset:") {
+                    offset = value.trim().parse().unwrap_or(0);
+                }
+            }
+
+            let text = rest.trim().to_string();
+            if starts.is_empty() {
+                if !text.is_empty() {
+                    unsynced.push(LyricsLine { start: None, text });
+                }
+                continue;
+            }
+            lines.extend(starts.into_iter().map(|start| LyricsLine {
+                start: Some(start),
+                text: text.clone(),
+            }));
+        }
+
+        if lines.is_empty() {
+            return Self { lines: unsynced };
+        }
+
+        for line in &mut lines {
+            line.start = line.start.map(|start| (start - offset).max(0));
+        }
+        lines.sort_by_key(|line| line.start);
+        Self { lines }
+    }
+}
+
+/// parses mm:ss, mm:ss.xx or mm:ss.xxx into ms
+fn parse_timestamp(tag: &str) -> Option<i64> {
+    let (minutes, seconds) = tag.split_once(':')?;
+    let minutes: i64 = minutes.trim().parse().ok()?;
+    let (seconds, fraction) = seconds.split_once(['.', ':']).unwrap_or((seconds, "0"));
+    let seconds: i64 = seconds.trim().parse().ok()?;
+    let millis = match fraction.len() {
+        1 => fraction.parse::<i64>().ok()? * 100,
+        2 => fraction.parse::<i64>().ok()? * 10,
+        3 => fraction.parse::<i64>().ok()?,
+        _ => return None,
+    };
+    Some((minutes * 60 + seconds) * 1000 + millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lrc() {
+        let content = "[ar:Artist]\n[offset:500]\n[00:01.00]first\n[00:12.50][01:02.000]chorus\n\n[00:05.3]second";
+        let lyrics = Lyrics::from_lrc(content);
+        assert!(lyrics.is_synced());
+
+        let starts: Vec<Option<i64>> = lyrics.lines().iter().map(|line| line.start).collect();
+        assert_eq!(
+            starts,
+            vec![Some(500), Some(4800), Some(12000), Some(61500)]
+        );
+        assert_eq!(lyrics.lines()[1].text, "second");
+
+        assert_eq!(lyrics.current_line(0), None);
+        assert_eq!(lyrics.current_line(5000), Some(1));
+        assert_eq!(lyrics.current_line(70000), Some(3));
+    }
+
+    #[test]
+    fn parse_lrc_without_timestamps() {
+        let lyrics = Lyrics::from_lrc("[ti:Title]\nfirst\nsecond");
+        assert!(!lyrics.is_synced());
+        assert_eq!(lyrics.lines().len(), 2);
+        assert_eq!(lyrics.current_line(1000), None);
+    }
+}
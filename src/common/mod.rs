@@ -1,7 +1,11 @@
 pub mod filter;
 pub mod filter_categories;
+pub mod lyrics;
 pub mod play_state;
 pub mod player;
+pub mod replay_gain;
+pub mod statistics;
+pub mod transcoding;
 pub mod types;
 pub mod views;
 
@@ -29,6 +33,18 @@ fn convert_for_label_intern(time: i64) -> Option<String> {
     Some(result)
 }
 
+/// converts bytes into a human readable size
+pub fn convert_for_size(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.2} GB", bytes / GB)
+    } else {
+        format!("{:.1} MB", bytes / MB)
+    }
+}
+
 pub fn sort_fn<T: PartialOrd>(a: &T, b: &T) -> relm4::gtk::Ordering {
     if a <= b {
         relm4::gtk::Ordering::Smaller
@@ -63,7 +79,7 @@ pub fn search_matching(mut test: String, mut search: String) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::convert_for_label;
+    use super::{convert_for_label, convert_for_size};
 
     #[test]
     fn convert_time() {
@@ -80,4 +96,11 @@ mod tests {
             assert_eq!(&convert_for_label(test.0), test.1);
         }
     }
+
+    #[test]
+    fn convert_size() {
+        assert_eq!(&convert_for_size(0), "0.0 MB");
+        assert_eq!(&convert_for_size(1024 * 1024 * 5), "5.0 MB");
+        assert_eq!(&convert_for_size(1024 * 1024 * 1024 * 3 / 2), "1.50 GB");
+    }
 }
@@ -0,0 +1,86 @@
+use gettextrs::gettext;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayGain {
+    #[default]
+    Off,
+    Track,
+    Album,
+}
+
+impl ReplayGain {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Track, Self::Album];
+
+    pub fn translate(&self) -> String {
+        match self {
+            Self::Off => gettext("Off"),
+            Self::Track => gettext("Track"),
+            Self::Album => gettext("Album"),
+        }
+    }
+
+    /// returns the gain in dB the server knows for this child
+    ///
+    /// the gain is reduced if the peak would clip, returns None when
+    /// the server has no information about it or normalization is off
+    pub fn gain(&self, child: &submarine::data::Child) -> Option<f64> {
+        let replay_gain = child.replay_gain.as_ref()?;
+        let (gain, peak) = match self {
+            Self::Off => return None,
+            Self::Track => (
+                replay_gain.track_gain.or(replay_gain.album_gain),
+                replay_gain.track_peak.or(replay_gain.album_peak),
+            ),
+            Self::Album => (
+                replay_gain.album_gain.or(replay_gain.track_gain),
+                replay_gain.album_peak.or(replay_gain.track_peak),
+            ),
+        };
+        let gain = f64::from(gain?);
+
+        match peak.map(f64::from) {
+            Some(peak) if peak > 0.0 => Some(gain.min(-20.0 * peak.log10())),
+            _ => Some(gain),
+        }
+    }
+}
+
+impl std::fmt::Display for ReplayGain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::Track => write!(f, "Track"),
+            Self::Album => write!(f, "Album"),
+        }
+    }
+}
+
+impl TryFrom<String> for ReplayGain {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_ref() {
+            "Off" => Ok(Self::Off),
+            "Track" => Ok(Self::Track),
+            "Album" => Ok(Self::Album),
+            e => Err(format!("{e} does not match a ReplayGain")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_self(mode: ReplayGain) {
+        assert_eq!(Ok(&mode), ReplayGain::try_from(mode.to_string()).as_ref());
+    }
+
+    #[test]
+    fn replay_gain_enum_conversion() {
+        test_self(ReplayGain::Off);
+        test_self(ReplayGain::Track);
+        test_self(ReplayGain::Album);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use chrono::Datelike;
+use gettextrs::gettext;
+
+use crate::subsonic_history::{PlayEnd, PlayRecord};
+
+/// number of entries in the top lists
+const TOP_COUNT: usize = 10;
+/// number of months shown in the library growth
+pub const GROWTH_MONTHS: usize = 24;
+
+/// time span statistics are computed for, the one containing a reference day
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Week,
+    #[default]
+    Month,
+    Year,
+    AllTime,
+}
+
+impl Period {
+    pub const ALL: [Self; 4] = [Self::Week, Self::Month, Self::Year, Self::AllTime];
+
+    pub fn translate(&self) -> String {
+        match self {
+            Self::Week => gettext("This week"),
+            Self::Month => gettext("This month"),
+            Self::Year => gettext("This year"),
+            Self::AllTime => gettext("All time"),
+        }
+    }
+
+    /// if day lies in the period containing reference
+    pub fn contains(&self, day: &chrono::NaiveDate, reference: &chrono::NaiveDate) -> bool {
+        match self {
+            Self::Week => day.iso_week() == reference.iso_week(),
+            Self::Month => day.year() == reference.year() && day.month() == reference.month(),
+            Self::Year => day.year() == reference.year(),
+            Self::AllTime => true,
+        }
+    }
+}
+
+/// years that have plays in the history and the current one, the newest first
+pub fn history_years(history: &[PlayRecord], today: &chrono::NaiveDate) -> Vec<i32> {
+    let first = history
+        .first()
+        .map(|play| play.day().year())
+        .unwrap_or(today.year())
+        .min(today.year());
+    (first..=today.year()).rev().collect()
+}
+
+/// year that is reviewed by default; early in the year the last one is complete
+pub fn review_year(today: &chrono::NaiveDate) -> i32 {
+    match today.month() {
+        1 => today.year() - 1,
+        _ => today.year(),
+    }
+}
+
+/// listening statistics of a period and the state of the library
+#[derive(Debug, Default)]
+pub struct Statistics {
+    /// name and number of plays, the most played first
+    pub top_artists: Vec<(String, usize)>,
+    pub top_albums: Vec<(String, usize)>,
+    pub top_tracks: Vec<(String, usize)>,
+    pub top_genres: Vec<(String, usize)>,
+    /// in ms
+    pub listening_time: i64,
+    /// month as "YYYY-MM" and number of albums added in it, the oldest first
+    pub growth: Vec<(String, usize)>,
+    /// decade as e.g. "1990s" and number of songs released in it, the oldest first
+    pub decades: Vec<(String, usize)>,
+}
+
+impl Statistics {
+    /// statistics of the period containing reference
+    ///
+    /// plays come from the local history; for all time the play counts of the cache are used,
+    /// as they include plays from before the history existed
+    pub fn new(
+        period: Period,
+        reference: &chrono::NaiveDate,
+        history: &[PlayRecord],
+        tracks: &[submarine::data::Child],
+        albums: &[submarine::data::Child],
+    ) -> Self {
+        let plays: Vec<(&submarine::data::Child, usize, i64)> = match period {
+            Period::AllTime => tracks
+                .iter()
+                .filter_map(|track| {
+                    let count = track.play_count.filter(|count| *count > 0)? as usize;
+                    let length = i64::from(track.duration.unwrap_or(0)) * 1000;
+                    Some((track, count, length * count as i64))
+                })
+                .collect(),
+            period => history
+                .iter()
+                // skipped songs were not really listened to
+                .filter(|play| play.end == PlayEnd::Finished || play.scrobbled)
+                .filter(|play| period.contains(&play.day(), reference))
+                .map(|play| {
+                    let length = i64::from(play.song.duration.unwrap_or(0)) * 1000;
+                    (&play.song, 1, length * i64::from(play.listened) / 100)
+                })
+                .collect(),
+        };
+
+        let mut artists = HashMap::new();
+        let mut albums_played = HashMap::new();
+        let mut tracks_played = HashMap::new();
+        let mut genres = HashMap::new();
+        let mut listening_time = 0;
+        for (song, count, time) in plays {
+            let artist = song.artist.clone().unwrap_or(gettext("Unknown Artist"));
+            *artists.entry(artist.clone()).or_default() += count;
+            if let Some(album) = &song.album {
+                *albums_played
+                    .entry(format!("{album} – {artist}"))
+                    .or_default() += count;
+            }
+            *tracks_played
+                .entry(format!("{} – {artist}", song.title))
+                .or_default() += count;
+            if let Some(genre) = &song.genre {
+                *genres.entry(genre.clone()).or_default() += count;
+            }
+            listening_time += time;
+        }
+
+        let mut growth: Vec<(String, usize)> = albums
+            .iter()
+            .filter_map(|album| album.created.as_ref())
+            .map(|created| created.format("%Y-%m").to_string())
+            .fold(
+                HashMap::new(),
+                |mut months: HashMap<String, usize>, month| {
+                    *months.entry(month).or_default() += 1;
+                    months
+                },
+            )
+            .into_iter()
+            .collect();
+        growth.sort();
+
+        let mut decades: Vec<(i32, usize)> = tracks
+            .iter()
+            .filter_map(|track| track.year.filter(|year| *year > 0))
+            .fold(HashMap::new(), |mut decades: HashMap<i32, usize>, year| {
+                *decades.entry(year / 10 * 10).or_default() += 1;
+                decades
+            })
+            .into_iter()
+            .collect();
+        decades.sort();
+
+        Self {
+            top_artists: top(artists),
+            top_albums: top(albums_played),
+            top_tracks: top(tracks_played),
+            top_genres: top(genres),
+            listening_time,
+            growth,
+            decades: decades
+                .into_iter()
+                .map(|(decade, count)| (format!("{decade}s"), count))
+                .collect(),
+        }
+    }
+
+    /// summary as a standalone html page
+    pub fn to_html(&self, title: &str) -> String {
+        let section = |heading: String, entries: &[(String, usize)]| {
+            let items: String = entries
+                .iter()
+                .map(|(name, count)| {
+                    format!("<li>{} <small>({count})</small></li>\n", escape_html(name))
+                })
+                .collect();
+            format!(
+                "<section>\n<h2>{}</h2>\n<ol>\n{items}</ol>\n</section>\n",
+                escape_html(&heading)
+            )
+        };
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>body {{ font-family: sans-serif; max-width: 50em; margin: auto; }}</style>\n\
+             </head>\n<body>\n<h1>{title}</h1>\n<p>{}: {}</p>\n",
+            escape_html(&gettext("Listening time")),
+            super::convert_for_label(self.listening_time),
+            title = escape_html(title),
+        );
+        html.push_str(&section(gettext("Top artists"), &self.top_artists));
+        html.push_str(&section(gettext("Top albums"), &self.top_albums));
+        html.push_str(&section(gettext("Top songs"), &self.top_tracks));
+        html.push_str(&section(gettext("Top genres"), &self.top_genres));
+        let growth_start = self.growth.len().saturating_sub(GROWTH_MONTHS);
+        html.push_str(&section(
+            gettext("Added albums per month"),
+            &self.growth[growth_start..],
+        ));
+        html.push_str(&section(gettext("Songs per decade"), &self.decades));
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// returns the entries with the highest count, ties are sorted by name
+fn top(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut entries: Vec<(String, usize)> = counts.into_iter().collect();
+    entries.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then(a_name.cmp(b_name)));
+    entries.truncate(TOP_COUNT);
+    entries
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_is_sorted_and_limited() {
+        let counts: HashMap<String, usize> =
+            (0..20).map(|i| (format!("entry {i:02}"), i % 5)).collect();
+        let top = top(counts);
+
+        assert_eq!(top.len(), TOP_COUNT);
+        assert_eq!(top[0], (String::from("entry 04"), 4));
+        assert_eq!(top[1], (String::from("entry 09"), 4));
+        assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn review_year_is_complete_early_in_the_year() {
+        let january = chrono::NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+        let june = chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+
+        assert_eq!(review_year(&january), 2024);
+        assert_eq!(review_year(&june), 2025);
+        assert_eq!(history_years(&[], &june), vec![2025]);
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        assert_eq!(
+            escape_html("<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use gettextrs::gettext;
+use relm4::gtk::gio::{self, prelude::NetworkMonitorExt};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/// format the server streams songs in
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TranscodeFormat {
+    /// original file without transcoding
+    #[default]
+    Raw,
+    Opus,
+    Mp3,
+}
+
+impl TranscodeFormat {
+    pub const ALL: [Self; 3] = [Self::Raw, Self::Opus, Self::Mp3];
+
+    pub fn translate(&self) -> String {
+        match self {
+            Self::Raw => gettext("Original"),
+            Self::Opus => String::from("Opus"),
+            Self::Mp3 => String::from("MP3"),
+        }
+    }
+
+    /// format parameter of the subsonic stream endpoint
+    pub fn parameter(&self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Opus => "opus",
+            Self::Mp3 => "mp3",
+        }
+    }
+}
+
+impl std::fmt::Display for TranscodeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Raw => write!(f, "Raw"),
+            Self::Opus => write!(f, "Opus"),
+            Self::Mp3 => write!(f, "Mp3"),
+        }
+    }
+}
+
+impl TryFrom<String> for TranscodeFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_ref() {
+            "Raw" => Ok(Self::Raw),
+            "Opus" => Ok(Self::Opus),
+            "Mp3" => Ok(Self::Mp3),
+            e => Err(format!("{e} does not match a TranscodeFormat")),
+        }
+    }
+}
+
+/// kind of network the computer is currently connected to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkProfile {
+    Unmetered,
+    /// e.g. mobile hotspots
+    Metered,
+}
+
+impl NetworkProfile {
+    pub fn current() -> Self {
+        if gio::NetworkMonitor::default().is_network_metered() {
+            Self::Metered
+        } else {
+            Self::Unmetered
+        }
+    }
+}
+
+/// how songs are streamed on a NetworkProfile
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamProfile {
+    #[serde(default)] //defaults to Raw
+    pub format: TranscodeFormat,
+    /// in kbps, 0 means no limit
+    #[serde(default)]
+    pub max_bit_rate: i32,
+}
+
+impl StreamProfile {
+    /// returns the profile of the network that is currently used
+    pub fn current() -> Self {
+        let settings = Settings::get().lock().unwrap();
+        match NetworkProfile::current() {
+            NetworkProfile::Unmetered => settings.stream_unmetered.clone(),
+            NetworkProfile::Metered => settings.stream_metered.clone(),
+        }
+    }
+
+    /// max bit rate parameter of the subsonic stream endpoint
+    ///
+    /// original files are never limited, as the server would transcode them otherwise
+    pub fn max_bit_rate(&self) -> Option<i32> {
+        match self.format {
+            TranscodeFormat::Raw => None,
+            _ if self.max_bit_rate <= 0 => None,
+            _ => Some(self.max_bit_rate),
+        }
+    }
+
+    /// returns the bit rate in kbps the song will be played with
+    ///
+    /// returns None when the server chooses the bit rate
+    pub fn effective_bit_rate(&self, child: &submarine::data::Child) -> Option<i32> {
+        match (self.max_bit_rate(), child.bit_rate) {
+            (None, bit_rate) if self.format == TranscodeFormat::Raw => bit_rate,
+            (None, _) => None,
+            (Some(max), Some(bit_rate)) => Some(max.min(bit_rate)),
+            (Some(max), None) => Some(max),
+        }
+    }
+
+    /// short description of format and bit rate, e.g. "Opus 128 kbps"
+    pub fn describe(&self, child: &submarine::data::Child) -> String {
+        let format = match self.format {
+            TranscodeFormat::Raw => child
+                .suffix
+                .as_ref()
+                .map(|suffix| suffix.to_uppercase())
+                .unwrap_or(self.format.translate()),
+            _ => self.format.translate(),
+        };
+
+        match self.effective_bit_rate(child) {
+            Some(bit_rate) => format!("{format} {bit_rate} kbps"),
+            None => format,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_self(format: TranscodeFormat) {
+        assert_eq!(
+            Ok(&format),
+            TranscodeFormat::try_from(format.to_string()).as_ref()
+        );
+    }
+
+    #[test]
+    fn transcode_format_enum_conversion() {
+        test_self(TranscodeFormat::Raw);
+        test_self(TranscodeFormat::Opus);
+        test_self(TranscodeFormat::Mp3);
+    }
+}
@@ -73,6 +73,8 @@ pub enum Droppable {
     Artist(Box<submarine::data::ArtistId3>),
     Playlist(Box<submarine::data::PlaylistWithSongs>),
     PlaylistItems(Vec<PlaylistUid>),
+    /// directory of the server and all songs it contains, including subdirectories
+    Directory(Box<submarine::data::Directory>, Vec<submarine::data::Child>),
 }
 
 impl Droppable {
@@ -116,6 +118,7 @@ impl Droppable {
             Droppable::PlaylistItems(items) => {
                 items.iter().map(|song| song.child.clone()).collect()
             }
+            Droppable::Directory(_dir, songs) => songs.clone(),
         }
     }
 
@@ -145,6 +148,7 @@ impl Droppable {
             }
             Droppable::Playlist(playlist) => playlist.entry.len(),
             Droppable::PlaylistItems(playlist) => playlist.len(),
+            Droppable::Directory(_dir, songs) => songs.len(),
         }
     }
 }
@@ -8,6 +8,14 @@ pub enum ClickableViews {
     Albums,
     Tracks,
     Playlists,
+    Radio,
+    Podcasts,
+    Statistics,
+    History,
+    Folders,
+    Genres,
+    Shares,
+    Bookmarks,
 }
 
 // contains all views that can appear in Browser
@@ -28,6 +36,14 @@ impl Into<Views> for &browser::Views {
             browser::Views::Albums(_) => Views::Clickable(ClickableViews::Albums),
             browser::Views::Tracks(_) => Views::Clickable(ClickableViews::Tracks),
             browser::Views::Playlists(_) => Views::Clickable(ClickableViews::Playlists),
+            browser::Views::Radio(_) => Views::Clickable(ClickableViews::Radio),
+            browser::Views::Podcasts(_) => Views::Clickable(ClickableViews::Podcasts),
+            browser::Views::Statistics(_) => Views::Clickable(ClickableViews::Statistics),
+            browser::Views::History(_) => Views::Clickable(ClickableViews::History),
+            browser::Views::Folders(_) => Views::Clickable(ClickableViews::Folders),
+            browser::Views::Genres(_) => Views::Clickable(ClickableViews::Genres),
+            browser::Views::Shares(_) => Views::Clickable(ClickableViews::Shares),
+            browser::Views::Bookmarks(_) => Views::Clickable(ClickableViews::Bookmarks),
             browser::Views::Artist(_) => Views::Artist,
             browser::Views::Album(_) => Views::Album,
         }
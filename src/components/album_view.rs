@@ -5,7 +5,8 @@ use relm4::{
     gtk::{
         self, glib,
         prelude::{
-            BoxExt, ButtonExt, ListModelExt, OrientableExt, SelectionModelExt, ToValue, WidgetExt,
+            BoxExt, ButtonExt, ListModelExt, OrientableExt, SelectionModelExt, ToValue,
+            ToggleButtonExt, WidgetExt,
         },
     },
     ComponentController, RelmWidgetExt,
@@ -22,7 +23,7 @@ use crate::{
         AlbumTrackRow, ArtistColumn, BitRateColumn, FavColumn, GenreColumn, LengthColumn,
         PlayCountColumn, PositionColumn, TitleColumn,
     },
-    gtk_helper::{loading_widget::LoadingWidgetState, stack::StackExt},
+    gtk_helper::{loading_widget::LoadingWidgetState, rating::Rating, stack::StackExt},
     settings::Settings,
     subsonic::Subsonic,
 };
@@ -32,6 +33,7 @@ pub struct AlbumView {
     subsonic: Rc<RefCell<Subsonic>>,
     id: Id,
     cover: relm4::Controller<Cover>,
+    rating: Rating,
     tracks: relm4::typed_view::column::TypedColumnView<AlbumTrackRow, gtk::MultiSelection>,
 }
 
@@ -41,6 +43,7 @@ pub enum AlbumViewIn {
     Cover(CoverOut),
     UpdateFavoriteAlbum(String, bool),
     UpdateFavoriteSong(String, bool),
+    UpdateRatingAlbum(String, Option<i32>),
     UpdatePlayCountSong(String, Option<i64>),
     SearchChanged,
     HoverCover(bool),
@@ -54,8 +57,10 @@ pub enum AlbumViewOut {
     ReplaceQueue(Droppable),
     FavoriteAlbumClicked(String, bool),
     FavoriteSongClicked(String, bool),
+    RatingAlbumClicked(String, i32),
     DisplayToast(String),
     Download(Droppable),
+    Pin(Droppable, bool),
     ArtistClicked(Id),
 }
 
@@ -126,12 +131,23 @@ impl relm4::Component for AlbumView {
             cover: Cover::builder()
                 .launch((subsonic.clone(), album.cover_art.clone()))
                 .forward(sender.input_sender(), AlbumViewIn::Cover),
+            rating: Rating::new(),
             tracks,
         };
 
         let widgets = view_output!();
         model.cover.model().add_css_class_image("size150");
 
+        // rating of album
+        model.rating.set_rating(album.user_rating);
+        let rating_sender = sender.clone();
+        let album_id = album.id.clone();
+        model.rating.connect_changed(move |rating| {
+            rating_sender
+                .output(AlbumViewOut::RatingAlbumClicked(album_id.clone(), rating))
+                .unwrap();
+        });
+
         //load album
         let id = model.id.clone();
         sender.oneshot_command(async move {
@@ -226,6 +242,10 @@ impl relm4::Component for AlbumView {
                                 set_label: &gettext("Genre:"),
                             }
                         },
+                        append = &model.rating.widget().clone() -> gtk::Box {
+                            set_halign: gtk::Align::Start,
+                            set_tooltip: &gettext("Rating of album"),
+                        },
                         gtk::Box {
                             set_spacing: 15,
                             append: append_append = &gtk::Button {
@@ -287,6 +307,22 @@ impl relm4::Component for AlbumView {
                                     let drop =  Droppable::Child(Box::new(album.clone()));
                                     sender.output(AlbumViewOut::Download(drop)).unwrap();
                                 }
+                            },
+                            append: pin_album = &gtk::ToggleButton {
+                                set_sensitive: false,
+                                set_active: subsonic.borrow().offline().is_pinned(&id),
+
+                                gtk::Box {
+                                    gtk::Image {
+                                        set_icon_name: Some("airplane-mode-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                },
+                                set_tooltip: &gettext("Keep this album available offline"),
+                                connect_clicked[sender, album] => move |btn| {
+                                    let drop = Droppable::AlbumChild(Box::new(album.clone()));
+                                    sender.output(AlbumViewOut::Pin(drop, btn.is_active())).unwrap();
+                                }
                             }
                         }
                     }
@@ -383,6 +419,11 @@ impl relm4::Component for AlbumView {
                         }
                     });
             }
+            AlbumViewIn::UpdateRatingAlbum(id, rating) => {
+                if self.id.as_ref() == id {
+                    self.rating.set_rating(rating);
+                }
+            }
             AlbumViewIn::UpdatePlayCountSong(id, play_count) => (0..self.tracks.len())
                 .filter_map(|i| self.tracks.get(i))
                 .filter(|t| t.borrow().item().id == id)
@@ -518,6 +559,7 @@ impl relm4::Component for AlbumView {
                 widgets.insert_album.set_sensitive(true);
                 widgets.replace_queue.set_sensitive(true);
                 widgets.download_album.set_sensitive(true);
+                widgets.pin_album.set_sensitive(true);
             }
         }
     }
@@ -19,7 +19,7 @@ use crate::{
     factory::{
         album_row::{
             AlbumRow, ArtistColumn, CoverColumn, FavColumn, GenreColumn, LengthColumn,
-            PlayCountColumn, TitleColumn, YearColumn,
+            PlayCountColumn, RatingColumn, TitleColumn, YearColumn,
         },
         filter_row::{FilterRow, FilterRowIn, FilterRowOut},
     },
@@ -83,6 +83,7 @@ pub enum AlbumsViewIn {
     FilterChanged,
     UpdateWidgetsSearchFilterChanged,
     UpdateFavoriteAlbum(String, bool),
+    UpdateRatingAlbum(String, Option<i32>),
     UpdatePlayCountAlbum(String, Option<i64>),
     Cover(CoverOut),
     FilterRow(FilterRowOut),
@@ -100,6 +101,7 @@ pub enum AlbumsViewOut {
     ClickedArtist(Id),
     DisplayToast(String),
     FavoriteClicked(String, bool),
+    RatingClicked(String, i32),
     AddToQueue(Droppable),
     AppendToQueue(Droppable),
     ReplaceQueue(Droppable),
@@ -127,6 +129,7 @@ impl relm4::component::Component for AlbumsView {
         entries.append_column::<YearColumn>();
         // entries.append_column::<CdColumn>();
         entries.append_column::<PlayCountColumn>();
+        entries.append_column::<RatingColumn>();
         entries.append_column::<FavColumn>();
 
         let columns = entries.get_columns();
@@ -154,6 +157,10 @@ impl relm4::component::Component for AlbumsView {
             .get("Year")
             .unwrap()
             .set_title(Some(&gettext("Year")));
+        columns
+            .get("Rating")
+            .unwrap()
+            .set_title(Some(&gettext("Rating")));
         columns
             .get("Favorite")
             .unwrap()
@@ -448,6 +455,12 @@ impl relm4::component::Component for AlbumsView {
                         }
                     });
             }
+            AlbumsViewIn::UpdateRatingAlbum(id, rating) => {
+                (0..self.entries.len())
+                    .filter_map(|i| self.entries.get(i))
+                    .filter(|a| a.borrow().item().id == id)
+                    .for_each(|album| album.borrow_mut().set_rating(rating));
+            }
             AlbumsViewIn::UpdatePlayCountAlbum(id, play_count) => {
                 (0..self.entries.len())
                     .filter_map(|i| self.entries.get(i))
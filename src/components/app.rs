@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
 
 use gettextrs::gettext;
 use gtk::prelude::{BoxExt, ButtonExt, CheckButtonExt, OrientableExt};
@@ -8,7 +8,7 @@ use relm4::{
         self, gdk,
         prelude::{
             ApplicationExt, EditableExt, EventControllerExt, GestureSingleExt, GtkApplicationExt,
-            GtkWindowExt, PopoverExt, ToggleButtonExt, WidgetExt,
+            GtkWindowExt, ObjectExt, PopoverExt, ToggleButtonExt, WidgetExt,
         },
     },
     Component, ComponentController, Controller, RelmWidgetExt,
@@ -19,28 +19,43 @@ use crate::{
     common::{
         play_state::PlayState,
         player::Command,
+        transcoding::StreamProfile,
         types::Droppable,
         views::{ClickableViews, Views},
     },
     components::{
         browser::{Browser, BrowserIn, BrowserOut},
         equalizer::{Equalizer, EqualizerOut},
+        lyrics::{LyricsView, LyricsViewIn},
+        music_folders::{MusicFolders, MusicFoldersOut},
         play_controls::{PlayControl, PlayControlIn, PlayControlOut},
         play_info::{PlayInfo, PlayInfoIn, PlayInfoOut},
         queue::{Queue, QueueIn, QueueOut},
+        random_mix::{RandomMix, RandomMixIn, RandomMixOut},
         seekbar::{Seekbar, SeekbarIn, SeekbarOut},
+        sequence_button_impl::queue_end::QueueEnd,
         settings_window::{SettingsWindow, SettingsWindowIn, SettingsWindowOut},
         volume_button::{VolumeButton, VolumeButtonIn, VolumeButtonOut},
+        warning_dialog::WarningDialog,
     },
     config,
     download::Download,
     mpris::{Mpris, MprisOut},
     playback::{Playback, PlaybackOut},
     settings::Settings,
+    share::{self, Share},
     subsonic::Subsonic,
+    subsonic_history::ActivePlay,
+    subsonic_offline::SubsonicOffline,
+    subsonic_scrobbles::{PendingScrobble, SubsonicScrobbles},
     Args,
 };
 
+/// number of songs that are appended when the queue continues
+const QUEUE_CONTINUATION_SIZE: usize = 10;
+/// number of the latest plays that are not repeated when the queue continues
+const RECENT_PLAYS: usize = 100;
+
 #[derive(Debug)]
 pub struct App {
     playback: Rc<RefCell<Playback>>,
@@ -51,10 +66,29 @@ pub struct App {
     play_controls: Controller<PlayControl>,
     seekbar: Controller<Seekbar>,
     play_info: Controller<PlayInfo>,
+    lyrics: Controller<LyricsView>,
     browser: AsyncController<Browser>,
     equalizer: Controller<Equalizer>,
+    music_folders: AsyncController<MusicFolders>,
     volume_button: Controller<VolumeButton>,
     settings_window: Controller<SettingsWindow>,
+    random_mix: AsyncController<RandomMix>,
+
+    /// position in ms to seek to when the current song starts playing
+    resume_position: Option<i64>,
+    /// internet radio station that is played instead of the queue
+    radio: Option<submarine::data::InternetRadioStation>,
+    /// id of the current song when it is a podcast episode
+    episode: Option<String>,
+    bookmarks: Vec<submarine::data::Bookmark>,
+    /// song and position in ms to continue at when it starts playing
+    play_bookmark: Option<(String, i64)>,
+    /// play of the current song that is not yet in the history
+    active_play: Option<ActivePlay>,
+    /// restored song and position in ms, its play only starts when playback starts
+    restored_play: Option<(submarine::data::Child, i64)>,
+    /// number of the latest scheduled retry of pending scrobbles
+    scrobble_retry: usize,
 }
 
 #[derive(Debug)]
@@ -65,6 +99,8 @@ pub enum AppIn {
     Seekbar(SeekbarOut),
     Playback(PlaybackOut),
     Equalizer(EqualizerOut),
+    MusicFolders(MusicFoldersOut),
+    RandomMix(RandomMixOut),
     Queue(Box<QueueOut>),
     Browser(BrowserOut),
     PlayInfo(PlayInfoOut),
@@ -75,23 +111,52 @@ pub enum AppIn {
     FavoriteAlbumClicked(String, bool),
     FavoriteArtistClicked(String, bool),
     FavoriteSongClicked(String, bool),
+    RatingAlbumClicked(String, i32),
+    RatingSongClicked(String, i32),
     SearchActivate(bool),
     SearchChanged,
     Download(Droppable),
+    Pin(Droppable, bool),
+    Share(Droppable),
+    /// ids, description and expiry in ms since epoch of a new share
+    CreateShare(Vec<String>, Option<String>, Option<i64>),
+    OfflineFileStored(String, String, u64),
     ClickedNavigationBtn(ClickableViews),
     DisableBigCoverOverlay,
     LoadBigCoverPicture(String),
     UpdateCanPlayNextOrPrev,
     BackPressed,
     OpenSettings,
+    /// index of profile in Settings or an index beyond them for adding a server
+    ProfileSelected(u32),
     SettingsWindow(SettingsWindowOut),
     VolumeButton(VolumeButtonOut),
+    SavePlayQueue,
+    LoadPlayQueue,
+    RestorePlayQueue(Box<submarine::data::PlayQueue>),
+    LoadBookmarks,
+    /// shows the bookmarks of the current song on the seekbar
+    ShowBookmarkMarks,
+    /// appends songs depending on the queue end mode when the last song of the queue plays
+    ContinueQueue,
+    /// adds a play to the local history
+    StorePlay(Box<ActivePlay>),
+    /// submits the pending scrobbles in batches
+    SubmitScrobbles,
+    /// retries submitting pending scrobbles after a delay that grows with every failed retry
+    ScheduleScrobbleRetry,
+    /// retry timer elapsed; ignored when a newer retry was scheduled since
+    RetryScrobbles(usize),
+    /// updates the indicator of pending scrobbles
+    PendingScrobblesChanged,
 }
 
 #[derive(Debug)]
 pub enum AppOut {
     Logout,
     Reload,
+    SwitchProfile(String),
+    AddServer,
     DisplayToast(String),
 }
 
@@ -127,12 +192,29 @@ impl relm4::component::AsyncComponent for App {
                             gtk::Label {
                                 set_text: &gettext("loading information from server"),
                                 add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                            },
+                            #[name(progress)]
+                            gtk::Label {
+                                add_css_class: "dim-label",
                             }
                         }
                     }
                 }
             }
         }
+
+        // show progress of loading the library
+        let receiver = Subsonic::progress();
+        let progress = progress.downgrade();
+        gtk::glib::spawn_future_local(async move {
+            while let Ok(msg) = receiver.recv().await {
+                let Some(progress) = progress.upgrade() else {
+                    break;
+                };
+                progress.set_text(&msg);
+            }
+        });
+
         Some(relm4::loading_widgets::LoadingWidgets::new(
             root.clone(),
             loading,
@@ -148,7 +230,19 @@ impl relm4::component::AsyncComponent for App {
         let time_startup = std::time::Instant::now();
 
         tracing::info!("start loading subsonic information");
-        let subsonic = Subsonic::load_or_create().await.unwrap_or_default();
+        let mut subsonic = match Subsonic::load_or_create().await {
+            Ok(subsonic) => subsonic,
+            Err(e) => {
+                sender.input(AppIn::DisplayToast(format!(
+                    "could not load library: {e:?}"
+                )));
+                Subsonic::default()
+            }
+        };
+        let outdated_pins = subsonic.take_outdated_pins();
+        if !outdated_pins.is_empty() {
+            SubsonicOffline::download(sender.clone(), gettext("pinned songs"), outdated_pins);
+        }
         let subsonic = std::rc::Rc::new(std::cell::RefCell::new(subsonic));
         tracing::info!("finished loaded subsonic information");
 
@@ -164,20 +258,27 @@ impl relm4::component::AsyncComponent for App {
         let play_info = PlayInfo::builder()
             .launch((subsonic.clone(), None))
             .forward(sender.input_sender(), AppIn::PlayInfo);
+        let lyrics = LyricsView::builder().launch(subsonic.clone()).detach();
         let browser = Browser::builder()
             .launch(subsonic.clone())
             .forward(sender.input_sender(), AppIn::Browser);
         let equalizer = Equalizer::builder()
             .launch(())
             .forward(sender.input_sender(), AppIn::Equalizer);
+        let music_folders = MusicFolders::builder()
+            .launch(())
+            .forward(sender.input_sender(), AppIn::MusicFolders);
         let volume_button = VolumeButton::builder()
             .launch(())
             .forward(sender.input_sender(), AppIn::VolumeButton);
         let settings_window = SettingsWindow::builder()
             .launch(())
             .forward(sender.input_sender(), AppIn::SettingsWindow);
+        let random_mix = RandomMix::builder()
+            .launch(subsonic.clone())
+            .forward(sender.input_sender(), AppIn::RandomMix);
 
-        let model = App {
+        let mut model = App {
             playback: playback.clone(),
             subsonic,
             mpris,
@@ -186,18 +287,48 @@ impl relm4::component::AsyncComponent for App {
             play_controls,
             seekbar,
             play_info,
+            lyrics,
             browser,
             equalizer,
+            music_folders,
             volume_button,
             settings_window,
+            random_mix,
+
+            resume_position: None,
+            radio: None,
+            episode: None,
+            bookmarks: vec![],
+            play_bookmark: None,
+            active_play: None,
+            restored_play: None,
+            scrobble_retry: 0,
         };
 
         let equalizer_popover = gtk::Popover::default();
         equalizer_popover.set_child(Some(model.equalizer.widget()));
+        let music_folders_popover = gtk::Popover::default();
+        music_folders_popover.set_child(Some(model.music_folders.widget()));
         let volume_popover = gtk::Popover::default();
         volume_popover.set_child(Some(model.volume_button.widget()));
         let volume_button_sender = model.volume_button.sender();
         let last_volume_scroll_event = Rc::new(RefCell::new(0));
+        let (profiles, active_profile) = {
+            let settings = Settings::get().lock().unwrap();
+            let mut names: Vec<String> = settings
+                .profiles
+                .iter()
+                .map(|profile| profile.name.clone())
+                .collect();
+            let active = settings
+                .profiles
+                .iter()
+                .position(|profile| Some(&profile.name) == settings.active_profile.as_ref())
+                .unwrap_or_default();
+            names.push(gettext("Add server…"));
+            let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+            (gtk::StringList::new(&names), active as u32)
+        };
         let widgets = view_output!();
         equalizer_popover.set_parent(&widgets.equalizer_btn);
         volume_popover.set_parent(&widgets.volume_btn);
@@ -220,18 +351,10 @@ impl relm4::component::AsyncComponent for App {
             let child = row.item().clone();
 
             // set playback song
-            let client = Client::get().unwrap();
-            match client.stream_url(
-                &child.id,
-                None,
-                None::<&str>,
-                None,
-                None::<&str>,
-                None,
-                None,
-            ) {
+            let gain = Settings::get().lock().unwrap().replay_gain.gain(&child);
+            match track_uri(&model.subsonic, &child) {
                 Ok(url) => {
-                    if let Err(e) = playback.borrow_mut().set_track(url) {
+                    if let Err(e) = playback.borrow_mut().set_track(url, gain) {
                         sender.input(AppIn::DisplayToast(format!("could not set track: {e}")));
                     }
                 }
@@ -249,6 +372,13 @@ impl relm4::component::AsyncComponent for App {
             model
                 .play_info
                 .emit(PlayInfoIn::NewState(Box::new(Some(child.clone()))));
+            model
+                .lyrics
+                .emit(LyricsViewIn::NewSong(Box::new(Some(child.clone()))));
+            let stream_info = stream_profile(&model.subsonic, &child).describe(&child);
+            model
+                .play_info
+                .emit(PlayInfoIn::StreamInfo(Some(stream_info)));
 
             // set controls
             model
@@ -261,6 +391,12 @@ impl relm4::component::AsyncComponent for App {
                     .seekbar
                     .emit(SeekbarIn::NewRange(i64::from(*duration) * 1000));
             }
+
+            // continue where playback stopped
+            if let Some(position) = model.queue.model().position() {
+                model.seekbar.emit(SeekbarIn::SeekTo(position));
+                model.resume_position = Some(position);
+            }
         } else {
             // set controls
             model
@@ -279,6 +415,12 @@ impl relm4::component::AsyncComponent for App {
                 if let Err(e) = library.borrow().save() {
                     send.input(AppIn::DisplayToast(format!("error saving library: {e}")));
                 }
+                if let Err(e) = library.borrow().podcasts().save() {
+                    send.input(AppIn::DisplayToast(format!(
+                        "error saving podcast positions: {e}"
+                    )));
+                }
+                send.input(AppIn::SavePlayQueue);
             }
         });
 
@@ -298,6 +440,10 @@ impl relm4::component::AsyncComponent for App {
             // scroll to current
             queue.emit(QueueIn::DirectJumpToCurrent);
         });
+        sender.input(AppIn::LoadPlayQueue);
+        sender.input(AppIn::LoadBookmarks);
+        sender.input(AppIn::PendingScrobblesChanged);
+        sender.input(AppIn::SubmitScrobbles);
 
         relm4::component::AsyncComponentParts { model, widgets }
     }
@@ -349,7 +495,19 @@ impl relm4::component::AsyncComponent for App {
                         },
                     },
 
-                    model.queue.widget(),
+                    gtk::StackSwitcher {
+                        set_halign: gtk::Align::Center,
+                        set_stack: Some(&sidebar_stack),
+                    },
+
+                    #[name = "sidebar_stack"]
+                    gtk::Stack {
+                        set_vexpand: true,
+                        set_transition_type: gtk::StackTransitionType::SlideLeftRight,
+
+                        add_titled[Some("queue"), &gettext("Queue")] = model.queue.widget(),
+                        add_titled[Some("lyrics"), &gettext("Lyrics")] = model.lyrics.widget(),
+                    },
                 },
 
                 #[wrap(Some)]
@@ -557,6 +715,182 @@ impl relm4::component::AsyncComponent for App {
                                             },
                                         }
                                     },
+                                    append: podcasts_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show podcasts"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Podcasts),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("microphone-sensitivity-high-symbolic"),
+                                            },
+                                            append: podcasts_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Podcasts"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: bookmarks_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show bookmarks"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Bookmarks),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("user-bookmarks-symbolic"),
+                                            },
+                                            append: bookmarks_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Bookmarks"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: shares_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show shared links"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Shares),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("emblem-shared-symbolic"),
+                                            },
+                                            append: shares_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Shares"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: genres_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show genres"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Genres),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("applications-multimedia-symbolic"),
+                                            },
+                                            append: genres_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Genres"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: folders_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Browse folders"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Folders),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("folder-symbolic"),
+                                            },
+                                            append: folders_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Folders"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: history_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show listening history"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::History),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("document-open-recent-symbolic"),
+                                            },
+                                            append: history_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("History"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: statistics_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show listening statistics"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Statistics),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("x-office-spreadsheet-symbolic"),
+                                            },
+                                            append: statistics_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Statistics"),
+                                                }
+                                            },
+                                        }
+                                    },
+                                    append: radio_btn = &gtk::ToggleButton {
+                                        add_css_class: "flat",
+                                        add_css_class: "circular",
+                                        set_tooltip: &gettext("Show internet radio stations"),
+                                        connect_clicked => AppIn::ClickedNavigationBtn(ClickableViews::Radio),
+
+                                        gtk::Box {
+                                            set_spacing: 3,
+
+                                            gtk::Image {
+                                                set_icon_name: Some("network-wireless-symbolic"),
+                                            },
+                                            append: radio_rvl = &gtk::Revealer {
+                                                set_transition_duration: 200,
+                                                set_transition_type: gtk::RevealerTransitionType::SlideRight,
+
+                                                gtk::Label {
+                                                    set_text: &gettext("Radio"),
+                                                }
+                                            },
+                                        }
+                                    },
                                 }
                             },
 
@@ -566,6 +900,46 @@ impl relm4::component::AsyncComponent for App {
                                 set_halign: gtk::Align::End,
                                 set_spacing: 5,
 
+                                #[name = "pending_scrobbles_btn"]
+                                gtk::Button {
+                                    add_css_class: "flat",
+                                    set_valign: gtk::Align::Center,
+                                    set_visible: false,
+                                    connect_clicked => AppIn::SubmitScrobbles,
+
+                                    gtk::Box {
+                                        set_spacing: 3,
+
+                                        gtk::Image {
+                                            set_icon_name: Some("emblem-synchronizing-symbolic"),
+                                        },
+                                        #[name = "pending_scrobbles"]
+                                        gtk::Label {},
+                                    }
+                                },
+
+                                #[name = "music_folders_btn"]
+                                gtk::MenuButton {
+                                    add_css_class: "flat",
+                                    set_valign: gtk::Align::Center,
+                                    set_visible: false,
+                                    set_icon_name: "folder-music-symbolic",
+                                    set_tooltip: &gettext("Restrict library to music folders"),
+                                    set_popover: Some(&music_folders_popover),
+                                },
+
+                                gtk::DropDown {
+                                    add_css_class: "flat",
+                                    set_valign: gtk::Align::Center,
+                                    set_tooltip: &gettext("Switch between servers"),
+                                    set_model: Some(&profiles),
+                                    set_selected: active_profile,
+
+                                    connect_selected_notify[sender] => move |drop_down| {
+                                        sender.input(AppIn::ProfileSelected(drop_down.selected()));
+                                    }
+                                },
+
                                 append: equalizer_btn = &gtk::Button {
                                     add_css_class: "flat",
                                     add_css_class: "size24",
@@ -728,6 +1102,7 @@ impl relm4::component::AsyncComponent for App {
         match msg {
             AppIn::PlayControlOutput(input) => match input {
                 PlayControlOut::Player(cmd) => sender.input(AppIn::Player(cmd)),
+                PlayControlOut::QueueEnd(_) => sender.input(AppIn::ContinueQueue),
             },
             AppIn::Seekbar(msg) => match msg {
                 SeekbarOut::SeekDragged(seek_in_ms) => {
@@ -735,11 +1110,69 @@ impl relm4::component::AsyncComponent for App {
                         sender.input(AppIn::DisplayToast(format!("seek failed: {e:?}")));
                     }
                 }
+                SeekbarOut::CreateBookmark(position) => {
+                    if self.radio.is_some() {
+                        return;
+                    }
+                    let Some((_, song)) = self.queue.model().current() else {
+                        return;
+                    };
+                    let client = Client::get().unwrap();
+                    if let Err(e) = client
+                        .create_bookmark(&song.item().id, position, None::<&str>)
+                        .await
+                    {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "could not create bookmark: {e:?}"
+                        )));
+                        return;
+                    }
+                    sender.input(AppIn::DisplayToast(gettext("Bookmark created")));
+                    sender.input(AppIn::LoadBookmarks);
+                    self.browser.emit(BrowserIn::BookmarksChanged);
+                }
             },
             AppIn::Playback(playback) => match playback {
+                // a radio stream has no following song
+                PlaybackOut::TrackEnd if self.radio.is_some() => {
+                    sender.input(AppIn::Player(Command::Stop));
+                }
                 PlaybackOut::TrackEnd => sender.input(AppIn::Player(Command::Next)),
+                PlaybackOut::AboutToFinish => self.queue.emit(QueueIn::PrepareNext),
+                PlaybackOut::TrackChanged => self.queue.emit(QueueIn::TrackChanged),
                 PlaybackOut::SongPosition(ms) => {
-                    sender.input(AppIn::Player(Command::SetSongPosition(ms)));
+                    // seek as soon as the song is playing
+                    if let Some(position) = self.resume_position.take() {
+                        if let Err(e) = self.playback.borrow_mut().set_position(position) {
+                            sender.input(AppIn::DisplayToast(format!(
+                                "could not resume song position: {e:?}"
+                            )));
+                        }
+                    }
+                    if let Some(id) = &self.episode {
+                        let length = self.seekbar.model().length();
+                        self.subsonic
+                            .borrow_mut()
+                            .podcasts_mut()
+                            .set_position(id, ms, length);
+                    }
+                    if self.radio.is_none() {
+                        if let Some(play) = &mut self.active_play {
+                            play.set_position(ms);
+                        }
+                        self.lyrics.emit(LyricsViewIn::SongPosition(ms));
+                        sender.input(AppIn::Player(Command::SetSongPosition(ms)));
+                    }
+                }
+                PlaybackOut::StreamTitle(title) => {
+                    let Some(station) = &self.radio else {
+                        return;
+                    };
+                    self.play_info
+                        .emit(PlayInfoIn::Radio(station.name.clone(), Some(title.clone())));
+                    self.mpris
+                        .borrow_mut()
+                        .set_station(Some(station.name.clone()), Some(title));
                 }
                 PlaybackOut::ScrobbleThresholdReached => {
                     if Settings::get().lock().unwrap().scrobble {
@@ -752,11 +1185,31 @@ impl relm4::component::AsyncComponent for App {
 
                         let client = Client::get().unwrap();
                         if let Err(e) = client.scrobble(vec![(&child.id, None)], Some(true)).await {
-                            sender.input(AppIn::DisplayToast(format!(
-                                "could not scrobble to server: {e:?}"
-                            )));
+                            // keep the play with its original time and submit it later
+                            tracing::warn!("could not scrobble to server: {e:?}");
+                            let time = match &self.active_play {
+                                Some(play) if play.song().id == child.id => play.started(),
+                                _ => chrono::Local::now().timestamp_millis(),
+                            };
+                            let pending = PendingScrobble { song: child, time };
+                            if let Err(e) = self.subsonic.borrow_mut().scrobbles_mut().push(pending)
+                            {
+                                sender.input(AppIn::DisplayToast(format!(
+                                    "could not store pending scrobble: {e:?}"
+                                )));
+                            }
+                            sender.input(AppIn::PendingScrobblesChanged);
+                            sender.input(AppIn::ScheduleScrobbleRetry);
                             return;
                         }
+                        if let Some(play) = &mut self.active_play {
+                            play.set_scrobbled();
+                        }
+
+                        // server is reachable again
+                        if !self.subsonic.borrow().scrobbles().pending().is_empty() {
+                            sender.input(AppIn::SubmitScrobbles);
+                        }
 
                         // update subsonic cache
                         self.subsonic.borrow_mut().increment_play_counter(&child);
@@ -797,6 +1250,120 @@ impl relm4::component::AsyncComponent for App {
                 EqualizerOut::Changed => self.playback.borrow_mut().sync_equalizer(),
                 EqualizerOut::DisplayToast(msg) => sender.input(AppIn::DisplayToast(msg)),
             },
+            AppIn::MusicFolders(msg) => match msg {
+                MusicFoldersOut::DisplayToast(msg) => sender.input(AppIn::DisplayToast(msg)),
+                MusicFoldersOut::Available(state) => widgets.music_folders_btn.set_visible(state),
+                MusicFoldersOut::Changed => {
+                    // keep queue, because the library is reloaded
+                    if let Err(e) = self.playback.borrow_mut().stop() {
+                        sender.input(AppIn::DisplayToast(format!("could not stop playback: {e}")));
+                    }
+                    let position = self.seekbar.model().current() as i64;
+                    if let Err(e) = self.queue.model().save(position) {
+                        sender.input(AppIn::DisplayToast(format!("could not save queue: {e}")));
+                    }
+                    sender.output(AppOut::Reload).unwrap();
+                }
+            },
+            AppIn::RandomMix(msg) => match msg {
+                RandomMixOut::DisplayToast(msg) => sender.input(AppIn::DisplayToast(msg)),
+                RandomMixOut::ReplaceQueue(drop) => self.queue.emit(QueueIn::Replace(drop)),
+                RandomMixOut::AppendToQueue(drop) => self.queue.emit(QueueIn::Append(drop)),
+                RandomMixOut::CreatePlaylist(name, songs) => {
+                    self.browser.emit(BrowserIn::NewPlaylist(name, songs));
+                }
+            },
+            AppIn::SubmitScrobbles => {
+                let batch = self.subsonic.borrow().scrobbles().batch();
+                if batch.is_empty() {
+                    return;
+                }
+
+                let client = Client::get().unwrap();
+                let ids: Vec<_> = batch
+                    .iter()
+                    .map(|scrobble| (&scrobble.song.id, Some(scrobble.time as usize)))
+                    .collect();
+                let stored = match client.scrobble(ids, Some(true)).await {
+                    // the server refused a scrobble, e.g. of a deleted song
+                    Err(e) if SubsonicScrobbles::is_refusal(&e) => {
+                        tracing::warn!("server refused pending scrobbles: {e:?}");
+                        let mut subsonic = self.subsonic.borrow_mut();
+                        match subsonic.scrobbles_mut().refused(&batch) {
+                            None => Ok(()),
+                            Some(dropped) => {
+                                tracing::warn!("dropped scrobble of {}", dropped.song.id);
+                                subsonic.scrobbles().save()
+                            }
+                        }
+                    }
+                    // the server is not reachable or can not scrobble right now, so try again later
+                    Err(e) => {
+                        tracing::warn!("could not submit pending scrobbles: {e:?}");
+                        sender.input(AppIn::ScheduleScrobbleRetry);
+                        return;
+                    }
+                    Ok(_) => {
+                        let mut subsonic = self.subsonic.borrow_mut();
+                        let stored = subsonic.scrobbles_mut().remove_submitted(&batch);
+
+                        // plays are stored as scrobbled in the history
+                        let plays: Vec<(String, i64)> = batch
+                            .iter()
+                            .map(|scrobble| (scrobble.song.id.clone(), scrobble.time))
+                            .collect();
+                        if let Some(play) = &mut self.active_play {
+                            if plays.contains(&(play.song().id.clone(), play.started())) {
+                                play.set_scrobbled();
+                            }
+                        }
+                        if let Err(e) = subsonic.history_mut().set_scrobbled(&plays) {
+                            sender.input(AppIn::DisplayToast(format!(
+                                "could not store play in history: {e:?}"
+                            )));
+                        }
+                        self.browser.emit(BrowserIn::NewPlay);
+                        stored
+                    }
+                };
+                sender.input(AppIn::PendingScrobblesChanged);
+
+                // the same scrobbles would be submitted again
+                if let Err(e) = stored {
+                    sender.input(AppIn::DisplayToast(format!(
+                        "could not store pending scrobbles: {e:?}"
+                    )));
+                    return;
+                }
+
+                // continue with the next batch
+                sender.input(AppIn::SubmitScrobbles);
+            }
+            AppIn::ScheduleScrobbleRetry => {
+                let delay = self.subsonic.borrow_mut().scrobbles_mut().retry_failed();
+                self.scrobble_retry += 1;
+                let retry = self.scrobble_retry;
+                tracing::info!("retrying pending scrobbles in {delay:?}");
+                gtk::glib::source::timeout_add_local_once(delay, move || {
+                    sender.input(AppIn::RetryScrobbles(retry));
+                });
+            }
+            AppIn::RetryScrobbles(retry) => {
+                if retry == self.scrobble_retry {
+                    sender.input(AppIn::SubmitScrobbles);
+                }
+            }
+            AppIn::PendingScrobblesChanged => {
+                let pending = self.subsonic.borrow().scrobbles().pending().len();
+                widgets.pending_scrobbles_btn.set_visible(pending > 0);
+                widgets.pending_scrobbles.set_text(&pending.to_string());
+                widgets.pending_scrobbles_btn.set_tooltip(&format!(
+                    "{pending} {}",
+                    gettext(
+                        "scrobbles are waiting to be submitted to the server. Click to retry now"
+                    )
+                ));
+            }
             AppIn::Logout => sender.output(AppOut::Logout).unwrap(),
             AppIn::ClearCache => {
                 if let Err(e) = self.subsonic.borrow_mut().delete_cache() {
@@ -814,19 +1381,17 @@ impl relm4::component::AsyncComponent for App {
             }
             AppIn::Queue(msg) => match *msg {
                 QueueOut::Play(child) => {
+                    self.resume_position = None;
+                    self.restored_play = None;
+                    if self.radio.take().is_some() {
+                        self.mpris.borrow_mut().set_station(None, None);
+                    }
+
                     // set playback track
-                    let client = Client::get().unwrap();
-                    match client.stream_url(
-                        child.clone().id,
-                        None,
-                        None::<&str>,
-                        None,
-                        None::<&str>,
-                        None,
-                        None,
-                    ) {
+                    let gain = Settings::get().lock().unwrap().replay_gain.gain(&child);
+                    match track_uri(&self.subsonic, &child) {
                         Ok(url) => {
-                            if let Err(e) = self.playback.borrow_mut().set_track(url) {
+                            if let Err(e) = self.playback.borrow_mut().set_track(url, gain) {
                                 sender.input(AppIn::DisplayToast(format!(
                                     "could not set track: {e}"
                                 )));
@@ -848,7 +1413,74 @@ impl relm4::component::AsyncComponent for App {
                         )));
                     }
 
+                    // continue bookmarks and episodes where they were stopped
+                    self.resume_position = match self.play_bookmark.take() {
+                        Some((id, position)) if id == child.id => Some(position),
+                        _ => self.subsonic.borrow().podcasts().position(&child.id),
+                    };
+
+                    sender.input(AppIn::Queue(Box::new(QueueOut::TrackChanged(child))));
+                }
+                QueueOut::PrepareNext(child) => {
+                    // end of queue, playback will send TrackEnd
+                    let Some(child) = *child else {
+                        return;
+                    };
+                    // radio streams have no end to follow
+                    if self.radio.is_some() {
+                        return;
+                    }
+
+                    // optionally no crossfade between songs of the same album
+                    let crossfade_ms = {
+                        let settings = Settings::get().lock().unwrap();
+                        let current_album = self
+                            .queue
+                            .model()
+                            .current()
+                            .and_then(|(_, row)| row.item().album_id.clone());
+                        match settings.crossfade_skip_same_album
+                            && current_album.is_some()
+                            && current_album == child.album_id
+                        {
+                            true => 0,
+                            false => u64::from(settings.crossfade_secs) * 1000,
+                        }
+                    };
+
+                    // preroll next track for a gapless transition
+                    let gain = Settings::get().lock().unwrap().replay_gain.gain(&child);
+                    match track_uri(&self.subsonic, &child) {
+                        Ok(url) => {
+                            if let Err(e) =
+                                self.playback
+                                    .borrow_mut()
+                                    .set_next_track(url, gain, crossfade_ms)
+                            {
+                                sender.input(AppIn::DisplayToast(format!(
+                                    "could not set next track: {e}"
+                                )));
+                            }
+                        }
+                        Err(e) => {
+                            sender.input(AppIn::DisplayToast(format!(
+                                "could not find song streaming url: {e:?}"
+                            )));
+                        }
+                    }
+                }
+                QueueOut::TrackChanged(child) => {
                     sender.input(AppIn::DesktopNotification);
+                    if let Some(play) = self.active_play.take() {
+                        sender.input(AppIn::StorePlay(Box::new(play)));
+                    }
+                    self.active_play = Some(ActivePlay::new(*child.clone()));
+                    self.episode = self
+                        .subsonic
+                        .borrow()
+                        .podcasts()
+                        .is_episode(&child.id)
+                        .then(|| child.id.clone());
 
                     // update seekbar
                     if let Some(length) = child.duration {
@@ -857,19 +1489,75 @@ impl relm4::component::AsyncComponent for App {
                     } else {
                         self.seekbar.emit(SeekbarIn::NewRange(0));
                     }
+                    sender.input(AppIn::ShowBookmarkMarks);
 
                     // update playcontrol
                     self.play_info
                         .emit(PlayInfoIn::NewState(Box::new(Some(*child.clone()))));
+                    self.lyrics
+                        .emit(LyricsViewIn::NewSong(Box::new(Some(*child.clone()))));
+                    let stream_info = stream_profile(&self.subsonic, &child).describe(&child);
+                    self.play_info
+                        .emit(PlayInfoIn::StreamInfo(Some(stream_info)));
 
                     self.mpris.borrow_mut().set_song(Some(*child));
                     self.mpris.borrow_mut().set_state(PlayState::Play);
+                    sender.input(AppIn::SavePlayQueue);
+                    sender.input(AppIn::ContinueQueue);
+                }
+                QueueOut::Restored(child, position) => {
+                    // load song paused, so it continues at position when playing
+                    let gain = Settings::get().lock().unwrap().replay_gain.gain(&child);
+                    match track_uri(&self.subsonic, &child) {
+                        Ok(url) => {
+                            if let Err(e) = self.playback.borrow_mut().set_track(url, gain) {
+                                sender.input(AppIn::DisplayToast(format!(
+                                    "could not set track: {e}"
+                                )));
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            sender.input(AppIn::DisplayToast(format!(
+                                "could not find song streaming url: {e:?}"
+                            )));
+                            return;
+                        }
+                    }
+                    if let Err(e) = self.playback.borrow_mut().pause() {
+                        sender.input(AppIn::DisplayToast(format!("error pausing: {e}")));
+                    }
+                    self.resume_position = Some(position);
+                    self.restored_play = Some((*child.clone(), position));
+
+                    self.play_info
+                        .emit(PlayInfoIn::NewState(Box::new(Some(*child.clone()))));
+                    self.lyrics
+                        .emit(LyricsViewIn::NewSong(Box::new(Some(*child.clone()))));
+                    let stream_info = stream_profile(&self.subsonic, &child).describe(&child);
+                    self.play_info
+                        .emit(PlayInfoIn::StreamInfo(Some(stream_info)));
+                    self.play_controls
+                        .emit(PlayControlIn::NewState(PlayState::Pause));
+                    if let Some(duration) = child.duration {
+                        self.seekbar
+                            .emit(SeekbarIn::NewRange(i64::from(duration) * 1000));
+                    }
+                    self.seekbar.emit(SeekbarIn::SeekTo(position));
+                    sender.input(AppIn::ShowBookmarkMarks);
+                    self.mpris.borrow_mut().set_song(Some(*child));
+                    self.mpris.borrow_mut().set_state(PlayState::Pause);
                 }
                 QueueOut::QueueEmpty => {
+                    self.restored_play = None;
+                    if let Some(play) = self.active_play.take() {
+                        sender.input(AppIn::StorePlay(Box::new(play)));
+                    }
                     if let Err(e) = self.playback.borrow_mut().stop() {
                         sender.input(AppIn::DisplayToast(format!("{e}")));
                     }
                     self.play_info.emit(PlayInfoIn::NewState(Box::new(None)));
+                    self.lyrics.emit(LyricsViewIn::NewSong(Box::new(None)));
                     self.play_controls.emit(PlayControlIn::Disable);
                     self.mpris.borrow_mut().can_play(false);
                 }
@@ -878,6 +1566,7 @@ impl relm4::component::AsyncComponent for App {
                     self.mpris.borrow_mut().can_play(true);
                 }
                 QueueOut::Player(cmd) => sender.input(AppIn::Player(cmd)),
+                QueueOut::Share(drop) => sender.input(AppIn::Share(drop)),
                 QueueOut::CreatePlaylist => {
                     self.browser.emit(BrowserIn::NewPlaylist(
                         gettext("New playlist from Queue"),
@@ -889,6 +1578,8 @@ impl relm4::component::AsyncComponent for App {
                     sender.input(AppIn::FavoriteSongClicked(id, state));
                 }
                 QueueOut::QueueSongsChanged => {
+                    // next song may have changed
+                    self.playback.borrow_mut().clear_next_track();
                     sender.input(AppIn::UpdateCanPlayNextOrPrev);
                 }
                 QueueOut::SongChanged => {
@@ -915,7 +1606,57 @@ impl relm4::component::AsyncComponent for App {
                 BrowserOut::FavoriteSongClicked(id, state) => {
                     sender.input(AppIn::FavoriteSongClicked(id, state));
                 }
+                BrowserOut::RatingAlbumClicked(id, rating) => {
+                    sender.input(AppIn::RatingAlbumClicked(id, rating));
+                }
+                BrowserOut::RatingSongClicked(id, rating) => {
+                    sender.input(AppIn::RatingSongClicked(id, rating));
+                }
                 BrowserOut::Download(drop) => sender.input(AppIn::Download(drop)),
+                BrowserOut::Pin(drop, state) => sender.input(AppIn::Pin(drop, state)),
+                BrowserOut::Share(drop) => sender.input(AppIn::Share(drop)),
+                BrowserOut::PlayBookmark(child, position) => {
+                    self.play_bookmark = Some((child.id.clone(), position));
+                    self.queue
+                        .emit(QueueIn::InsertAndPlay(Droppable::Queue(vec![*child])));
+                }
+                BrowserOut::BookmarksChanged => sender.input(AppIn::LoadBookmarks),
+                BrowserOut::PlayRadio(station) => {
+                    // radio is played outside of the queue
+                    self.queue.emit(QueueIn::NewState(PlayState::Stop));
+                    self.resume_position = None;
+
+                    if let Err(e) = self
+                        .playback
+                        .borrow_mut()
+                        .set_track(&station.stream_url, None)
+                    {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "could not play radio station: {e}"
+                        )));
+                        return;
+                    }
+                    if let Err(e) = self.playback.borrow_mut().play() {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "could not play playback: {e:?}"
+                        )));
+                        return;
+                    }
+
+                    self.play_info
+                        .emit(PlayInfoIn::Radio(station.name.clone(), None));
+                    self.lyrics.emit(LyricsViewIn::NewSong(Box::new(None)));
+                    self.play_controls
+                        .emit(PlayControlIn::NewState(PlayState::Play));
+                    self.seekbar.emit(SeekbarIn::Disable);
+                    self.mpris.borrow_mut().set_song(None);
+                    self.mpris
+                        .borrow_mut()
+                        .set_station(Some(station.name.clone()), None);
+                    self.mpris.borrow_mut().set_state(PlayState::Play);
+                    self.radio = Some(*station);
+                }
+                BrowserOut::OpenRandomMix => self.random_mix.emit(RandomMixIn::Show),
                 BrowserOut::ChangedViewTo(view) => {
                     sender.input(AppIn::SearchActivate(false));
                     match view {
@@ -971,6 +1712,19 @@ impl relm4::component::AsyncComponent for App {
                     self.mpris.borrow_mut().set_state(PlayState::Play);
                 }
                 Command::Play => {
+                    // continue paused radio
+                    if self.radio.is_some() {
+                        if let Err(e) = self.playback.borrow_mut().play() {
+                            sender.input(AppIn::DisplayToast(format!(
+                                "could not play playback: {e:?}"
+                            )));
+                        }
+                        self.play_controls
+                            .emit(PlayControlIn::NewState(PlayState::Play));
+                        self.mpris.borrow_mut().set_state(PlayState::Play);
+                        return;
+                    }
+
                     if !self.queue.model().can_play() {
                         return;
                     }
@@ -980,6 +1734,20 @@ impl relm4::component::AsyncComponent for App {
                         return;
                     }
 
+                    // continue restored song where it stopped
+                    if let Some(position) = self.resume_position.take() {
+                        if let Err(e) = self.playback.borrow_mut().set_position(position) {
+                            sender.input(AppIn::DisplayToast(format!(
+                                "could not resume song position: {e:?}"
+                            )));
+                        }
+                    }
+                    if let Some((child, position)) = self.restored_play.take() {
+                        let mut play = ActivePlay::new(child);
+                        play.set_position(position);
+                        self.active_play = Some(play);
+                    }
+
                     if let Err(e) = self.playback.borrow_mut().play() {
                         sender.input(AppIn::DisplayToast(format!(
                             "could not play playback: {e:?}"
@@ -1005,12 +1773,19 @@ impl relm4::component::AsyncComponent for App {
                     PlayState::Play => sender.input(AppIn::Player(Command::Pause)),
                 },
                 Command::Stop => {
+                    if self.radio.take().is_some() {
+                        self.mpris.borrow_mut().set_station(None, None);
+                    }
+                    if let Some(play) = self.active_play.take() {
+                        sender.input(AppIn::StorePlay(Box::new(play)));
+                    }
                     if let Err(e) = self.playback.borrow_mut().stop() {
                         sender.input(AppIn::DisplayToast(format!(
                             "could not stop playback: {e:?}"
                         )));
                     }
                     self.play_info.emit(PlayInfoIn::NewState(Box::new(None)));
+                    self.lyrics.emit(LyricsViewIn::NewSong(Box::new(None)));
                     self.play_controls
                         .emit(PlayControlIn::NewState(PlayState::Stop));
                     self.queue.emit(QueueIn::NewState(PlayState::Stop));
@@ -1051,9 +1826,11 @@ impl relm4::component::AsyncComponent for App {
                     }
                 }
                 Command::Repeat(repeat) => {
+                    self.playback.borrow_mut().clear_next_track();
                     self.mpris.borrow_mut().set_loop_status(repeat.clone());
                 }
                 Command::Shuffle(shuffle) => {
+                    self.playback.borrow_mut().clear_next_track();
                     self.mpris.borrow_mut().set_shuffle(shuffle.clone());
                 }
                 Command::MuteToggle => {
@@ -1133,6 +1910,34 @@ impl relm4::component::AsyncComponent for App {
                     }
                 }
             }
+            AppIn::RatingAlbumClicked(id, rating) => {
+                let client = Client::get().unwrap();
+                if let Err(e) = client.set_rating(&id, rating).await {
+                    sender.input(AppIn::DisplayToast(format!("could not rate album: {e:?}")));
+                    return;
+                }
+
+                // change subsonic
+                self.subsonic.borrow_mut().rate_album(&id, rating);
+
+                //update views
+                let rating = (rating > 0).then_some(rating);
+                self.browser.emit(BrowserIn::UpdateRatingAlbum(id, rating));
+            }
+            AppIn::RatingSongClicked(id, rating) => {
+                let client = Client::get().unwrap();
+                if let Err(e) = client.set_rating(&id, rating).await {
+                    sender.input(AppIn::DisplayToast(format!("could not rate song: {e:?}")));
+                    return;
+                }
+
+                // change subsonic
+                self.subsonic.borrow_mut().rate_song(&id, rating);
+
+                //update views
+                let rating = (rating > 0).then_some(rating);
+                self.browser.emit(BrowserIn::UpdateRatingSong(id, rating));
+            }
             AppIn::SearchActivate(true) => {
                 Settings::get().lock().unwrap().search_active = true;
                 widgets.search_bar.set_reveal_child(true);
@@ -1155,6 +1960,85 @@ impl relm4::component::AsyncComponent for App {
                 }
             }
             AppIn::Download(drop) => Download::download(&self.subsonic, sender.clone(), drop),
+            AppIn::Share(drop) => Share::show_dialog(&self.subsonic, sender.clone(), drop),
+            AppIn::CreateShare(ids, description, expires) => {
+                let client = Client::get().unwrap();
+                let shares = match client.create_share(ids, description, expires).await {
+                    Ok(shares) => shares,
+                    Err(e) => {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "could not create share: {e:?}"
+                        )));
+                        return;
+                    }
+                };
+                if let Some(created) = shares.first() {
+                    share::copy_to_clipboard(&created.url);
+                    sender.input(AppIn::DisplayToast(gettext("Copied link to clipboard")));
+                }
+                self.browser.emit(BrowserIn::SharesChanged);
+            }
+            AppIn::Pin(drop, state) => {
+                let Some((id, name)) = SubsonicOffline::pin_info(&drop) else {
+                    sender.input(AppIn::DisplayToast(String::from(
+                        "only albums, artists and playlists can be kept offline",
+                    )));
+                    return;
+                };
+
+                if !state {
+                    if let Err(e) = self.subsonic.borrow_mut().offline_mut().unpin(&id) {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "could not remove offline songs: {e:?}"
+                        )));
+                    }
+                    if let Err(e) = self.subsonic.borrow().offline().save() {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "error saving offline info: {e}"
+                        )));
+                    }
+                    return;
+                }
+
+                let songs = drop.get_songs(&self.subsonic);
+                let missing = self
+                    .subsonic
+                    .borrow_mut()
+                    .offline_mut()
+                    .pin(&id, &name, songs);
+                if let Err(e) = self.subsonic.borrow().offline().save() {
+                    sender.input(AppIn::DisplayToast(format!(
+                        "error saving offline info: {e}"
+                    )));
+                }
+                if missing.is_empty() {
+                    sender.input(AppIn::DisplayToast(format!(
+                        "{}: {name}",
+                        gettext("Available offline")
+                    )));
+                    return;
+                }
+                sender.input(AppIn::DisplayToast(format!(
+                    "{} {} {}: {name}",
+                    gettext("Storing"),
+                    missing.len(),
+                    gettext("songs offline")
+                )));
+                SubsonicOffline::download(sender.clone(), name, missing);
+            }
+            AppIn::OfflineFileStored(id, file_name, size) => {
+                let mut subsonic = self.subsonic.borrow_mut();
+                if let Err(e) = subsonic.offline_mut().add_file(id, file_name, size) {
+                    sender.input(AppIn::DisplayToast(format!(
+                        "could not remove unpinned offline song: {e:?}"
+                    )));
+                }
+                if let Err(e) = subsonic.offline().save() {
+                    sender.input(AppIn::DisplayToast(format!(
+                        "error saving offline info: {e}"
+                    )));
+                }
+            }
             AppIn::ClickedNavigationBtn(view) => {
                 reset_navigation_btns(widgets);
 
@@ -1184,6 +2068,46 @@ impl relm4::component::AsyncComponent for App {
                         widgets.playlists_rvl.set_reveal_child(true);
                         widgets.playlists_btn.set_active(true);
                     }
+                    ClickableViews::Podcasts => {
+                        self.browser.emit(BrowserIn::ShowPodcasts);
+                        widgets.podcasts_rvl.set_reveal_child(true);
+                        widgets.podcasts_btn.set_active(true);
+                    }
+                    ClickableViews::Bookmarks => {
+                        self.browser.emit(BrowserIn::ShowBookmarks);
+                        widgets.bookmarks_rvl.set_reveal_child(true);
+                        widgets.bookmarks_btn.set_active(true);
+                    }
+                    ClickableViews::Shares => {
+                        self.browser.emit(BrowserIn::ShowShares);
+                        widgets.shares_rvl.set_reveal_child(true);
+                        widgets.shares_btn.set_active(true);
+                    }
+                    ClickableViews::Genres => {
+                        self.browser.emit(BrowserIn::ShowGenres);
+                        widgets.genres_rvl.set_reveal_child(true);
+                        widgets.genres_btn.set_active(true);
+                    }
+                    ClickableViews::Folders => {
+                        self.browser.emit(BrowserIn::ShowFolders);
+                        widgets.folders_rvl.set_reveal_child(true);
+                        widgets.folders_btn.set_active(true);
+                    }
+                    ClickableViews::History => {
+                        self.browser.emit(BrowserIn::ShowHistory);
+                        widgets.history_rvl.set_reveal_child(true);
+                        widgets.history_btn.set_active(true);
+                    }
+                    ClickableViews::Statistics => {
+                        self.browser.emit(BrowserIn::ShowStatistics);
+                        widgets.statistics_rvl.set_reveal_child(true);
+                        widgets.statistics_btn.set_active(true);
+                    }
+                    ClickableViews::Radio => {
+                        self.browser.emit(BrowserIn::ShowRadio);
+                        widgets.radio_rvl.set_reveal_child(true);
+                        widgets.radio_btn.set_active(true);
+                    }
                 }
             }
             AppIn::DisableBigCoverOverlay => {
@@ -1225,11 +2149,208 @@ impl relm4::component::AsyncComponent for App {
                     .emit(PlayControlIn::DisableNext(can_next));
                 self.mpris.borrow_mut().can_play_next(can_next);
             }
-            AppIn::OpenSettings => self.settings_window.emit(SettingsWindowIn::Show),
+            AppIn::OpenSettings => {
+                let size = self.subsonic.borrow().offline().size();
+                self.settings_window
+                    .emit(SettingsWindowIn::OfflineSize(size));
+                self.settings_window.emit(SettingsWindowIn::Show);
+            }
+            AppIn::ProfileSelected(index) => {
+                let (profile, active) = {
+                    let settings = Settings::get().lock().unwrap();
+                    (
+                        settings
+                            .profiles
+                            .get(index as usize)
+                            .map(|p| p.name.clone()),
+                        settings.active_profile.clone(),
+                    )
+                };
+                if profile.is_some() && profile == active {
+                    return;
+                }
+
+                // stop playing and keep queue of current server
+                if let Err(e) = self.playback.borrow_mut().stop() {
+                    sender.input(AppIn::DisplayToast(format!("could not stop playback: {e}")));
+                }
+                let position = self.seekbar.model().current() as i64;
+                if let Err(e) = self.queue.model().save(position) {
+                    sender.input(AppIn::DisplayToast(format!("could not save queue: {e}")));
+                }
+
+                match profile {
+                    Some(name) => sender.output(AppOut::SwitchProfile(name)).unwrap(),
+                    None => sender.output(AppOut::AddServer).unwrap(),
+                }
+            }
             AppIn::BackPressed => self.browser.emit(BrowserIn::GoBack),
+            AppIn::SavePlayQueue => {
+                if !Settings::get().lock().unwrap().sync_queue {
+                    return;
+                }
+
+                let queue = self.queue.model();
+                let ids: Vec<String> = queue.songs().into_iter().map(|song| song.id).collect();
+                if ids.is_empty() {
+                    return;
+                }
+                let current = queue.current().map(|(_, row)| row.item().id.clone());
+                let position = self.seekbar.model().current() as i64;
+
+                let client = Client::get().unwrap();
+                if let Err(e) = client.save_play_queue(ids, current, Some(position)).await {
+                    tracing::warn!("could not save queue on server: {e}");
+                }
+            }
+            AppIn::LoadBookmarks => {
+                let client = Client::get().unwrap();
+                match client.get_bookmarks().await {
+                    Ok(bookmarks) => self.bookmarks = bookmarks,
+                    Err(e) => tracing::warn!("could not load bookmarks: {e}"),
+                }
+                sender.input(AppIn::ShowBookmarkMarks);
+            }
+            AppIn::ShowBookmarkMarks => {
+                let Some((_, song)) = self.queue.model().current() else {
+                    return;
+                };
+                let positions = self
+                    .bookmarks
+                    .iter()
+                    .filter(|bookmark| bookmark.entry.id == song.item().id)
+                    .map(|bookmark| bookmark.position)
+                    .collect();
+                self.seekbar.emit(SeekbarIn::Bookmarks(positions));
+            }
+            AppIn::StorePlay(play) => {
+                let record = play.finish();
+                if let Err(e) = self.subsonic.borrow_mut().history_mut().push(record) {
+                    sender.input(AppIn::DisplayToast(format!(
+                        "could not store play in history: {e:?}"
+                    )));
+                    return;
+                }
+                self.browser.emit(BrowserIn::NewPlay);
+            }
+            AppIn::ContinueQueue => {
+                let mode = Settings::get().lock().unwrap().queue_end.clone();
+                if mode == QueueEnd::Stop || self.radio.is_some() {
+                    return;
+                }
+
+                // only continue when the last song of the queue plays
+                let queue = self.queue.model();
+                if queue.next_index().is_some() {
+                    return;
+                }
+                let Some((_, current)) = queue.current() else {
+                    return;
+                };
+                let current = current.item().clone();
+                // songs in the queue count as recently played
+                let mut known: HashSet<String> =
+                    queue.songs().into_iter().map(|song| song.id).collect();
+                drop(queue);
+                // so are the latest plays, even when they were removed from the queue;
+                // albums are continued as a whole anyway
+                if mode != QueueEnd::NextAlbum {
+                    let subsonic = self.subsonic.borrow();
+                    known.extend(
+                        subsonic
+                            .history()
+                            .records()
+                            .iter()
+                            .rev()
+                            .take(RECENT_PLAYS)
+                            .map(|play| play.song.id.clone()),
+                    );
+                }
+
+                let songs = match queue_continuation(&self.subsonic, &mode, &current).await {
+                    Ok(songs) => songs,
+                    Err(e) => {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "could not continue queue: {e:?}"
+                        )));
+                        return;
+                    }
+                };
+                // albums are appended as a whole
+                let limit = match mode {
+                    QueueEnd::NextAlbum => usize::MAX,
+                    _ => QUEUE_CONTINUATION_SIZE,
+                };
+                let songs: Vec<submarine::data::Child> = songs
+                    .into_iter()
+                    .filter(|song| known.insert(song.id.clone()))
+                    .take(limit)
+                    .collect();
+                if songs.is_empty() {
+                    sender.input(AppIn::DisplayToast(gettext(
+                        "Found no new songs to continue the queue with",
+                    )));
+                    return;
+                }
+                self.queue.emit(QueueIn::Append(Droppable::Queue(songs)));
+            }
+            AppIn::LoadPlayQueue => {
+                if !Settings::get().lock().unwrap().sync_queue {
+                    return;
+                }
+
+                let client = Client::get().unwrap();
+                let remote = match client.get_play_queue().await {
+                    Ok(remote) => remote,
+                    Err(e) => {
+                        tracing::warn!("could not load queue from server: {e}");
+                        return;
+                    }
+                };
+                if remote.entry.is_empty() {
+                    return;
+                }
+
+                // compare with local queue
+                let queue = self.queue.model();
+                let local: Vec<String> = queue.songs().into_iter().map(|song| song.id).collect();
+                let current = queue.current().map(|(_, row)| row.item().id.clone());
+                let remote_ids: Vec<&String> = remote.entry.iter().map(|song| &song.id).collect();
+                if local.iter().eq(remote_ids) && current == remote.current {
+                    return;
+                }
+
+                if local.is_empty() {
+                    sender.input(AppIn::RestorePlayQueue(Box::new(remote)));
+                } else {
+                    show_play_queue_prompt(sender.clone(), remote);
+                }
+            }
+            AppIn::RestorePlayQueue(remote) => {
+                let current = remote
+                    .current
+                    .as_ref()
+                    .and_then(|id| remote.entry.iter().position(|song| &song.id == id));
+                self.queue.emit(QueueIn::Restore(
+                    remote.entry,
+                    current,
+                    remote.position.unwrap_or_default(),
+                ));
+            }
             AppIn::SettingsWindow(msg) => match msg {
                 SettingsWindowOut::ClearCache => sender.input(AppIn::ClearCache),
                 SettingsWindowOut::Logout => sender.input(AppIn::Logout),
+                SettingsWindowOut::ReplayGainChanged => {
+                    self.playback.borrow_mut().sync_replay_gain();
+                }
+                SettingsWindowOut::DeleteOffline => {
+                    if let Err(e) = self.subsonic.borrow_mut().offline_mut().delete_cache() {
+                        sender.input(AppIn::DisplayToast(format!(
+                            "error while deleting offline songs: {e:?}"
+                        )));
+                    }
+                    self.settings_window.emit(SettingsWindowIn::OfflineSize(0));
+                }
             },
             AppIn::VolumeButton(msg) => match msg {
                 VolumeButtonOut::Player(cmd) => sender.input(AppIn::Player(cmd)),
@@ -1244,10 +2365,23 @@ impl relm4::component::AsyncComponent for App {
         tracing::info!("shutdown app");
 
         //save queue
-        if let Err(e) = self.queue.model().save() {
+        let position = self.seekbar.model().current() as i64;
+        if let Err(e) = self.queue.model().save(position) {
             tracing::error!("could not save queue: {e}");
         }
 
+        //save podcast positions
+        if let Err(e) = self.subsonic.borrow().podcasts().save() {
+            tracing::error!("could not save podcast positions: {e}");
+        }
+
+        //store the current play in history
+        if let Some(record) = self.active_play.take().map(ActivePlay::finish) {
+            if let Err(e) = self.subsonic.borrow_mut().history_mut().push(record) {
+                tracing::error!("could not store play in history: {e}");
+            }
+        }
+
         //save window state to settings
         let mut settings = Settings::get().lock().unwrap();
         settings.paned_position = widgets.paned.position();
@@ -1259,6 +2393,157 @@ impl relm4::component::AsyncComponent for App {
     }
 }
 
+/// returns the local file of a song if it is stored offline, otherwise the streaming url
+fn track_uri(
+    subsonic: &Rc<RefCell<Subsonic>>,
+    child: &submarine::data::Child,
+) -> anyhow::Result<String> {
+    if let Some(uri) = subsonic.borrow().offline().local_uri(&child.id) {
+        return Ok(uri);
+    }
+
+    let profile = StreamProfile::current();
+    let client = Client::get().unwrap();
+    let url = client.stream_url(
+        &child.id,
+        profile.max_bit_rate(),
+        Some(profile.format.parameter()),
+        None,
+        None::<&str>,
+        None,
+        None,
+    )?;
+    Ok(url.to_string())
+}
+
+/// fetches songs to append to the queue after the current song depending on mode
+async fn queue_continuation(
+    subsonic: &Rc<RefCell<Subsonic>>,
+    mode: &QueueEnd,
+    current: &submarine::data::Child,
+) -> anyhow::Result<Vec<submarine::data::Child>> {
+    let client = Client::get().unwrap();
+    match mode {
+        QueueEnd::Stop => Ok(vec![]),
+        QueueEnd::Similar => {
+            let Some(artist_id) = &current.artist_id else {
+                anyhow::bail!("song has no artist");
+            };
+            // fetch more than needed as already played songs are removed
+            Ok(client.get_similar_songs2(artist_id, Some(30)).await?)
+        }
+        QueueEnd::Genre => {
+            let Some(genre) = &current.genre else {
+                anyhow::bail!("song has no genre");
+            };
+            // the server filters a single folder; several folders are filtered with the cache
+            let music_folders = Settings::get().lock().unwrap().music_folders();
+            let folder = match music_folders.as_slice() {
+                [folder] => Some(folder.clone()),
+                _ => None,
+            };
+            let mut songs = client
+                .get_random_songs(Some(30), Some(genre), None, None, folder)
+                .await?;
+            if music_folders.len() > 1 {
+                let subsonic = subsonic.borrow();
+                songs.retain(|song| subsonic.find_track(&song.id).is_some());
+            }
+            Ok(songs)
+        }
+        QueueEnd::NextAlbum => {
+            let subsonic = subsonic.borrow();
+            let Some(album) = subsonic.album_of_song(current) else {
+                anyhow::bail!("album of song not found");
+            };
+
+            // discography of the artist ordered by release
+            let mut albums: Vec<&submarine::data::Child> = subsonic
+                .albums()
+                .iter()
+                .filter(|candidate| candidate.artist_id == album.artist_id)
+                .collect();
+            albums.sort_by(|a, b| a.year.cmp(&b.year).then(a.title.cmp(&b.title)));
+            let Some(next) = albums
+                .iter()
+                .skip_while(|candidate| candidate.id != album.id)
+                .nth(1)
+            else {
+                return Ok(vec![]);
+            };
+
+            let mut songs: Vec<submarine::data::Child> = subsonic
+                .tracks_from_album(next)
+                .into_iter()
+                .cloned()
+                .collect();
+            songs.sort_by(|a, b| {
+                a.disc_number
+                    .cmp(&b.disc_number)
+                    .then(a.track.cmp(&b.track))
+            });
+            Ok(songs)
+        }
+    }
+}
+
+/// returns the StreamProfile a song is played with
+fn stream_profile(
+    subsonic: &Rc<RefCell<Subsonic>>,
+    child: &submarine::data::Child,
+) -> StreamProfile {
+    match subsonic.borrow().offline().local_uri(&child.id) {
+        // offline files are stored in their original format
+        Some(_) => StreamProfile::default(),
+        None => StreamProfile::current(),
+    }
+}
+
+/// asks if the queue from the server should replace the local one
+fn show_play_queue_prompt(
+    sender: relm4::AsyncComponentSender<App>,
+    remote: submarine::data::PlayQueue,
+) {
+    let warning = format!(
+        "{} {} {}\n{}",
+        gettext("The queue on the server was changed by"),
+        remote.changed_by,
+        gettext("and differs from this queue."),
+        gettext("Continue with the queue from the server?"),
+    );
+
+    relm4::view! {
+        #[template]
+        window = WarningDialog {
+            #[template_child]
+            warning_text {
+                set_label: &warning,
+            },
+            #[template_child]
+            cancel_btn {
+                set_label: &gettext("Keep this queue"),
+            },
+            #[template_child]
+            proceed_btn {
+                set_label: &gettext("Use server queue"),
+            }
+        }
+    }
+
+    let win = window.clone();
+    window.cancel_btn.connect_clicked(move |_btn| {
+        win.close();
+    });
+
+    let win = window.clone();
+    window.proceed_btn.connect_clicked(move |_btn| {
+        win.close();
+        sender.input(AppIn::RestorePlayQueue(Box::new(remote.clone())));
+    });
+
+    window.dialog.show();
+}
+
 async fn show_desktop_notification(
     subsonic: &Rc<RefCell<Subsonic>>,
     child: submarine::data::Child,
@@ -1351,6 +2636,14 @@ fn reset_navigation_btns(widgets: &mut <App as relm4::component::AsyncComponent>
     widgets.album_rvl.set_reveal_child(false);
     widgets.tracks_rvl.set_reveal_child(false);
     widgets.playlists_rvl.set_reveal_child(false);
+    widgets.podcasts_rvl.set_reveal_child(false);
+    widgets.radio_rvl.set_reveal_child(false);
+    widgets.statistics_rvl.set_reveal_child(false);
+    widgets.history_rvl.set_reveal_child(false);
+    widgets.folders_rvl.set_reveal_child(false);
+    widgets.genres_rvl.set_reveal_child(false);
+    widgets.shares_rvl.set_reveal_child(false);
+    widgets.bookmarks_rvl.set_reveal_child(false);
 
     widgets.dashboard_btn.set_active(false);
     widgets.artists_btn.set_active(false);
@@ -1359,4 +2652,12 @@ fn reset_navigation_btns(widgets: &mut <App as relm4::component::AsyncComponent>
     widgets.album_btn.set_active(false);
     widgets.tracks_btn.set_active(false);
     widgets.playlists_btn.set_active(false);
+    widgets.podcasts_btn.set_active(false);
+    widgets.radio_btn.set_active(false);
+    widgets.statistics_btn.set_active(false);
+    widgets.history_btn.set_active(false);
+    widgets.folders_btn.set_active(false);
+    widgets.genres_btn.set_active(false);
+    widgets.shares_btn.set_active(false);
+    widgets.bookmarks_btn.set_active(false);
 }
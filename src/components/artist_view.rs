@@ -6,7 +6,7 @@ use rand::seq::IteratorRandom;
 use relm4::{
     gtk::{
         self, pango,
-        prelude::{BoxExt, ButtonExt, OrientableExt, ToValue, WidgetExt},
+        prelude::{BoxExt, ButtonExt, OrientableExt, ToValue, ToggleButtonExt, WidgetExt},
     },
     ComponentController, RelmWidgetExt,
 };
@@ -59,6 +59,8 @@ pub enum ArtistViewOut {
     FavoriteAlbumClicked(String, bool),
     FavoriteArtistClicked(String, bool),
     Download(Droppable),
+    Pin(Droppable, bool),
+    Share(Droppable),
 }
 
 #[derive(Debug)]
@@ -305,6 +307,21 @@ impl relm4::Component for ArtistView {
                                 connect_clicked[sender, artist] => move |_btn| {
                                     sender.output(ArtistViewOut::Download(Droppable::Artist(Box::new(artist.clone())))).unwrap();
                                 }
+                            },
+                            gtk::ToggleButton {
+                                set_active: subsonic.borrow().offline().is_pinned(&model.id),
+
+                                gtk::Box {
+                                    gtk::Image {
+                                        set_icon_name: Some("airplane-mode-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                },
+                                set_tooltip: &gettext("Keep all albums of this artist available offline"),
+                                connect_clicked[sender, artist] => move |btn| {
+                                    let drop = Droppable::Artist(Box::new(artist.clone()));
+                                    sender.output(ArtistViewOut::Pin(drop, btn.is_active())).unwrap();
+                                }
                             }
                         }
                     }
@@ -493,6 +510,7 @@ impl relm4::Component for ArtistView {
                 AlbumElementOut::FavoriteClicked(id, state) => sender
                     .output(ArtistViewOut::FavoriteAlbumClicked(id, state))
                     .unwrap(),
+                AlbumElementOut::Share(drop) => sender.output(ArtistViewOut::Share(drop)).unwrap(),
             },
             ArtistViewIn::Cover(msg) => match msg {
                 CoverOut::DisplayToast(title) => {
@@ -0,0 +1,243 @@
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, OrientableExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common,
+    factory::bookmark_row::{
+        ArtistColumn, BookmarkRow, ChangedColumn, CommentColumn, PositionColumn, TitleColumn,
+    },
+    settings::Settings,
+};
+
+#[derive(Debug)]
+pub struct BookmarksView {
+    bookmarks: relm4::typed_view::column::TypedColumnView<BookmarkRow, gtk::SingleSelection>,
+}
+
+impl BookmarksView {
+    fn selected(&self) -> Option<submarine::data::Bookmark> {
+        let index = self.bookmarks.selection_model.selected();
+        self.bookmarks
+            .get_visible(index)
+            .map(|row| row.borrow().item().clone())
+    }
+}
+
+#[derive(Debug)]
+pub enum BookmarksViewIn {
+    SearchChanged,
+    Reload,
+    Activated(u32),
+    PlayClicked,
+    DeleteClicked,
+}
+
+#[derive(Debug)]
+pub enum BookmarksViewOut {
+    DisplayToast(String),
+    /// song and position in ms to continue at
+    Play(Box<submarine::data::Child>, i64),
+    Changed,
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for BookmarksView {
+    type Init = ();
+    type Input = BookmarksViewIn;
+    type Output = BookmarksViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut bookmarks =
+            relm4::typed_view::column::TypedColumnView::<BookmarkRow, gtk::SingleSelection>::new();
+        bookmarks.append_column::<TitleColumn>();
+        bookmarks.append_column::<ArtistColumn>();
+        bookmarks.append_column::<PositionColumn>();
+        bookmarks.append_column::<CommentColumn>();
+        bookmarks.append_column::<ChangedColumn>();
+
+        let columns = bookmarks.get_columns();
+        columns
+            .get("Title")
+            .unwrap()
+            .set_title(Some(&gettext("Title")));
+        columns
+            .get("Artist")
+            .unwrap()
+            .set_title(Some(&gettext("Artist")));
+        columns
+            .get("Position")
+            .unwrap()
+            .set_title(Some(&gettext("Position")));
+        columns
+            .get("Comment")
+            .unwrap()
+            .set_title(Some(&gettext("Comment")));
+        columns
+            .get("Changed")
+            .unwrap()
+            .set_title(Some(&gettext("Changed")));
+
+        // add search filter
+        bookmarks.add_filter(move |row| {
+            let search = Settings::get().lock().unwrap().search_text.clone();
+            let entry = &row.item().entry;
+            let test = format!(
+                "{} {}",
+                entry.title,
+                entry.artist.as_deref().unwrap_or_default()
+            );
+            common::search_matching(test, search)
+        });
+
+        let model = Self { bookmarks };
+
+        let widgets = view_output!();
+
+        sender.input(BookmarksViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            add_css_class: "tracks-view",
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 8,
+
+            // info
+            gtk::WindowHandle {
+                gtk::CenterBox {
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 8,
+                        set_margin_horizontal: 7,
+
+                        append: bookmark_count = &gtk::Label {
+                            set_halign: gtk::Align::Start,
+                        },
+                        gtk::Box {
+                            set_spacing: 15,
+
+                            #[name = "play_btn"]
+                            gtk::Button {
+                                set_icon_name: "media-playback-start-symbolic",
+                                set_tooltip: &gettext("Continue playing at selected bookmark"),
+                                connect_clicked => BookmarksViewIn::PlayClicked,
+                            },
+                            #[name = "delete_btn"]
+                            gtk::Button {
+                                set_icon_name: "user-trash-symbolic",
+                                set_tooltip: &gettext("Delete selected bookmark from server"),
+                                connect_clicked => BookmarksViewIn::DeleteClicked,
+                            },
+                        }
+                    },
+                }
+            },
+
+            gtk::ScrolledWindow {
+                set_hexpand: true,
+                set_vexpand: true,
+
+                model.bookmarks.view.clone() {
+                    set_widget_name: "bookmarks-view-bookmarks",
+                    set_vexpand: true,
+
+                    connect_activate[sender] => move |_column_view, index| {
+                        sender.input(BookmarksViewIn::Activated(index));
+                    },
+                }
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            BookmarksViewIn::SearchChanged => _ = self.bookmarks.notify_filter_changed(0),
+            BookmarksViewIn::Reload => {
+                let client = Client::get().unwrap();
+                let bookmarks = match client.get_bookmarks().await {
+                    Ok(bookmarks) => bookmarks,
+                    Err(e) => {
+                        sender
+                            .output(BookmarksViewOut::DisplayToast(format!(
+                                "could not fetch bookmarks: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+
+                self.bookmarks.clear();
+                for bookmark in bookmarks {
+                    self.bookmarks.append(BookmarkRow::new(bookmark));
+                }
+
+                widgets.bookmark_count.set_label(&format!(
+                    "{}: {}",
+                    gettext("Bookmarks"),
+                    self.bookmarks.len()
+                ));
+                let has_bookmarks = !self.bookmarks.is_empty();
+                widgets.play_btn.set_sensitive(has_bookmarks);
+                widgets.delete_btn.set_sensitive(has_bookmarks);
+            }
+            BookmarksViewIn::Activated(index) => {
+                if let Some(row) = self.bookmarks.get_visible(index) {
+                    let bookmark = row.borrow().item().clone();
+                    sender
+                        .output(BookmarksViewOut::Play(
+                            Box::new(bookmark.entry),
+                            bookmark.position,
+                        ))
+                        .unwrap();
+                }
+            }
+            BookmarksViewIn::PlayClicked => {
+                if let Some(bookmark) = self.selected() {
+                    sender
+                        .output(BookmarksViewOut::Play(
+                            Box::new(bookmark.entry),
+                            bookmark.position,
+                        ))
+                        .unwrap();
+                }
+            }
+            BookmarksViewIn::DeleteClicked => {
+                let Some(bookmark) = self.selected() else {
+                    return;
+                };
+                let client = Client::get().unwrap();
+                if let Err(e) = client.delete_bookmark(&bookmark.entry.id).await {
+                    sender
+                        .output(BookmarksViewOut::DisplayToast(format!(
+                            "could not delete bookmark: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                sender.output(BookmarksViewOut::Changed).unwrap();
+                sender.input(BookmarksViewIn::Reload);
+            }
+        }
+    }
+}
@@ -22,8 +22,16 @@ use crate::{
         albums_view::{AlbumsView, AlbumsViewIn, AlbumsViewOut},
         artist_view::{ArtistView, ArtistViewIn, ArtistViewOut},
         artists_view::{ArtistsView, ArtistsViewIn, ArtistsViewOut},
+        bookmarks_view::{BookmarksView, BookmarksViewIn, BookmarksViewOut},
         dashboard::{Dashboard, DashboardIn, DashboardOut},
+        folders_view::{FoldersView, FoldersViewIn, FoldersViewOut},
+        genres_view::{GenresView, GenresViewIn, GenresViewOut},
+        history_view::{HistoryView, HistoryViewIn, HistoryViewOut},
         playlists_view::{PlaylistsView, PlaylistsViewIn, PlaylistsViewOut},
+        podcasts_view::{PodcastsView, PodcastsViewIn, PodcastsViewOut},
+        radio_view::{RadioView, RadioViewIn, RadioViewOut},
+        shares_view::{SharesView, SharesViewIn, SharesViewOut},
+        statistics_view::{StatisticsView, StatisticsViewIn, StatisticsViewOut},
         tracks_view::{TracksView, TracksViewIn, TracksViewOut},
     },
     subsonic::Subsonic,
@@ -38,6 +46,14 @@ pub enum Views {
     Album(gtk::Box),
     Tracks(gtk::Box),
     Playlists(gtk::Box),
+    Radio(gtk::Box),
+    Podcasts(gtk::Box),
+    Statistics(gtk::Box),
+    History(gtk::Box),
+    Folders(gtk::Box),
+    Genres(gtk::Box),
+    Shares(gtk::Box),
+    Bookmarks(gtk::Box),
 }
 
 impl Views {
@@ -49,7 +65,15 @@ impl Views {
             | Self::Albums(w)
             | Self::Album(w)
             | Self::Tracks(w)
-            | Self::Playlists(w) => w,
+            | Self::Playlists(w)
+            | Self::Radio(w)
+            | Self::Podcasts(w)
+            | Self::Statistics(w)
+            | Self::History(w)
+            | Self::Folders(w)
+            | Self::Genres(w)
+            | Self::Shares(w)
+            | Self::Bookmarks(w) => w,
         }
     }
 }
@@ -67,6 +91,14 @@ pub struct Browser {
     album_views: Vec<relm4::Controller<AlbumView>>,
     artist_views: Vec<relm4::Controller<ArtistView>>,
     playlists_views: Vec<relm4::component::AsyncController<PlaylistsView>>,
+    radio: Option<relm4::component::AsyncController<RadioView>>,
+    podcasts: Option<relm4::component::AsyncController<PodcastsView>>,
+    statistics: Option<relm4::component::AsyncController<StatisticsView>>,
+    history: Option<relm4::component::AsyncController<HistoryView>>,
+    folders: Option<relm4::component::AsyncController<FoldersView>>,
+    genres: Option<relm4::component::AsyncController<GenresView>>,
+    shares: Option<relm4::component::AsyncController<SharesView>>,
+    bookmarks: Option<relm4::component::AsyncController<BookmarksView>>,
 }
 
 #[derive(Debug)]
@@ -79,6 +111,14 @@ pub enum BrowserIn {
     ShowAlbums,
     ShowTracks,
     ShowPlaylists,
+    ShowRadio,
+    ShowPodcasts,
+    ShowStatistics,
+    ShowHistory,
+    ShowFolders,
+    ShowGenres,
+    ShowShares,
+    ShowBookmarks,
     ShowAlbum(Id),
     Dashboard(DashboardOut),
     AlbumsView(AlbumsViewOut),
@@ -87,14 +127,30 @@ pub enum BrowserIn {
     ArtistsView(ArtistsViewOut),
     ArtistView(Box<ArtistViewOut>),
     PlaylistsView(PlaylistsViewOut),
+    RadioView(RadioViewOut),
+    PodcastsView(PodcastsViewOut),
+    StatisticsView(StatisticsViewOut),
+    HistoryView(HistoryViewOut),
+    FoldersView(FoldersViewOut),
+    GenresView(GenresViewOut),
+    SharesView(SharesViewOut),
+    /// a share was created outside of the view
+    SharesChanged,
+    BookmarksView(BookmarksViewOut),
+    /// bookmarks were changed outside of the view
+    BookmarksChanged,
     RenamePlaylist(submarine::data::Playlist),
     NewPlaylist(String, Vec<submarine::data::Child>),
     UpdateFavoriteAlbum(String, bool),
     UpdateFavoriteArtist(String, bool),
     UpdateFavoriteSong(String, bool),
+    UpdateRatingAlbum(String, Option<i32>),
+    UpdateRatingSong(String, Option<i32>),
     UpdatePlayCountSong(String, Option<i64>),
     UpdatePlayCountAlbum(String, Option<i64>),
     InsertSongsToPlaylist(u32, Vec<submarine::data::Child>),
+    /// a play was added to the local history
+    NewPlay,
 }
 
 #[derive(Debug)]
@@ -107,8 +163,16 @@ pub enum BrowserOut {
     FavoriteAlbumClicked(String, bool),
     FavoriteArtistClicked(String, bool),
     FavoriteSongClicked(String, bool),
+    RatingAlbumClicked(String, i32),
+    RatingSongClicked(String, i32),
     Download(Droppable),
+    Pin(Droppable, bool),
+    Share(Droppable),
+    PlayRadio(Box<submarine::data::InternetRadioStation>),
+    PlayBookmark(Box<submarine::data::Child>, i64),
+    BookmarksChanged,
     ChangedViewTo(views::Views),
+    OpenRandomMix,
 }
 
 #[relm4::component(async, pub)]
@@ -137,6 +201,14 @@ impl relm4::component::AsyncComponent for Browser {
             album_views: vec![],
             artist_views: vec![],
             playlists_views: vec![],
+            radio: None,
+            podcasts: None,
+            statistics: None,
+            history: None,
+            folders: None,
+            genres: None,
+            shares: None,
+            bookmarks: None,
         };
         let widgets = view_output!();
 
@@ -182,6 +254,30 @@ impl relm4::component::AsyncComponent for Browser {
                 for view in &self.playlists_views {
                     view.emit(PlaylistsViewIn::SearchChanged);
                 }
+                if let Some(radio) = &self.radio {
+                    radio.emit(RadioViewIn::SearchChanged);
+                }
+                if let Some(podcasts) = &self.podcasts {
+                    podcasts.emit(PodcastsViewIn::SearchChanged);
+                }
+                if let Some(statistics) = &self.statistics {
+                    statistics.emit(StatisticsViewIn::SearchChanged);
+                }
+                if let Some(history) = &self.history {
+                    history.emit(HistoryViewIn::SearchChanged);
+                }
+                if let Some(folders) = &self.folders {
+                    folders.emit(FoldersViewIn::SearchChanged);
+                }
+                if let Some(genres) = &self.genres {
+                    genres.emit(GenresViewIn::SearchChanged);
+                }
+                if let Some(shares) = &self.shares {
+                    shares.emit(SharesViewIn::SearchChanged);
+                }
+                if let Some(bookmarks) = &self.bookmarks {
+                    bookmarks.emit(BookmarksViewIn::SearchChanged);
+                }
             }
             BrowserIn::GoBack => {
                 if self.history_widget.len() > 1 {
@@ -193,7 +289,15 @@ impl relm4::component::AsyncComponent for Browser {
                             Views::Dashboard(_)
                             | Views::Artists(_)
                             | Views::Albums(_)
-                            | Views::Tracks(_) => {}
+                            | Views::Tracks(_)
+                            | Views::Radio(_)
+                            | Views::Podcasts(_)
+                            | Views::Statistics(_)
+                            | Views::History(_)
+                            | Views::Folders(_)
+                            | Views::Genres(_)
+                            | Views::Shares(_)
+                            | Views::Bookmarks(_) => {}
                             // these are not
                             Views::Artist(_) => _ = self.artist_views.pop(),
                             Views::Album(_) => _ = self.album_views.pop(),
@@ -385,6 +489,221 @@ impl relm4::component::AsyncComponent for Browser {
                     .output(BrowserOut::BackButtonSensitivity(true))
                     .unwrap();
             }
+            BrowserIn::ShowRadio => {
+                if let Some(&Views::Radio(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.radio.is_none() {
+                    self.radio = Some(
+                        RadioView::builder()
+                            .launch(())
+                            .forward(sender.input_sender(), BrowserIn::RadioView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Radio,
+                    )))
+                    .unwrap();
+                self.history_widget
+                    .push(Views::Radio(self.radio.as_ref().unwrap().widget().clone()));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowPodcasts => {
+                if let Some(&Views::Podcasts(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.podcasts.is_none() {
+                    self.podcasts = Some(
+                        PodcastsView::builder()
+                            .launch(self.subsonic.clone())
+                            .forward(sender.input_sender(), BrowserIn::PodcastsView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Podcasts,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::Podcasts(
+                    self.podcasts.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowBookmarks => {
+                if let Some(&Views::Bookmarks(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.bookmarks.is_none() {
+                    self.bookmarks = Some(
+                        BookmarksView::builder()
+                            .launch(())
+                            .forward(sender.input_sender(), BrowserIn::BookmarksView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Bookmarks,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::Bookmarks(
+                    self.bookmarks.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowShares => {
+                if let Some(&Views::Shares(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.shares.is_none() {
+                    self.shares = Some(
+                        SharesView::builder()
+                            .launch(())
+                            .forward(sender.input_sender(), BrowserIn::SharesView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Shares,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::Shares(
+                    self.shares.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowGenres => {
+                if let Some(&Views::Genres(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.genres.is_none() {
+                    self.genres = Some(
+                        GenresView::builder()
+                            .launch(self.subsonic.clone())
+                            .forward(sender.input_sender(), BrowserIn::GenresView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Genres,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::Genres(
+                    self.genres.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowFolders => {
+                if let Some(&Views::Folders(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.folders.is_none() {
+                    self.folders = Some(
+                        FoldersView::builder()
+                            .launch(())
+                            .forward(sender.input_sender(), BrowserIn::FoldersView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Folders,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::Folders(
+                    self.folders.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowHistory => {
+                if let Some(&Views::History(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.history.is_none() {
+                    self.history = Some(
+                        HistoryView::builder()
+                            .launch(self.subsonic.clone())
+                            .forward(sender.input_sender(), BrowserIn::HistoryView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::History,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::History(
+                    self.history.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
+            BrowserIn::ShowStatistics => {
+                if let Some(&Views::Statistics(_)) = self.history_widget.last() {
+                    return;
+                }
+
+                if self.statistics.is_none() {
+                    self.statistics = Some(
+                        StatisticsView::builder()
+                            .launch(self.subsonic.clone())
+                            .forward(sender.input_sender(), BrowserIn::StatisticsView),
+                    );
+                }
+
+                sender
+                    .output(BrowserOut::ChangedViewTo(views::Views::Clickable(
+                        views::ClickableViews::Statistics,
+                    )))
+                    .unwrap();
+                self.history_widget.push(Views::Statistics(
+                    self.statistics.as_ref().unwrap().widget().clone(),
+                ));
+                self.content
+                    .set_child(Some(self.history_widget.last().unwrap().widget()));
+                sender
+                    .output(BrowserOut::BackButtonSensitivity(true))
+                    .unwrap();
+            }
             BrowserIn::Dashboard(output) => match output {
                 DashboardOut::ClickedAlbum(id) => {
                     match self.subsonic.borrow().find_album(id.as_ref()) {
@@ -404,6 +723,8 @@ impl relm4::component::AsyncComponent for Browser {
                 DashboardOut::FavoriteClicked(id, state) => sender
                     .output(BrowserOut::FavoriteAlbumClicked(id, state))
                     .unwrap(),
+                DashboardOut::Share(drop) => sender.output(BrowserOut::Share(drop)).unwrap(),
+                DashboardOut::OpenRandomMix => sender.output(BrowserOut::OpenRandomMix).unwrap(),
             },
             BrowserIn::AlbumsView(msg) => match msg {
                 AlbumsViewOut::ClickedAlbum(id) => {
@@ -430,6 +751,9 @@ impl relm4::component::AsyncComponent for Browser {
                 AlbumsViewOut::FavoriteClicked(id, state) => sender
                     .output(BrowserOut::FavoriteAlbumClicked(id, state))
                     .unwrap(),
+                AlbumsViewOut::RatingClicked(id, rating) => sender
+                    .output(BrowserOut::RatingAlbumClicked(id, rating))
+                    .unwrap(),
                 AlbumsViewOut::AddToQueue(drop) => sender
                     .output(BrowserOut::InsertAfterCurrentInQueue(drop))
                     .unwrap(),
@@ -478,7 +802,13 @@ impl relm4::component::AsyncComponent for Browser {
                 AlbumViewOut::FavoriteSongClicked(id, state) => sender
                     .output(BrowserOut::FavoriteSongClicked(id, state))
                     .unwrap(),
+                AlbumViewOut::RatingAlbumClicked(id, rating) => sender
+                    .output(BrowserOut::RatingAlbumClicked(id, rating))
+                    .unwrap(),
                 AlbumViewOut::Download(drop) => sender.output(BrowserOut::Download(drop)).unwrap(),
+                AlbumViewOut::Pin(drop, state) => {
+                    sender.output(BrowserOut::Pin(drop, state)).unwrap();
+                }
                 AlbumViewOut::ArtistClicked(id) => sender.input(BrowserIn::ShowArtist(id)),
             },
             BrowserIn::ArtistView(msg) => match *msg {
@@ -519,6 +849,10 @@ impl relm4::component::AsyncComponent for Browser {
                     .output(BrowserOut::FavoriteArtistClicked(id, state))
                     .unwrap(),
                 ArtistViewOut::Download(drop) => sender.output(BrowserOut::Download(drop)).unwrap(),
+                ArtistViewOut::Share(drop) => sender.output(BrowserOut::Share(drop)).unwrap(),
+                ArtistViewOut::Pin(drop, state) => {
+                    sender.output(BrowserOut::Pin(drop, state)).unwrap();
+                }
             },
             BrowserIn::TracksView(msg) => match msg {
                 TracksViewOut::DisplayToast(msg) => {
@@ -534,31 +868,63 @@ impl relm4::component::AsyncComponent for Browser {
                     sender.output(BrowserOut::ReplaceQueue(drop)).unwrap();
                 }
                 TracksViewOut::Download(drop) => sender.output(BrowserOut::Download(drop)).unwrap(),
+                TracksViewOut::Share(drop) => sender.output(BrowserOut::Share(drop)).unwrap(),
                 TracksViewOut::FavoriteClicked(id, state) => sender
                     .output(BrowserOut::FavoriteSongClicked(id, state))
                     .unwrap(),
+                TracksViewOut::RatingClicked(id, rating) => sender
+                    .output(BrowserOut::RatingSongClicked(id, rating))
+                    .unwrap(),
                 TracksViewOut::ClickedArtist(id) => sender.input(BrowserIn::ShowArtist(id)),
                 TracksViewOut::ClickedAlbum(id) => sender.input(BrowserIn::ShowAlbum(id)),
                 TracksViewOut::CreatePlaylist(name, tracks) => {
                     sender.input(BrowserIn::NewPlaylist(name, tracks))
                 }
+                TracksViewOut::CreateSmartPlaylist(mut list) => {
+                    if list.mirror {
+                        let songs = list.evaluate(self.subsonic.borrow().tracks());
+                        match list.mirror(&songs).await {
+                            Ok(playlist) => {
+                                self.subsonic.borrow_mut().push_playlist(&playlist);
+                                for view in &self.playlists_views {
+                                    view.emit(PlaylistsViewIn::NewPlaylist(playlist.clone()));
+                                }
+                            }
+                            // mirroring is tried again on the next sync
+                            Err(e) => sender
+                                .output(BrowserOut::DisplayToast(format!(
+                                    "could not mirror smart playlist to server: {e:?}"
+                                )))
+                                .unwrap(),
+                        }
+                    }
+
+                    let name = list.name.clone();
+                    self.subsonic.borrow_mut().smart_playlists_mut().push(list);
+                    for view in &self.playlists_views {
+                        view.emit(PlaylistsViewIn::SmartPlaylistsChanged);
+                    }
+                    sender
+                        .output(BrowserOut::DisplayToast(format!(
+                            "{}: {name}",
+                            gettext("Created a new smart playlist")
+                        )))
+                        .unwrap();
+                }
             },
             BrowserIn::PlaylistsView(msg) => match msg {
                 PlaylistsViewOut::DisplayToast(title) => {
                     sender.output(BrowserOut::DisplayToast(title)).unwrap();
                 }
-                PlaylistsViewOut::AppendToQueue(list) => {
-                    let drop = Droppable::Playlist(Box::new(list));
+                PlaylistsViewOut::AppendToQueue(drop) => {
                     sender.output(BrowserOut::AppendToQueue(drop)).unwrap();
                 }
-                PlaylistsViewOut::AddToQueue(list) => {
-                    let drop = Droppable::Playlist(Box::new(list));
+                PlaylistsViewOut::AddToQueue(drop) => {
                     sender
                         .output(BrowserOut::InsertAfterCurrentInQueue(drop))
                         .unwrap();
                 }
-                PlaylistsViewOut::ReplaceQueue(list) => {
-                    let drop = Droppable::Playlist(Box::new(list));
+                PlaylistsViewOut::ReplaceQueue(drop) => {
                     sender.output(BrowserOut::ReplaceQueue(drop)).unwrap();
                 }
                 PlaylistsViewOut::DeletePlaylist(index, list) => {
@@ -581,6 +947,16 @@ impl relm4::component::AsyncComponent for Browser {
                         view.emit(PlaylistsViewIn::DeletePlaylist(index.clone()));
                     }
                 }
+                PlaylistsViewOut::DeleteSmartPlaylist(index) => {
+                    // a mirrored playlist on the server is kept as a normal playlist
+                    self.subsonic
+                        .borrow_mut()
+                        .smart_playlists_mut()
+                        .remove(index);
+                    for view in &self.playlists_views {
+                        view.emit(PlaylistsViewIn::SmartPlaylistsChanged);
+                    }
+                }
                 PlaylistsViewOut::CreateEmptyPlaylist => {
                     sender.input(BrowserIn::NewPlaylist(gettext("New Playlist"), vec![]));
                 }
@@ -592,6 +968,10 @@ impl relm4::component::AsyncComponent for Browser {
                 PlaylistsViewOut::Download(drop) => {
                     sender.output(BrowserOut::Download(drop)).unwrap();
                 }
+                PlaylistsViewOut::Pin(drop, state) => {
+                    sender.output(BrowserOut::Pin(drop, state)).unwrap();
+                }
+                PlaylistsViewOut::Share(drop) => sender.output(BrowserOut::Share(drop)).unwrap(),
                 PlaylistsViewOut::FavoriteClicked(id, state) => sender
                     .output(BrowserOut::FavoriteSongClicked(id, state))
                     .unwrap(),
@@ -601,6 +981,102 @@ impl relm4::component::AsyncComponent for Browser {
                     sender.input(BrowserIn::RenamePlaylist(list))
                 }
             },
+            BrowserIn::RadioView(msg) => match msg {
+                RadioViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+                RadioViewOut::Play(station) => {
+                    sender.output(BrowserOut::PlayRadio(station)).unwrap();
+                }
+            },
+            BrowserIn::PodcastsView(msg) => match msg {
+                PodcastsViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+                PodcastsViewOut::AppendToQueue(drop) => {
+                    sender.output(BrowserOut::AppendToQueue(drop)).unwrap();
+                }
+                PodcastsViewOut::AddToQueue(drop) => sender
+                    .output(BrowserOut::InsertAfterCurrentInQueue(drop))
+                    .unwrap(),
+                PodcastsViewOut::ReplaceQueue(drop) => {
+                    sender.output(BrowserOut::ReplaceQueue(drop)).unwrap();
+                }
+            },
+            BrowserIn::GenresView(msg) => match msg {
+                GenresViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+                GenresViewOut::ClickedAlbum(id) => sender.input(BrowserIn::ShowAlbum(id)),
+                GenresViewOut::AppendToQueue(drop) => {
+                    sender.output(BrowserOut::AppendToQueue(drop)).unwrap();
+                }
+                GenresViewOut::AddToQueue(drop) => sender
+                    .output(BrowserOut::InsertAfterCurrentInQueue(drop))
+                    .unwrap(),
+                GenresViewOut::ReplaceQueue(drop) => {
+                    sender.output(BrowserOut::ReplaceQueue(drop)).unwrap();
+                }
+            },
+            BrowserIn::StatisticsView(msg) => match msg {
+                StatisticsViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+            },
+            BrowserIn::HistoryView(msg) => match msg {
+                HistoryViewOut::AppendToQueue(drop) => {
+                    sender.output(BrowserOut::AppendToQueue(drop)).unwrap();
+                }
+                HistoryViewOut::AddToQueue(drop) => sender
+                    .output(BrowserOut::InsertAfterCurrentInQueue(drop))
+                    .unwrap(),
+                HistoryViewOut::ReplaceQueue(drop) => {
+                    sender.output(BrowserOut::ReplaceQueue(drop)).unwrap();
+                }
+            },
+            BrowserIn::FoldersView(msg) => match msg {
+                FoldersViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+                FoldersViewOut::AppendToQueue(drop) => {
+                    sender.output(BrowserOut::AppendToQueue(drop)).unwrap();
+                }
+                FoldersViewOut::AddToQueue(drop) => sender
+                    .output(BrowserOut::InsertAfterCurrentInQueue(drop))
+                    .unwrap(),
+                FoldersViewOut::ReplaceQueue(drop) => {
+                    sender.output(BrowserOut::ReplaceQueue(drop)).unwrap();
+                }
+                FoldersViewOut::Download(drop) => {
+                    sender.output(BrowserOut::Download(drop)).unwrap()
+                }
+            },
+            BrowserIn::BookmarksView(msg) => match msg {
+                BookmarksViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+                BookmarksViewOut::Play(child, position) => sender
+                    .output(BrowserOut::PlayBookmark(child, position))
+                    .unwrap(),
+                BookmarksViewOut::Changed => {
+                    sender.output(BrowserOut::BookmarksChanged).unwrap();
+                }
+            },
+            BrowserIn::SharesView(msg) => match msg {
+                SharesViewOut::DisplayToast(title) => {
+                    sender.output(BrowserOut::DisplayToast(title)).unwrap();
+                }
+            },
+            BrowserIn::SharesChanged => {
+                if let Some(shares) = &self.shares {
+                    shares.emit(SharesViewIn::Reload);
+                }
+            }
+            BrowserIn::BookmarksChanged => {
+                if let Some(bookmarks) = &self.bookmarks {
+                    bookmarks.emit(BookmarksViewIn::Reload);
+                }
+            }
             BrowserIn::RenamePlaylist(list) => {
                 // change server
                 let client = Client::get().unwrap();
@@ -733,6 +1209,20 @@ impl relm4::component::AsyncComponent for Browser {
                     view.emit(ArtistViewIn::UpdateFavoriteAlbum(id.clone(), state));
                 }
             }
+            BrowserIn::UpdateRatingAlbum(id, rating) => {
+                //notify all views with album ratings in them
+                if let Some(albums) = &self.albums {
+                    albums.emit(AlbumsViewIn::UpdateRatingAlbum(id.clone(), rating));
+                }
+                for view in &self.album_views {
+                    view.emit(AlbumViewIn::UpdateRatingAlbum(id.clone(), rating));
+                }
+            }
+            BrowserIn::UpdateRatingSong(id, rating) => {
+                if let Some(tracks) = &self.tracks {
+                    tracks.emit(TracksViewIn::UpdateRatingSong(id, rating));
+                }
+            }
             BrowserIn::UpdateFavoriteArtist(id, state) => {
                 //notify all views with artists in them
                 for view in &self.artist_views {
@@ -763,6 +1253,14 @@ impl relm4::component::AsyncComponent for Browser {
                     view.emit(PlaylistsViewIn::InsertSongsTo(index, songs.clone()));
                 }
             }
+            BrowserIn::NewPlay => {
+                if let Some(history) = &self.history {
+                    history.emit(HistoryViewIn::NewPlay);
+                }
+                if let Some(statistics) = &self.statistics {
+                    statistics.emit(StatisticsViewIn::Reload);
+                }
+            }
         }
     }
 }
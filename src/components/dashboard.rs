@@ -14,7 +14,7 @@ use relm4::{
 
 use crate::{
     client::Client,
-    common::types::Id,
+    common::types::{Droppable, Id},
     factory::album_element::{
         get_info_of_flowboxchild, AlbumElement, AlbumElementIn, AlbumElementOut,
     },
@@ -50,6 +50,8 @@ pub enum DashboardOut {
     ClickedAlbum(Id),
     DisplayToast(String),
     FavoriteClicked(String, bool),
+    Share(Droppable),
+    OpenRandomMix,
 }
 
 #[derive(Debug)]
@@ -57,6 +59,7 @@ pub enum DashboardIn {
     FilterChanged,
     AlbumElement(AlbumElementOut),
     ClickedRandomize,
+    ClickedRandomMix,
     UpdateFavoriteAlbum(String, bool),
     ScrollOuter(f64),
     UpdateRecentlyPlayed,
@@ -400,6 +403,11 @@ impl relm4::Component for Dashboard {
                                     set_icon_name: "media-playlist-shuffle-symbolic",
                                     set_tooltip: &gettext("Rerandomize albums"),
                                     connect_clicked => DashboardIn::ClickedRandomize,
+                                },
+                                gtk::Button {
+                                    set_label: &gettext("Random mix"),
+                                    set_tooltip: &gettext("Create a mix of random songs by genre, year and folder"),
+                                    connect_clicked => DashboardIn::ClickedRandomMix,
                                 }
                             },
                             #[wrap(Some)]
@@ -586,6 +594,7 @@ impl relm4::Component for Dashboard {
                 AlbumElementOut::FavoriteClicked(id, state) => sender
                     .output(DashboardOut::FavoriteClicked(id, state))
                     .unwrap(),
+                AlbumElementOut::Share(drop) => sender.output(DashboardOut::Share(drop)).unwrap(),
             },
             DashboardIn::ClickedRandomize => {
                 self.random_album_list.guard().clear();
@@ -602,6 +611,7 @@ impl relm4::Component for Dashboard {
                 ids.into_iter()
                     .for_each(|id| _ = guard.push_back((self.subsonic.clone(), id)));
             }
+            DashboardIn::ClickedRandomMix => sender.output(DashboardOut::OpenRandomMix).unwrap(),
             DashboardIn::UpdateFavoriteAlbum(id, state) => {
                 self.recently_added_list
                     .broadcast(AlbumElementIn::Favorited(id.clone(), state));
@@ -624,7 +634,17 @@ impl relm4::Component for Dashboard {
                 }
             }
             DashboardIn::UpdateRecentlyPlayed => {
-                let dashboard_line_items = Settings::get().lock().unwrap().dashboard_line_items;
+                let settings = Settings::get().lock().unwrap();
+                let dashboard_line_items = settings.dashboard_line_items;
+                let music_folders = settings.music_folders();
+                drop(settings);
+                // the server filters a single folder; several folders are filtered with the cache,
+                // so fetch more than needed
+                let (folder, size) = match music_folders.as_slice() {
+                    [] => (None, dashboard_line_items),
+                    [folder] => (Some(folder.clone()), dashboard_line_items),
+                    _ => (None, dashboard_line_items * 4),
+                };
                 sender.oneshot_command(async move {
                     let client = match Client::get() {
                         None => return DashboardCmd::Error(String::from("no client found")),
@@ -634,9 +654,9 @@ impl relm4::Component for Dashboard {
                         client
                             .get_album_list2(
                                 submarine::api::get_album_list::Order::Recent,
-                                Some(dashboard_line_items),
+                                Some(size),
                                 None,
-                                None::<String>,
+                                folder,
                             )
                             .await,
                     )
@@ -655,7 +675,14 @@ impl relm4::Component for Dashboard {
         match msg {
             DashboardCmd::Error(msg) => sender.output(DashboardOut::DisplayToast(msg)).unwrap(),
             DashboardCmd::LoadedRecentlyPlayed(Err(_e)) => {}
-            DashboardCmd::LoadedRecentlyPlayed(Ok(list)) => {
+            DashboardCmd::LoadedRecentlyPlayed(Ok(mut list)) => {
+                // the cache only contains albums of the selected music folders
+                if Settings::get().lock().unwrap().music_folders().len() > 1 {
+                    let subsonic = self.subsonic.borrow();
+                    list.retain(|album| subsonic.find_album(&album.id).is_some());
+                    list.truncate(Settings::get().lock().unwrap().dashboard_line_items);
+                }
+
                 if list.is_empty() {
                     widgets
                         .recently_stack
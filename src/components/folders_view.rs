@@ -0,0 +1,388 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, OrientableExt, SelectionModelExt, WidgetExt},
+    },
+    typed_view::column::TypedColumnView,
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common::{self, types::Droppable},
+    factory::folder_row::{ArtistColumn, FolderItem, FolderRow, LengthColumn, NameColumn},
+    settings::Settings,
+};
+
+#[derive(Debug)]
+pub struct FoldersView {
+    entries: TypedColumnView<FolderRow, gtk::MultiSelection>,
+    crumbs: gtk::Box,
+    /// id and name of the opened directories, empty when showing the root
+    path: Vec<(String, String)>,
+    /// directories that were fetched already, they are only loaded when needed
+    directories: HashMap<String, submarine::data::Directory>,
+}
+
+impl FoldersView {
+    async fn fetch_directory(&mut self, id: &str) -> anyhow::Result<submarine::data::Directory> {
+        if let Some(dir) = self.directories.get(id) {
+            return Ok(dir.clone());
+        }
+
+        let client = Client::get().unwrap();
+        let dir = client.get_music_directory(id).await?;
+        self.directories.insert(id.to_string(), dir.clone());
+        Ok(dir)
+    }
+
+    /// fetches the entries of the root of all selected music folders
+    async fn fetch_root() -> anyhow::Result<Vec<FolderRow>> {
+        let client = Client::get().unwrap();
+        let mut folders: Vec<Option<String>> = Settings::get()
+            .lock()
+            .unwrap()
+            .music_folders()
+            .into_iter()
+            .map(Some)
+            .collect();
+        if folders.is_empty() {
+            folders.push(None);
+        }
+
+        let mut ids = HashSet::new();
+        let mut rows = vec![];
+        for folder in folders {
+            let indexes = client.get_indexes(folder, None).await?;
+            let dirs = indexes
+                .shortcut
+                .into_iter()
+                .chain(indexes.index.into_iter().flat_map(|index| index.artist));
+            for dir in dirs {
+                if ids.insert(dir.id.clone()) {
+                    rows.push(FolderRow::directory(dir.id, dir.name));
+                }
+            }
+            for child in indexes.child {
+                if ids.insert(child.id.clone()) {
+                    rows.push(FolderRow::from_child(child));
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// returns the directory and all songs in it and its subdirectories
+    async fn collect_songs(
+        &mut self,
+        id: &str,
+    ) -> anyhow::Result<(submarine::data::Directory, Vec<submarine::data::Child>)> {
+        let root = self.fetch_directory(id).await?;
+        let mut songs = vec![];
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(dir) = queue.pop_front() {
+            for child in dir.child {
+                if child.is_dir {
+                    queue.push_back(self.fetch_directory(&child.id).await?);
+                } else {
+                    songs.push(child);
+                }
+            }
+        }
+        Ok((root, songs))
+    }
+
+    /// returns the selected entries or the opened directory when nothing is selected
+    async fn selected(&mut self) -> anyhow::Result<Option<Droppable>> {
+        let model = self.entries.view.model().unwrap();
+        let items: Vec<FolderItem> = (0..self.entries.selection_model.n_items())
+            .filter(|i| model.is_selected(*i))
+            .filter_map(|i| self.entries.get_visible(i))
+            .map(|row| row.borrow().item().clone())
+            .collect();
+
+        match items.as_slice() {
+            [] => {
+                let Some((id, _name)) = self.path.last().cloned() else {
+                    return Ok(None);
+                };
+                let (dir, songs) = self.collect_songs(&id).await?;
+                Ok(Some(Droppable::Directory(Box::new(dir), songs)))
+            }
+            [FolderItem::Directory(id, _name)] => {
+                let (dir, songs) = self.collect_songs(id).await?;
+                Ok(Some(Droppable::Directory(Box::new(dir), songs)))
+            }
+            items => {
+                let mut songs = vec![];
+                for item in items {
+                    match item {
+                        FolderItem::Directory(id, _name) => {
+                            songs.extend(self.collect_songs(id).await?.1);
+                        }
+                        FolderItem::Song(child) => songs.push(child.clone()),
+                    }
+                }
+                Ok(Some(Droppable::Queue(songs)))
+            }
+        }
+    }
+
+    /// shows the content of the last directory in path
+    async fn show_path(&mut self, sender: &relm4::AsyncComponentSender<Self>) {
+        let rows = match self.path.last().cloned() {
+            None => Self::fetch_root().await,
+            Some((id, _name)) => self
+                .fetch_directory(&id)
+                .await
+                .map(|dir| dir.child.into_iter().map(FolderRow::from_child).collect()),
+        };
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                self.path.pop();
+                sender
+                    .output(FoldersViewOut::DisplayToast(format!(
+                        "could not fetch folder: {e:?}"
+                    )))
+                    .unwrap();
+                return;
+            }
+        };
+
+        self.entries.clear();
+        for row in rows {
+            self.entries.append(row);
+        }
+        self.update_crumbs(sender);
+    }
+
+    fn update_crumbs(&self, sender: &relm4::AsyncComponentSender<Self>) {
+        while let Some(child) = self.crumbs.first_child() {
+            self.crumbs.remove(&child);
+        }
+
+        let names = std::iter::once(gettext("Music folders"))
+            .chain(self.path.iter().map(|(_id, name)| name.clone()));
+        for (depth, name) in names.enumerate() {
+            if depth > 0 {
+                self.crumbs.append(&gtk::Label::new(Some("/")));
+            }
+            let crumb = gtk::Button::with_label(&name);
+            crumb.add_css_class("flat");
+            crumb.set_sensitive(depth < self.path.len());
+            let sender = sender.clone();
+            crumb.connect_clicked(move |_btn| sender.input(FoldersViewIn::ShowCrumb(depth)));
+            self.crumbs.append(&crumb);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FoldersViewIn {
+    SearchChanged,
+    Reload,
+    Activated(u32),
+    /// shows the directory at given depth of the breadcrumbs
+    ShowCrumb(usize),
+    AppendToQueue,
+    AddToQueue,
+    ReplaceQueue,
+    Download,
+}
+
+#[derive(Debug)]
+pub enum FoldersViewOut {
+    DisplayToast(String),
+    AppendToQueue(Droppable),
+    AddToQueue(Droppable),
+    ReplaceQueue(Droppable),
+    Download(Droppable),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for FoldersView {
+    type Init = ();
+    type Input = FoldersViewIn;
+    type Output = FoldersViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut entries = TypedColumnView::<FolderRow, gtk::MultiSelection>::new();
+        entries.append_column::<NameColumn>();
+        entries.append_column::<ArtistColumn>();
+        entries.append_column::<LengthColumn>();
+
+        let columns = entries.get_columns();
+        columns
+            .get("Name")
+            .unwrap()
+            .set_title(Some(&gettext("Name")));
+        columns
+            .get("Artist")
+            .unwrap()
+            .set_title(Some(&gettext("Artist")));
+        columns
+            .get("Length")
+            .unwrap()
+            .set_title(Some(&gettext("Length")));
+
+        // add search filter
+        entries.add_filter(move |row| {
+            let search = Settings::get().lock().unwrap().search_text.clone();
+            common::search_matching(row.name().to_string(), search)
+        });
+
+        let model = Self {
+            entries,
+            crumbs: gtk::Box::default(),
+            path: vec![],
+            directories: HashMap::new(),
+        };
+
+        let widgets = view_output!();
+
+        sender.input(FoldersViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            add_css_class: "tracks-view",
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 8,
+
+            gtk::WindowHandle {
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 8,
+                    set_margin_horizontal: 7,
+
+                    gtk::Label {
+                        add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                        set_halign: gtk::Align::Start,
+                        set_text: &gettext("Folders"),
+                    },
+                    model.crumbs.clone() {
+                        set_spacing: 3,
+                    },
+                    gtk::Box {
+                        set_spacing: 15,
+
+                        gtk::Button {
+                            gtk::Image {
+                                set_icon_name: Some("queue-append-symbolic"),
+                                set_pixel_size: 20,
+                            },
+                            set_tooltip: &gettext("Append selected entries or opened folder to end of queue"),
+                            connect_clicked => FoldersViewIn::AppendToQueue,
+                        },
+                        gtk::Button {
+                            gtk::Image {
+                                set_icon_name: Some("queue-insert-symbolic"),
+                                set_pixel_size: 20,
+                            },
+                            set_tooltip: &gettext("Insert selected entries or opened folder after currently played or paused item"),
+                            connect_clicked => FoldersViewIn::AddToQueue,
+                        },
+                        gtk::Button {
+                            gtk::Image {
+                                set_icon_name: Some("queue-replace-symbolic"),
+                                set_pixel_size: 20,
+                            },
+                            set_tooltip: &gettext("Replaces current queue with selected entries or opened folder"),
+                            connect_clicked => FoldersViewIn::ReplaceQueue,
+                        },
+                        gtk::Button {
+                            gtk::Image {
+                                set_icon_name: Some("browser-download-symbolic"),
+                                set_pixel_size: 20,
+                            },
+                            set_tooltip: &gettext("Click to select a folder to download selected entries or opened folder to"),
+                            connect_clicked => FoldersViewIn::Download,
+                        },
+                    },
+                }
+            },
+
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+
+                model.entries.view.clone() {
+                    set_widget_name: "folders-view-entries",
+
+                    connect_activate[sender] => move |_column_view, index| {
+                        sender.input(FoldersViewIn::Activated(index));
+                    },
+                }
+            },
+        }
+    }
+
+    async fn update(
+        &mut self,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            FoldersViewIn::SearchChanged => _ = self.entries.notify_filter_changed(0),
+            FoldersViewIn::Reload => {
+                self.directories.clear();
+                self.show_path(&sender).await;
+            }
+            FoldersViewIn::Activated(index) => {
+                let Some(row) = self.entries.get_visible(index) else {
+                    return;
+                };
+                let item = row.borrow().item().clone();
+                match item {
+                    FolderItem::Directory(id, name) => {
+                        self.path.push((id, name));
+                        self.show_path(&sender).await;
+                    }
+                    FolderItem::Song(child) => {
+                        let drop = Droppable::Child(Box::new(child));
+                        sender.output(FoldersViewOut::AppendToQueue(drop)).unwrap();
+                    }
+                }
+            }
+            FoldersViewIn::ShowCrumb(depth) => {
+                self.path.truncate(depth);
+                self.show_path(&sender).await;
+            }
+            FoldersViewIn::AppendToQueue
+            | FoldersViewIn::AddToQueue
+            | FoldersViewIn::ReplaceQueue
+            | FoldersViewIn::Download => {
+                let drop = match self.selected().await {
+                    Ok(Some(drop)) => drop,
+                    Ok(None) => return,
+                    Err(e) => {
+                        sender
+                            .output(FoldersViewOut::DisplayToast(format!(
+                                "could not fetch folder content: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+                let out = match msg {
+                    FoldersViewIn::AppendToQueue => FoldersViewOut::AppendToQueue(drop),
+                    FoldersViewIn::AddToQueue => FoldersViewOut::AddToQueue(drop),
+                    FoldersViewIn::ReplaceQueue => FoldersViewOut::ReplaceQueue(drop),
+                    _ => FoldersViewOut::Download(drop),
+                };
+                sender.output(out).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, OrientableExt, SelectionModelExt, WidgetExt},
+    },
+    typed_view::column::TypedColumnView,
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common::{
+        self,
+        types::{Droppable, Id},
+    },
+    factory::genre_row::{
+        AlbumArtistColumn, AlbumCountColumn, AlbumTitleColumn, AlbumYearColumn, GenreAlbumRow,
+        GenreColumn, GenreRow, GenreTrackRow, SongCountColumn, TrackAlbumColumn, TrackArtistColumn,
+        TrackLengthColumn, TrackTitleColumn,
+    },
+    settings::Settings,
+    subsonic::Subsonic,
+};
+
+#[derive(Debug)]
+pub struct GenresView {
+    subsonic: Rc<RefCell<Subsonic>>,
+    genres: TypedColumnView<GenreRow, gtk::SingleSelection>,
+    albums: TypedColumnView<GenreAlbumRow, gtk::SingleSelection>,
+    tracks: TypedColumnView<GenreTrackRow, gtk::MultiSelection>,
+}
+
+impl GenresView {
+    /// returns the selected tracks or all tracks of the genre when none are selected
+    fn selected_tracks(&self) -> Option<Droppable> {
+        let model = self.tracks.view.model().unwrap();
+        let mut songs: Vec<submarine::data::Child> = (0..self.tracks.selection_model.n_items())
+            .filter(|i| model.is_selected(*i))
+            .filter_map(|i| self.tracks.get_visible(i))
+            .map(|row| row.borrow().item().clone())
+            .collect();
+        if songs.is_empty() {
+            songs = (0..self.tracks.len())
+                .filter_map(|i| self.tracks.get(i))
+                .map(|row| row.borrow().item().clone())
+                .collect();
+        }
+
+        if songs.is_empty() {
+            return None;
+        }
+        Some(Droppable::Queue(songs))
+    }
+}
+
+#[derive(Debug)]
+pub enum GenresViewIn {
+    SearchChanged,
+    Reload,
+    GenreSelected(u32),
+    AlbumActivated(u32),
+    AppendToQueue,
+    AddToQueue,
+    ReplaceQueue,
+}
+
+#[derive(Debug)]
+pub enum GenresViewOut {
+    DisplayToast(String),
+    ClickedAlbum(Id),
+    AppendToQueue(Droppable),
+    AddToQueue(Droppable),
+    ReplaceQueue(Droppable),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for GenresView {
+    type Init = Rc<RefCell<Subsonic>>;
+    type Input = GenresViewIn;
+    type Output = GenresViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        subsonic: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut genres = TypedColumnView::<GenreRow, gtk::SingleSelection>::new();
+        genres.append_column::<GenreColumn>();
+        genres.append_column::<AlbumCountColumn>();
+        genres.append_column::<SongCountColumn>();
+
+        let columns = genres.get_columns();
+        columns
+            .get("Genre")
+            .unwrap()
+            .set_title(Some(&gettext("Genre")));
+        columns
+            .get("Albums")
+            .unwrap()
+            .set_title(Some(&gettext("Albums")));
+        columns
+            .get("Songs")
+            .unwrap()
+            .set_title(Some(&gettext("Songs")));
+
+        // add search filter
+        genres.add_filter(move |row| {
+            let search = Settings::get().lock().unwrap().search_text.clone();
+            common::search_matching(row.item().value.clone(), search)
+        });
+
+        let mut albums = TypedColumnView::<GenreAlbumRow, gtk::SingleSelection>::new();
+        albums.append_column::<AlbumTitleColumn>();
+        albums.append_column::<AlbumArtistColumn>();
+        albums.append_column::<AlbumYearColumn>();
+
+        let columns = albums.get_columns();
+        columns
+            .get("Album")
+            .unwrap()
+            .set_title(Some(&gettext("Album")));
+        columns
+            .get("Artist")
+            .unwrap()
+            .set_title(Some(&gettext("Artist")));
+        columns
+            .get("Year")
+            .unwrap()
+            .set_title(Some(&gettext("Year")));
+
+        let mut tracks = TypedColumnView::<GenreTrackRow, gtk::MultiSelection>::new();
+        tracks.append_column::<TrackTitleColumn>();
+        tracks.append_column::<TrackArtistColumn>();
+        tracks.append_column::<TrackAlbumColumn>();
+        tracks.append_column::<TrackLengthColumn>();
+
+        let columns = tracks.get_columns();
+        columns
+            .get("Title")
+            .unwrap()
+            .set_title(Some(&gettext("Title")));
+        columns
+            .get("Artist")
+            .unwrap()
+            .set_title(Some(&gettext("Artist")));
+        columns
+            .get("Album")
+            .unwrap()
+            .set_title(Some(&gettext("Album")));
+        columns
+            .get("Length")
+            .unwrap()
+            .set_title(Some(&gettext("Length")));
+
+        let model = Self {
+            subsonic,
+            genres,
+            albums,
+            tracks,
+        };
+
+        let widgets = view_output!();
+
+        sender.input(GenresViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            gtk::Paned {
+                add_css_class: "tracks-view",
+                set_hexpand: true,
+                set_position: 300,
+                set_shrink_start_child: false,
+                set_shrink_end_child: false,
+
+                // genres
+                #[wrap(Some)]
+                set_start_child = &gtk::ScrolledWindow {
+                    set_vexpand: true,
+
+                    model.genres.view.clone() {
+                        set_widget_name: "genres-view-genres",
+                        set_single_click_activate: true,
+
+                        connect_activate[sender] => move |_column_view, index| {
+                            sender.input(GenresViewIn::GenreSelected(index));
+                        },
+                    }
+                },
+
+                // content of genre
+                #[wrap(Some)]
+                set_end_child = &gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 8,
+
+                    gtk::WindowHandle {
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 8,
+                            set_margin_horizontal: 7,
+
+                            #[name = "genre_title"]
+                            gtk::Label {
+                                add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                                set_halign: gtk::Align::Start,
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_text: &gettext("Genres"),
+                            },
+                            #[name = "genre_info"]
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                set_text: &gettext("Select a genre to show its albums and tracks"),
+                            },
+                            gtk::CenterBox {
+                                #[wrap(Some)]
+                                set_start_widget = &gtk::Box {
+                                    set_spacing: 15,
+
+                                    #[name = "append_btn"]
+                                    gtk::Button {
+                                        set_sensitive: false,
+                                        gtk::Image {
+                                            set_icon_name: Some("queue-append-symbolic"),
+                                            set_pixel_size: 20,
+                                        },
+                                        set_tooltip: &gettext("Append selected tracks or whole genre to end of queue"),
+                                        connect_clicked => GenresViewIn::AppendToQueue,
+                                    },
+                                    #[name = "insert_btn"]
+                                    gtk::Button {
+                                        set_sensitive: false,
+                                        gtk::Image {
+                                            set_icon_name: Some("queue-insert-symbolic"),
+                                            set_pixel_size: 20,
+                                        },
+                                        set_tooltip: &gettext("Insert selected tracks or whole genre after currently played or paused item"),
+                                        connect_clicked => GenresViewIn::AddToQueue,
+                                    },
+                                    #[name = "replace_btn"]
+                                    gtk::Button {
+                                        set_sensitive: false,
+                                        gtk::Image {
+                                            set_icon_name: Some("queue-replace-symbolic"),
+                                            set_pixel_size: 20,
+                                        },
+                                        set_tooltip: &gettext("Replaces current queue with selected tracks or whole genre"),
+                                        connect_clicked => GenresViewIn::ReplaceQueue,
+                                    },
+                                },
+
+                                #[wrap(Some)]
+                                set_end_widget = &gtk::StackSwitcher {
+                                    set_stack: Some(&content),
+                                },
+                            },
+                        }
+                    },
+
+                    #[name = "content"]
+                    gtk::Stack {
+                        set_vexpand: true,
+                        set_transition_type: gtk::StackTransitionType::Crossfade,
+
+                        add_titled[Some("albums"), &gettext("Albums")] = &gtk::ScrolledWindow {
+                            model.albums.view.clone() {
+                                set_widget_name: "genres-view-albums",
+
+                                connect_activate[sender] => move |_column_view, index| {
+                                    sender.input(GenresViewIn::AlbumActivated(index));
+                                },
+                            }
+                        },
+                        add_titled[Some("tracks"), &gettext("Tracks")] = &gtk::ScrolledWindow {
+                            model.tracks.view.clone() {
+                                set_widget_name: "genres-view-tracks",
+                            }
+                        },
+                    },
+                },
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            GenresViewIn::SearchChanged => _ = self.genres.notify_filter_changed(0),
+            GenresViewIn::Reload => {
+                let client = Client::get().unwrap();
+                let genres = match client.get_genres().await {
+                    Ok(genres) => genres,
+                    Err(e) => {
+                        sender
+                            .output(GenresViewOut::DisplayToast(format!(
+                                "could not fetch genres: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+
+                self.genres.clear();
+                for genre in genres {
+                    self.genres.append(GenreRow::new(genre));
+                }
+            }
+            GenresViewIn::GenreSelected(index) => {
+                let Some(genre) = self.genres.get_visible(index) else {
+                    return;
+                };
+                let genre = genre.borrow().item().value.clone();
+
+                // albums and tracks come from the cache
+                let subsonic = self.subsonic.borrow();
+                let albums = subsonic.albums_of_genre(&genre);
+                let tracks = subsonic.tracks_of_genre(&genre);
+                widgets.genre_title.set_text(&genre);
+                widgets.genre_info.set_text(&format!(
+                    "{}: {} • {}: {}",
+                    gettext("Albums"),
+                    albums.len(),
+                    gettext("Songs"),
+                    tracks.len()
+                ));
+
+                let has_tracks = !tracks.is_empty();
+                widgets.append_btn.set_sensitive(has_tracks);
+                widgets.insert_btn.set_sensitive(has_tracks);
+                widgets.replace_btn.set_sensitive(has_tracks);
+
+                self.albums.clear();
+                for album in albums {
+                    self.albums.append(GenreAlbumRow::new(album));
+                }
+                self.tracks.clear();
+                for track in tracks {
+                    self.tracks.append(GenreTrackRow::new(track));
+                }
+            }
+            GenresViewIn::AlbumActivated(index) => {
+                if let Some(album) = self.albums.get_visible(index) {
+                    let id = Id::album(&album.borrow().item().id);
+                    sender.output(GenresViewOut::ClickedAlbum(id)).unwrap();
+                }
+            }
+            GenresViewIn::AppendToQueue => {
+                if let Some(drop) = self.selected_tracks() {
+                    sender.output(GenresViewOut::AppendToQueue(drop)).unwrap();
+                }
+            }
+            GenresViewIn::AddToQueue => {
+                if let Some(drop) = self.selected_tracks() {
+                    sender.output(GenresViewOut::AddToQueue(drop)).unwrap();
+                }
+            }
+            GenresViewIn::ReplaceQueue => {
+                if let Some(drop) = self.selected_tracks() {
+                    sender.output(GenresViewOut::ReplaceQueue(drop)).unwrap();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, OrientableExt, SelectionModelExt, WidgetExt},
+    },
+    typed_view::column::TypedColumnView,
+    RelmWidgetExt,
+};
+
+use crate::{
+    common::{self, types::Droppable},
+    factory::history_row::{
+        AlbumColumn, ArtistColumn, DayColumn, HistoryDayRow, HistoryPlayRow, ListenedColumn,
+        PlayCountColumn, StatusColumn, TimeColumn, TitleColumn,
+    },
+    settings::Settings,
+    subsonic::Subsonic,
+    subsonic_history::PlayRecord,
+};
+
+/// returns true when title, artist or album of the played song match the search
+fn play_matches(play: &PlayRecord) -> bool {
+    let search = Settings::get().lock().unwrap().search_text.clone();
+    [
+        Some(&play.song.title),
+        play.song.artist.as_ref(),
+        play.song.album.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|text| common::search_matching(text.clone(), search.clone()))
+}
+
+#[derive(Debug)]
+pub struct HistoryView {
+    subsonic: Rc<RefCell<Subsonic>>,
+    days: TypedColumnView<HistoryDayRow, gtk::SingleSelection>,
+    plays: TypedColumnView<HistoryPlayRow, gtk::MultiSelection>,
+    selected_day: Option<chrono::NaiveDate>,
+}
+
+impl HistoryView {
+    /// returns the selected plays or all plays of the day when none are selected
+    fn selected_songs(&self) -> Option<Droppable> {
+        let model = self.plays.view.model().unwrap();
+        let mut songs: Vec<submarine::data::Child> = (0..self.plays.selection_model.n_items())
+            .filter(|i| model.is_selected(*i))
+            .filter_map(|i| self.plays.get_visible(i))
+            .map(|row| row.borrow().item().song.clone())
+            .collect();
+        if songs.is_empty() {
+            songs = (0..self.plays.selection_model.n_items())
+                .filter_map(|i| self.plays.get_visible(i))
+                .map(|row| row.borrow().item().song.clone())
+                .collect();
+        }
+
+        if songs.is_empty() {
+            return None;
+        }
+        Some(Droppable::Queue(songs))
+    }
+
+    /// plays of the cache grouped by day, the latest day first
+    fn plays_by_day(&self) -> Vec<(chrono::NaiveDate, Vec<PlayRecord>)> {
+        let mut days: BTreeMap<chrono::NaiveDate, Vec<PlayRecord>> = BTreeMap::new();
+        for play in self.subsonic.borrow().history().records().iter().rev() {
+            days.entry(play.day()).or_default().push(play.clone());
+        }
+        days.into_iter().rev().collect()
+    }
+
+    fn show_day(
+        &mut self,
+        widgets: &<Self as relm4::component::AsyncComponent>::Widgets,
+        day: chrono::NaiveDate,
+        plays: Vec<PlayRecord>,
+    ) {
+        self.selected_day = Some(day);
+        widgets.day_title.set_text(&day.format("%x").to_string());
+        let listened: i64 = plays
+            .iter()
+            .map(|play| {
+                i64::from(play.song.duration.unwrap_or(0)) * 1000 * i64::from(play.listened) / 100
+            })
+            .sum();
+        widgets.day_info.set_text(&format!(
+            "{}: {} • {}: {}",
+            gettext("Plays"),
+            plays.len(),
+            gettext("Listened"),
+            common::convert_for_label(listened)
+        ));
+
+        let has_plays = !plays.is_empty();
+        widgets.append_btn.set_sensitive(has_plays);
+        widgets.insert_btn.set_sensitive(has_plays);
+        widgets.replace_btn.set_sensitive(has_plays);
+
+        self.plays.clear();
+        for play in plays {
+            self.plays.append(HistoryPlayRow::new(play));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum HistoryViewIn {
+    SearchChanged,
+    Reload,
+    /// a play was added to the history
+    NewPlay,
+    DaySelected(u32),
+    PlayActivated(u32),
+    AppendToQueue,
+    AddToQueue,
+    ReplaceQueue,
+}
+
+#[derive(Debug)]
+pub enum HistoryViewOut {
+    AppendToQueue(Droppable),
+    AddToQueue(Droppable),
+    ReplaceQueue(Droppable),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for HistoryView {
+    type Init = Rc<RefCell<Subsonic>>;
+    type Input = HistoryViewIn;
+    type Output = HistoryViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        subsonic: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut days = TypedColumnView::<HistoryDayRow, gtk::SingleSelection>::new();
+        days.append_column::<DayColumn>();
+        days.append_column::<PlayCountColumn>();
+
+        let columns = days.get_columns();
+        columns.get("Day").unwrap().set_title(Some(&gettext("Day")));
+        columns
+            .get("Plays")
+            .unwrap()
+            .set_title(Some(&gettext("Plays")));
+
+        // days are shown when one of their plays matches the search
+        days.add_filter(move |row| row.plays().iter().any(play_matches));
+
+        let mut plays = TypedColumnView::<HistoryPlayRow, gtk::MultiSelection>::new();
+        plays.append_column::<TimeColumn>();
+        plays.append_column::<TitleColumn>();
+        plays.append_column::<ArtistColumn>();
+        plays.append_column::<AlbumColumn>();
+        plays.append_column::<ListenedColumn>();
+        plays.append_column::<StatusColumn>();
+
+        let columns = plays.get_columns();
+        columns
+            .get("Time")
+            .unwrap()
+            .set_title(Some(&gettext("Time")));
+        columns
+            .get("Title")
+            .unwrap()
+            .set_title(Some(&gettext("Title")));
+        columns
+            .get("Artist")
+            .unwrap()
+            .set_title(Some(&gettext("Artist")));
+        columns
+            .get("Album")
+            .unwrap()
+            .set_title(Some(&gettext("Album")));
+        columns
+            .get("Listened")
+            .unwrap()
+            .set_title(Some(&gettext("Listened")));
+        columns
+            .get("Status")
+            .unwrap()
+            .set_title(Some(&gettext("Status")));
+
+        plays.add_filter(move |row| play_matches(row.item()));
+
+        let model = Self {
+            subsonic,
+            days,
+            plays,
+            selected_day: None,
+        };
+
+        let widgets = view_output!();
+
+        sender.input(HistoryViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            gtk::Paned {
+                add_css_class: "tracks-view",
+                set_hexpand: true,
+                set_position: 250,
+                set_shrink_start_child: false,
+                set_shrink_end_child: false,
+
+                // days
+                #[wrap(Some)]
+                set_start_child = &gtk::ScrolledWindow {
+                    set_vexpand: true,
+
+                    model.days.view.clone() {
+                        set_widget_name: "history-view-days",
+                        set_single_click_activate: true,
+
+                        connect_activate[sender] => move |_column_view, index| {
+                            sender.input(HistoryViewIn::DaySelected(index));
+                        },
+                    }
+                },
+
+                // plays of day
+                #[wrap(Some)]
+                set_end_child = &gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 8,
+
+                    gtk::WindowHandle {
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 8,
+                            set_margin_horizontal: 7,
+
+                            #[name = "day_title"]
+                            gtk::Label {
+                                add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                                set_halign: gtk::Align::Start,
+                                set_text: &gettext("History"),
+                            },
+                            #[name = "day_info"]
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                set_text: &gettext("Select a day to show what was played"),
+                            },
+                            gtk::Box {
+                                set_spacing: 15,
+
+                                #[name = "append_btn"]
+                                gtk::Button {
+                                    set_sensitive: false,
+                                    gtk::Image {
+                                        set_icon_name: Some("queue-append-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                    set_tooltip: &gettext("Append selected songs or whole day to end of queue"),
+                                    connect_clicked => HistoryViewIn::AppendToQueue,
+                                },
+                                #[name = "insert_btn"]
+                                gtk::Button {
+                                    set_sensitive: false,
+                                    gtk::Image {
+                                        set_icon_name: Some("queue-insert-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                    set_tooltip: &gettext("Insert selected songs or whole day after currently played or paused item"),
+                                    connect_clicked => HistoryViewIn::AddToQueue,
+                                },
+                                #[name = "replace_btn"]
+                                gtk::Button {
+                                    set_sensitive: false,
+                                    gtk::Image {
+                                        set_icon_name: Some("queue-replace-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                    set_tooltip: &gettext("Replaces current queue with selected songs or whole day"),
+                                    connect_clicked => HistoryViewIn::ReplaceQueue,
+                                },
+                            },
+                        }
+                    },
+
+                    gtk::ScrolledWindow {
+                        set_vexpand: true,
+
+                        model.plays.view.clone() {
+                            set_widget_name: "history-view-plays",
+
+                            connect_activate[sender] => move |_column_view, index| {
+                                sender.input(HistoryViewIn::PlayActivated(index));
+                            },
+                        }
+                    },
+                },
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            HistoryViewIn::SearchChanged => {
+                _ = self.days.notify_filter_changed(0);
+                _ = self.plays.notify_filter_changed(0);
+            }
+            HistoryViewIn::Reload | HistoryViewIn::NewPlay => {
+                let days = self.plays_by_day();
+                self.days.clear();
+                for (day, plays) in &days {
+                    self.days.append(HistoryDayRow::new(*day, plays.clone()));
+                }
+
+                // keep showing the selected day
+                if let Some(selected) = self.selected_day {
+                    if let Some((day, plays)) = days.into_iter().find(|(day, _)| *day == selected) {
+                        self.show_day(widgets, day, plays);
+                    }
+                }
+            }
+            HistoryViewIn::DaySelected(index) => {
+                let Some(row) = self.days.get_visible(index) else {
+                    return;
+                };
+                let (day, plays) = {
+                    let row = row.borrow();
+                    (*row.day(), row.plays().clone())
+                };
+                self.show_day(widgets, day, plays);
+            }
+            HistoryViewIn::PlayActivated(index) => {
+                if let Some(row) = self.plays.get_visible(index) {
+                    let drop = Droppable::Child(Box::new(row.borrow().item().song.clone()));
+                    sender.output(HistoryViewOut::AppendToQueue(drop)).unwrap();
+                }
+            }
+            HistoryViewIn::AppendToQueue => {
+                if let Some(drop) = self.selected_songs() {
+                    sender.output(HistoryViewOut::AppendToQueue(drop)).unwrap();
+                }
+            }
+            HistoryViewIn::AddToQueue => {
+                if let Some(drop) = self.selected_songs() {
+                    sender.output(HistoryViewOut::AddToQueue(drop)).unwrap();
+                }
+            }
+            HistoryViewIn::ReplaceQueue => {
+                if let Some(drop) = self.selected_songs() {
+                    sender.output(HistoryViewOut::ReplaceQueue(drop)).unwrap();
+                }
+            }
+        }
+    }
+}
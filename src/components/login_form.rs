@@ -7,7 +7,11 @@ use relm4::{
     },
 };
 
-use crate::{config, settings::Settings};
+use crate::{
+    client::Client,
+    config,
+    settings::{ServerProfile, Settings},
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct LoginForm {}
@@ -18,11 +22,14 @@ pub enum LoginFormIn {
     UriChanged,
     FormChanged,
     ResetClicked,
+    CancelClicked,
 }
 
 #[derive(Debug)]
 pub enum LoginFormOut {
     LoggedIn,
+    /// adding another server was canceled
+    Canceled,
     DisplayToast(String),
 }
 
@@ -44,11 +51,16 @@ impl relm4::component::AsyncComponent for LoginForm {
         //init widgets
         {
             let settings = Settings::get().lock().unwrap();
-            if let Some(uri) = &settings.login_uri {
-                widgets.uri.set_text(uri);
-            }
-            if let Some(user) = &settings.login_username {
-                widgets.user.set_text(user);
+            // another server is added when already logged in, so start with an empty form
+            let adding_server = settings.login_set();
+            widgets.cancel_btn.set_visible(adding_server);
+            if !adding_server {
+                if let Some(uri) = &settings.login_uri {
+                    widgets.uri.set_text(uri);
+                }
+                if let Some(user) = &settings.login_username {
+                    widgets.user.set_text(user);
+                }
             }
         }
 
@@ -109,6 +121,10 @@ impl relm4::component::AsyncComponent for LoginForm {
                     set_orientation: gtk::Orientation::Horizontal,
                     set_spacing: 5,
 
+                    append: cancel_btn = &gtk::Button {
+                        set_label: &gettext("Cancel"),
+                        connect_clicked => LoginFormIn::CancelClicked,
+                    },
                     append: login_btn = &gtk::Button {
                         set_label: &gettext("Login"),
                         set_sensitive: false,
@@ -138,10 +154,13 @@ impl relm4::component::AsyncComponent for LoginForm {
                     Ok(_) => {
                         {
                             let mut settings = Settings::get().lock().unwrap();
-                            settings.login_uri = Some(widgets.uri.text().to_string());
-                            settings.login_username = Some(widgets.user.text().to_string());
-                            settings.login_hash = Some(hash);
-                            settings.login_salt = Some(salt);
+                            let profile = ServerProfile::new(
+                                widgets.uri.text(),
+                                widgets.user.text(),
+                                hash,
+                                salt,
+                            );
+                            settings.add_profile(profile);
                             if let Err(e) = settings.save() {
                                 sender
                                     .output(LoginFormOut::DisplayToast(format!(
@@ -150,6 +169,8 @@ impl relm4::component::AsyncComponent for LoginForm {
                                     .unwrap();
                             }
                         }
+                        // create client for new login
+                        Client::get_mut().lock().unwrap().reset();
                         sender.output(LoginFormOut::LoggedIn).unwrap();
                     }
                     Err(e) => {
@@ -187,6 +208,7 @@ impl relm4::component::AsyncComponent for LoginForm {
                         .unwrap();
                 }
             }
+            LoginFormIn::CancelClicked => sender.output(LoginFormOut::Canceled).unwrap(),
             LoginFormIn::UriChanged => match url::Url::parse(&widgets.uri.text()) {
                 Ok(_) => {
                     widgets.uri.set_secondary_icon_name(None);
@@ -0,0 +1,263 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{AdjustmentExt, BoxExt, OrientableExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common::lyrics::Lyrics,
+    gtk_helper::{loading_widget::LoadingWidgetState, scroll::ScrolledWindowExt, stack::StackExt},
+    subsonic::Subsonic,
+};
+
+#[derive(Debug)]
+pub struct LyricsView {
+    subsonic: Rc<RefCell<Subsonic>>,
+    /// id of the song the lyrics are shown for
+    song: Option<String>,
+    lyrics: Lyrics,
+    labels: Vec<gtk::Label>,
+    current: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum LyricsViewIn {
+    NewSong(Box<Option<submarine::data::Child>>),
+    /// position of the current song in ms
+    SongPosition(i64),
+}
+
+#[derive(Debug)]
+pub enum LyricsViewCmd {
+    Loaded(String, Option<Lyrics>),
+}
+
+#[relm4::component(pub)]
+impl relm4::component::Component for LyricsView {
+    type Init = Rc<RefCell<Subsonic>>;
+    type Input = LyricsViewIn;
+    type Output = ();
+    type CommandOutput = LyricsViewCmd;
+
+    fn init(
+        subsonic: Self::Init,
+        root: Self::Root,
+        sender: relm4::ComponentSender<Self>,
+    ) -> relm4::ComponentParts<Self> {
+        let model = Self {
+            subsonic,
+            song: None,
+            lyrics: Lyrics::default(),
+            labels: vec![],
+            current: None,
+        };
+
+        let widgets = view_output!();
+
+        relm4::ComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            set_widget_name: "lyrics",
+            set_orientation: gtk::Orientation::Vertical,
+
+            append: stack = &gtk::Stack {
+                set_vexpand: true,
+
+                add_enumed[LoadingWidgetState::NotEmpty]: scrolled = &gtk::ScrolledWindow {
+                    set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                    #[name = "lines"]
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 8,
+                        set_margin_all: 10,
+                    }
+                },
+                add_enumed[LoadingWidgetState::Empty] = &gtk::CenterBox {
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    #[wrap(Some)]
+                    set_center_widget = &gtk::Label {
+                        add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                        set_label: &gettext("No lyrics found"),
+                    },
+                },
+                add_enumed[LoadingWidgetState::Loading] = &gtk::CenterBox {
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    #[wrap(Some)]
+                    set_center_widget = &gtk::Spinner {
+                        start: (),
+                    },
+                },
+                set_visible_child_enum: &LoadingWidgetState::Empty,
+            }
+        }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            LyricsViewIn::NewSong(child) => {
+                let Some(child) = *child else {
+                    self.song = None;
+                    self.show_lyrics(widgets, Lyrics::default());
+                    return;
+                };
+                if self.song.as_ref() == Some(&child.id) {
+                    return;
+                }
+                self.song = Some(child.id.clone());
+
+                // prefer sidecar files of offline songs
+                let file = self.subsonic.borrow().offline().lyrics_file(&child.id);
+                if let Some(file) = file {
+                    match std::fs::read_to_string(&file) {
+                        Ok(content) => {
+                            self.show_lyrics(widgets, Lyrics::from_lrc(&content));
+                            return;
+                        }
+                        Err(e) => tracing::warn!("could not read lyrics file {file:?}: {e}"),
+                    }
+                }
+
+                widgets
+                    .stack
+                    .set_visible_child_enum(&LoadingWidgetState::Loading);
+                sender.oneshot_command(async move {
+                    let lyrics = fetch_lyrics(&child).await;
+                    LyricsViewCmd::Loaded(child.id, lyrics)
+                });
+            }
+            LyricsViewIn::SongPosition(ms) => {
+                let current = self.lyrics.current_line(ms);
+                if current == self.current {
+                    return;
+                }
+
+                if let Some(label) = self.current.and_then(|i| self.labels.get(i)) {
+                    label.remove_css_class("lyrics-current");
+                }
+                self.current = current;
+                let Some(label) = current.and_then(|i| self.labels.get(i)) else {
+                    return;
+                };
+                label.add_css_class("lyrics-current");
+
+                // keep the current line in the middle of the panel
+                let height = widgets.scrolled.vadjustment().upper();
+                if height <= 0.0 {
+                    return;
+                }
+                let scroll_y =
+                    f64::from(label.allocation().y()) - f64::from(widgets.scrolled.height()) * 0.45;
+                widgets.scrolled.smooth_scroll_to(
+                    scroll_y.max(0.0) / height,
+                    std::time::Duration::from_millis(300),
+                    std::time::Duration::from_millis(16),
+                    None,
+                );
+            }
+        }
+    }
+
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::CommandOutput,
+        _sender: relm4::ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            LyricsViewCmd::Loaded(id, lyrics) => {
+                // song changed while loading
+                if self.song.as_ref() != Some(&id) {
+                    return;
+                }
+                self.show_lyrics(widgets, lyrics.unwrap_or_default());
+            }
+        }
+    }
+}
+
+impl LyricsView {
+    fn show_lyrics(&mut self, widgets: &mut LyricsViewWidgets, lyrics: Lyrics) {
+        for label in self.labels.drain(..) {
+            widgets.lines.remove(&label);
+        }
+        self.current = None;
+
+        if lyrics.is_empty() {
+            self.lyrics = Lyrics::default();
+            widgets
+                .stack
+                .set_visible_child_enum(&LoadingWidgetState::Empty);
+            return;
+        }
+
+        for line in lyrics.lines() {
+            let label = gtk::Label::builder()
+                .label(&line.text)
+                .wrap(true)
+                .justify(gtk::Justification::Center)
+                .build();
+            if lyrics.is_synced() {
+                label.add_css_class("lyrics-line");
+            }
+            widgets.lines.append(&label);
+            self.labels.push(label);
+        }
+        self.lyrics = lyrics;
+        widgets.scrolled.vadjustment().set_value(0.0);
+        widgets
+            .stack
+            .set_visible_child_enum(&LoadingWidgetState::NotEmpty);
+    }
+}
+
+/// asks the server for structured lyrics and falls back to plain lyrics
+async fn fetch_lyrics(child: &submarine::data::Child) -> Option<Lyrics> {
+    let client = Client::get()?;
+
+    match client.get_lyrics_by_song_id(&child.id).await {
+        Err(e) => tracing::info!("no structured lyrics for {}: {e}", child.title),
+        Ok(list) => {
+            // prefer synced lyrics
+            let structured = list
+                .structured_lyrics
+                .iter()
+                .find(|lyrics| lyrics.synced)
+                .or(list.structured_lyrics.first());
+            if let Some(structured) = structured {
+                let lyrics = Lyrics::from_structured(structured);
+                if !lyrics.is_empty() {
+                    return Some(lyrics);
+                }
+            }
+        }
+    }
+
+    match client
+        .get_lyrics(child.artist.as_deref(), Some(&child.title))
+        .await
+    {
+        Err(e) => {
+            tracing::info!("no lyrics for {}: {e}", child.title);
+            None
+        }
+        Ok(lyrics) => lyrics.value.map(|text| Lyrics::from_plain(&text)),
+    }
+}
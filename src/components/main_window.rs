@@ -23,6 +23,7 @@ use crate::{
     mpris::{Mpris, MprisOut},
     playback::{Playback, PlaybackOut},
     settings::Settings,
+    subsonic::Subsonic,
     Args,
 };
 
@@ -252,6 +253,13 @@ impl relm4::component::AsyncComponent for MainWindow {
             } else if settings.valid_login().await {
                 tracing::info!("show app");
                 sender.input(MainWindowIn::ShowApp);
+            } else if Subsonic::has_cache() {
+                // start offline with the cached library and offline songs
+                tracing::info!("server is not reachable; show app with cached info");
+                sender.input(MainWindowIn::DisplayToast(gettext(
+                    "Server is not reachable, only the cached library is available",
+                )));
+                sender.input(MainWindowIn::ShowApp);
             } else {
                 tracing::info!("show no connection screen");
                 sender.input(MainWindowIn::ShowNoConnection);
@@ -436,10 +444,34 @@ impl relm4::component::AsyncComponent for MainWindow {
             MainWindowIn::App(msg) => match msg {
                 AppOut::Logout => sender.input(MainWindowIn::ShowLogin),
                 AppOut::Reload => sender.input(MainWindowIn::ShowApp),
+                AppOut::SwitchProfile(name) => {
+                    tracing::info!("switching to profile {name}");
+                    {
+                        let mut settings = Settings::get().lock().unwrap();
+                        if !settings.activate_profile(&name) {
+                            sender.input(MainWindowIn::DisplayToast(format!(
+                                "no server profile named {name}"
+                            )));
+                            return;
+                        }
+                        if let Err(e) = settings.save() {
+                            sender.input(MainWindowIn::DisplayToast(format!(
+                                "error while saving settings: {e}"
+                            )));
+                        }
+                    }
+                    crate::client::Client::get_mut().lock().unwrap().reset();
+                    sender.input(MainWindowIn::RetryLogin);
+                }
+                AppOut::AddServer => {
+                    self.app.replace(None);
+                    sender.input(MainWindowIn::ShowLogin);
+                }
                 AppOut::DisplayToast(msg) => sender.input(MainWindowIn::DisplayToast(msg)),
             },
             MainWindowIn::LoginForm(msg) => match msg {
                 LoginFormOut::LoggedIn => sender.input(MainWindowIn::ShowApp),
+                LoginFormOut::Canceled => sender.input(MainWindowIn::ShowApp),
                 LoginFormOut::DisplayToast(msg) => sender.input(MainWindowIn::DisplayToast(msg)),
             },
             MainWindowIn::ShowNoConnection => {
@@ -474,6 +506,13 @@ impl relm4::component::AsyncComponent for MainWindow {
                     } else if settings.valid_login().await {
                         tracing::info!("show app");
                         sender.input(MainWindowIn::ShowApp);
+                    } else if Subsonic::has_cache() {
+                        // start offline with the cached library and offline songs
+                        tracing::info!("server is not reachable; show app with cached info");
+                        sender.input(MainWindowIn::DisplayToast(gettext(
+                            "Server is not reachable, only the cached library is available",
+                        )));
+                        sender.input(MainWindowIn::ShowApp);
                     } else {
                         tracing::info!("show error screen");
                         sender.input(MainWindowIn::ShowNoConnection);
@@ -3,22 +3,33 @@ pub mod albums_view;
 pub mod app;
 pub mod artist_view;
 pub mod artists_view;
+pub mod bookmarks_view;
 pub mod browser;
 pub mod cover;
 pub mod dashboard;
 pub mod descriptive_cover;
 pub mod equalizer;
+pub mod folders_view;
+pub mod genres_view;
+pub mod history_view;
 pub mod login_form;
+pub mod lyrics;
 pub mod main_window;
+pub mod music_folders;
 pub mod play_controls;
 pub mod play_info;
 pub mod playlists_view;
+pub mod podcasts_view;
 pub mod queue;
+pub mod radio_view;
+pub mod random_mix;
 pub mod seekbar;
 pub mod sequence_button;
 pub mod sequence_button_impl;
 pub mod settings_window;
+pub mod shares_view;
 pub mod sort_by;
+pub mod statistics_view;
 pub mod tracks_view;
 pub mod volume_button;
 pub mod warning_dialog;
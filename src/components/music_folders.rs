@@ -0,0 +1,147 @@
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, CheckButtonExt, OrientableExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+use crate::{client::Client, settings::Settings};
+
+/// selection of the music folders the library is restricted to
+#[derive(Debug, Default)]
+pub struct MusicFolders {
+    /// folder id and its check button
+    folders: Vec<(String, gtk::CheckButton)>,
+}
+
+#[derive(Debug)]
+pub enum MusicFoldersIn {
+    Load,
+    Apply,
+}
+
+#[derive(Debug)]
+pub enum MusicFoldersOut {
+    DisplayToast(String),
+    /// true when the server has more than one folder to choose from
+    Available(bool),
+    /// the selection was saved and the library needs to be reloaded
+    Changed,
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for MusicFolders {
+    type Init = ();
+    type Input = MusicFoldersIn;
+    type Output = MusicFoldersOut;
+    type CommandOutput = ();
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let model = Self::default();
+        let widgets = view_output!();
+
+        sender.input(MusicFoldersIn::Load);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 7,
+            set_margin_all: 5,
+
+            gtk::Label {
+                add_css_class: granite::STYLE_CLASS_H4_LABEL,
+                set_halign: gtk::Align::Start,
+                set_label: &gettext("Music folders"),
+            },
+            append: folder_list = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 3,
+            },
+            gtk::Label {
+                add_css_class: "dim-label",
+                set_halign: gtk::Align::Start,
+                set_label: &gettext("Nothing selected shows all folders"),
+            },
+            gtk::Button {
+                add_css_class: "suggested-action",
+                set_label: &gettext("Apply and reload library"),
+                connect_clicked => MusicFoldersIn::Apply,
+            },
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            MusicFoldersIn::Load => {
+                let client = Client::get().unwrap();
+                let folders = match client.get_music_folders().await {
+                    Ok(folders) => folders,
+                    Err(e) => {
+                        sender
+                            .output(MusicFoldersOut::DisplayToast(format!(
+                                "could not fetch music folders: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+
+                let selected = Settings::get().lock().unwrap().music_folders();
+                for (_id, check) in self.folders.drain(..) {
+                    widgets.folder_list.remove(&check);
+                }
+                for folder in &folders {
+                    let id = folder.id.to_string();
+                    let name = folder.name.clone().unwrap_or(id.clone());
+                    let check = gtk::CheckButton::with_label(&name);
+                    check.set_active(selected.contains(&id));
+                    widgets.folder_list.append(&check);
+                    self.folders.push((id, check));
+                }
+
+                sender
+                    .output(MusicFoldersOut::Available(folders.len() > 1))
+                    .unwrap();
+            }
+            MusicFoldersIn::Apply => {
+                let selected: Vec<String> = self
+                    .folders
+                    .iter()
+                    .filter(|(_id, check)| check.is_active())
+                    .map(|(id, _check)| id.clone())
+                    .collect();
+
+                {
+                    let mut settings = Settings::get().lock().unwrap();
+                    if settings.music_folders() == selected {
+                        return;
+                    }
+                    settings.set_music_folders(selected);
+                    if let Err(e) = settings.save() {
+                        sender
+                            .output(MusicFoldersOut::DisplayToast(format!(
+                                "error saving settings: {e}"
+                            )))
+                            .unwrap();
+                    }
+                }
+                sender.output(MusicFoldersOut::Changed).unwrap();
+            }
+        }
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     common::{play_state::PlayState, player::Command},
     components::{
         sequence_button::Sequence,
-        sequence_button_impl::{repeat::Repeat, shuffle::Shuffle},
+        sequence_button_impl::{queue_end::QueueEnd, repeat::Repeat, shuffle::Shuffle},
     },
     settings::Settings,
 };
@@ -25,6 +25,7 @@ pub struct PlayControl {
     next_btn: gtk::Button,
     random_btn: gtk::Button,
     repeat_btn: gtk::Button,
+    queue_end_btn: gtk::Button,
 }
 
 #[derive(Debug)]
@@ -39,6 +40,7 @@ pub enum PlayControlIn {
 #[derive(Debug)]
 pub enum PlayControlOut {
     Player(Command),
+    QueueEnd(QueueEnd),
 }
 
 #[component(pub)]
@@ -62,6 +64,12 @@ impl relm4::SimpleComponent for PlayControl {
         let settings = Settings::get().lock().unwrap();
         model.random_btn.set_icon_name(settings.shuffle.current());
         model.repeat_btn.set_icon_name(settings.repeat.current());
+        model
+            .queue_end_btn
+            .set_icon_name(settings.queue_end.current());
+        model
+            .queue_end_btn
+            .set_tooltip_text(settings.queue_end.tooltip());
         drop(settings);
 
         relm4::ComponentParts { model, widgets }
@@ -153,6 +161,22 @@ impl relm4::SimpleComponent for PlayControl {
                         settings.repeat = repeat.clone();
                         sender.output(PlayControlOut::Player(Command::Repeat(repeat))).unwrap();
                     }
+                },
+
+                model.queue_end_btn.clone() {
+                    add_css_class: "flat",
+                    set_focus_on_click: false,
+
+                    connect_clicked[sender] => move |btn| {
+                        let mut queue_end = QueueEnd::from_str(&btn.icon_name().unwrap()).unwrap();
+                        queue_end.next();
+                        btn.set_icon_name(queue_end.current());
+                        btn.set_tooltip_text(queue_end.tooltip());
+                        let mut settings = Settings::get().lock().unwrap();
+                        settings.queue_end = queue_end.clone();
+                        drop(settings);
+                        sender.output(PlayControlOut::QueueEnd(queue_end)).unwrap();
+                    }
                 }
             },
         }
@@ -25,6 +25,10 @@ pub struct PlayInfo {
 #[derive(Debug)]
 pub enum PlayInfoIn {
     NewState(Box<Option<submarine::data::Child>>),
+    /// format and bit rate of the current song
+    StreamInfo(Option<String>),
+    /// name of an internet radio station and the title it currently plays
+    Radio(String, Option<String>),
     Cover(CoverOut),
     CoverClicked,
 }
@@ -106,6 +110,13 @@ impl relm4::component::Component for PlayInfo {
                     gtk::glib::signal::Propagation::Stop
                 },
             },
+
+            append: stream_info = &gtk::Label {
+                add_css_class: "dim-label",
+                set_halign: gtk::Align::Center,
+                set_visible: false,
+                set_tooltip: &gettext("Format and bit rate the song is played with"),
+            },
         }
     }
 
@@ -124,12 +135,34 @@ impl relm4::component::Component for PlayInfo {
                 widgets.info.set_tooltip_markup(tooltip.as_deref());
 
                 match &self.child {
-                    None => self.covers.emit(CoverIn::LoadId(None)),
+                    None => {
+                        self.covers.emit(CoverIn::LoadId(None));
+                        widgets.stream_info.set_visible(false);
+                    }
                     Some(child) => {
                         self.covers.emit(CoverIn::LoadSong(Box::new(child.clone())));
                     }
                 }
             }
+            PlayInfoIn::StreamInfo(info) => {
+                widgets.stream_info.set_visible(info.is_some());
+                widgets.stream_info.set_text(&info.unwrap_or_default());
+            }
+            PlayInfoIn::Radio(station, title) => {
+                self.child = None;
+                self.covers.emit(CoverIn::LoadId(None));
+                widgets.stream_info.set_visible(false);
+
+                let title = glib::markup_escape_text(title.as_deref().unwrap_or(&station));
+                let station = glib::markup_escape_text(&station);
+                widgets.info.set_markup(&format!(
+                    "<span font_size=\"xx-large\" weight=\"bold\">{title}</span>\n{} <span font_size=\"large\" style=\"italic\" weight=\"bold\">{station}</span>",
+                    gettext("on"),
+                ));
+                widgets
+                    .info
+                    .set_tooltip_markup(Some(&format!("<span weight=\"bold\">{title}</span>")));
+            }
             PlayInfoIn::Cover(msg) => match msg {
                 CoverOut::DisplayToast(title) => {
                     sender.output(PlayInfoOut::DisplayToast(title)).unwrap();
@@ -5,7 +5,10 @@ use relm4::{
     gtk::{
         self, gdk,
         glib::prelude::ToValue,
-        prelude::{BoxExt, ButtonExt, ListBoxRowExt, OrientableExt, SelectionModelExt, WidgetExt},
+        prelude::{
+            BoxExt, ButtonExt, ListBoxRowExt, OrientableExt, SelectionModelExt, ToggleButtonExt,
+            WidgetExt,
+        },
     },
     Component, ComponentController, RelmWidgetExt,
 };
@@ -39,6 +42,9 @@ pub struct PlaylistsView {
     playlists: relm4::factory::FactoryVecDeque<PlaylistElement>,
 
     selected_playlist: Option<submarine::data::PlaylistWithSongs>,
+    smart_playlists: gtk::ListBox,
+    /// index of the selected smart playlist; they are not part of selected_playlist
+    selected_smart: Option<usize>,
     tracks: relm4::typed_view::column::TypedColumnView<PlaylistRow, gtk::MultiSelection>,
     info_cover: relm4::Controller<Cover>,
     info_cover_controller: gtk::DragSource,
@@ -131,6 +137,57 @@ impl PlaylistsView {
             .broadcast(PlaylistElementIn::UpdatePlaylist(updated_list));
     }
 
+    /// songs of the selected smart playlist evaluated against the cache
+    fn smart_songs(&self) -> Option<Vec<submarine::data::Child>> {
+        let subsonic = self.subsonic.borrow();
+        let list = subsonic
+            .smart_playlists()
+            .lists()
+            .get(self.selected_smart?)?;
+        Some(list.evaluate(subsonic.tracks()))
+    }
+
+    /// returns the selected playlist or the songs of the selected smart playlist
+    fn selected_droppable(&self) -> Option<Droppable> {
+        if let Some(songs) = self.smart_songs() {
+            return Some(Droppable::Queue(songs));
+        }
+        let row = self.playlists.widget().selected_row()?;
+        let element = self.playlists.get(row.index() as usize)?;
+        Some(Droppable::Playlist(Box::new(element.info().clone())))
+    }
+
+    fn show_smart_playlists(&self, sender: &relm4::AsyncComponentSender<Self>) {
+        while let Some(row) = self.smart_playlists.first_child() {
+            self.smart_playlists.remove(&row);
+        }
+
+        let subsonic = self.subsonic.borrow();
+        for (index, list) in subsonic.smart_playlists().lists().iter().enumerate() {
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 7);
+            row.append(&gtk::Image::from_icon_name("playlist-symbolic"));
+            let name = gtk::Label::new(Some(&list.name));
+            name.set_hexpand(true);
+            name.set_halign(gtk::Align::Start);
+            name.set_ellipsize(gtk::pango::EllipsizeMode::End);
+            row.append(&name);
+            if list.mirror {
+                let mirror = gtk::Image::from_icon_name("emblem-shared-symbolic");
+                mirror.set_tooltip(&gettext("Mirrored to a playlist on the server"));
+                row.append(&mirror);
+            }
+            let delete = gtk::Button::from_icon_name("user-trash-symbolic");
+            delete.add_css_class("flat");
+            delete.set_tooltip(&gettext("Delete smart playlist"));
+            let sender = sender.clone();
+            delete.connect_clicked(move |_btn| {
+                sender.input(PlaylistsViewIn::DeleteSmartPlaylist(index));
+            });
+            row.append(&delete);
+            self.smart_playlists.append(&row);
+        }
+    }
+
     fn find_nearest_widget(&self, y: f64) -> Option<(f64, u32)> {
         (0..self.tracks.len())
             .filter_map(|i| self.tracks.get(i).map(|t| (i, t)))
@@ -172,9 +229,13 @@ pub enum PlaylistsViewIn {
     Cover(CoverOut),
     NewPlaylist(submarine::data::PlaylistWithSongs),
     DeletePlaylist(relm4::factory::DynamicIndex),
+    SmartPlaylistsChanged,
+    SmartSelected(i32),
+    DeleteSmartPlaylist(usize),
     UpdateFavoriteSong(String, bool),
     UpdatePlayCountSong(String, Option<i64>),
     DownloadClicked,
+    PinClicked(bool),
     Selected(i32),
     DropHover(f64),
     DropMotionLeave,
@@ -188,18 +249,21 @@ pub enum PlaylistsViewIn {
 
 #[derive(Debug)]
 pub enum PlaylistsViewOut {
-    ReplaceQueue(submarine::data::PlaylistWithSongs),
-    AddToQueue(submarine::data::PlaylistWithSongs),
-    AppendToQueue(submarine::data::PlaylistWithSongs),
+    ReplaceQueue(Droppable),
+    AddToQueue(Droppable),
+    AppendToQueue(Droppable),
     DeletePlaylist(
         relm4::factory::DynamicIndex,
         submarine::data::PlaylistWithSongs,
     ),
+    DeleteSmartPlaylist(usize),
     CreateEmptyPlaylist,
     CreatePlaylist(Droppable),
     RenamePlaylist(submarine::data::Playlist),
     DisplayToast(String),
     Download(Droppable),
+    Pin(Droppable, bool),
+    Share(Droppable),
     FavoriteClicked(String, bool),
     ClickedArtist(Id),
     ClickedAlbum(Id),
@@ -251,6 +315,8 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                 .forward(sender.input_sender(), PlaylistsViewIn::PlaylistElement),
 
             selected_playlist: None,
+            smart_playlists: gtk::ListBox::default(),
+            selected_smart: None,
             tracks,
             info_cover: Cover::builder()
                 .launch((subsonic, None))
@@ -281,6 +347,7 @@ impl relm4::component::AsyncComponent for PlaylistsView {
             });
 
         // add playlists to list
+        model.show_smart_playlists(&sender);
         let mut guard = model.playlists.guard();
         for playlist in model.subsonic.borrow().playlists() {
             guard.push_back((model.subsonic.clone(), playlist.clone()));
@@ -386,7 +453,30 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                             }
                         }
                     }
-                }
+                },
+
+                // local smart playlists
+                #[name = "smart_section"]
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 7,
+                    set_visible: !model.subsonic.borrow().smart_playlists().lists().is_empty(),
+
+                    gtk::Label {
+                        add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                        set_label: &gettext("Smart playlists"),
+                    },
+                    model.smart_playlists.clone() -> gtk::ListBox {
+                        add_css_class: granite::STYLE_CLASS_FRAME,
+                        add_css_class: granite::STYLE_CLASS_RICH_LIST,
+
+                        connect_row_selected[sender] => move |_listbox, row| {
+                            if let Some(row) = row {
+                                sender.input(PlaylistsViewIn::SmartSelected(row.index()));
+                            }
+                        },
+                    },
+                },
             },
 
             gtk::Box {
@@ -472,6 +562,19 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                                             },
                                             set_tooltip: &gettext("Click to select a folder to download this album to"),
                                             connect_clicked => PlaylistsViewIn::DownloadClicked,
+                                        },
+                                        #[name = "pin_playlist"]
+                                        gtk::ToggleButton {
+                                            gtk::Box {
+                                                gtk::Image {
+                                                    set_icon_name: Some("airplane-mode-symbolic"),
+                                                    set_pixel_size: 20,
+                                                },
+                                            },
+                                            set_tooltip: &gettext("Keep this playlist available offline"),
+                                            connect_clicked[sender] => move |btn| {
+                                                sender.input(PlaylistsViewIn::PinClicked(btn.is_active()));
+                                            }
                                         }
                                     }
                                 }
@@ -609,6 +712,9 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                 PlaylistElementOut::Clicked(index) => {
                     sender.input(PlaylistsViewIn::Selected(index.current_index() as i32));
                 }
+                PlaylistElementOut::Share(drop) => {
+                    sender.output(PlaylistsViewOut::Share(drop)).unwrap();
+                }
                 PlaylistElementOut::DropAppend(drop, list) => {
                     sender.input(PlaylistsViewIn::DragCssReset);
 
@@ -720,32 +826,19 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                     .unwrap(),
             },
             PlaylistsViewIn::ReplaceQueue => {
-                let Some(row) = self.playlists.widget().selected_row() else {
-                    unreachable!("replace should not be possible when no playlists selected");
-                };
-                if let Some(element) = self.playlists.get(row.index() as usize) {
-                    sender
-                        .output(PlaylistsViewOut::ReplaceQueue(element.info().clone()))
-                        .unwrap();
+                if let Some(drop) = self.selected_droppable() {
+                    sender.output(PlaylistsViewOut::ReplaceQueue(drop)).unwrap();
                 }
             }
             PlaylistsViewIn::AddToQueue => {
-                let Some(row) = self.playlists.widget().selected_row() else {
-                    unreachable!("add to queue should not be possible when no playlists selected");
-                };
-                if let Some(element) = self.playlists.get(row.index() as usize) {
-                    sender
-                        .output(PlaylistsViewOut::AddToQueue(element.info().clone()))
-                        .unwrap();
+                if let Some(drop) = self.selected_droppable() {
+                    sender.output(PlaylistsViewOut::AddToQueue(drop)).unwrap();
                 }
             }
             PlaylistsViewIn::AppendToQueue => {
-                let Some(row) = self.playlists.widget().selected_row() else {
-                    unreachable!("add to queue should not be possible when no playlists selected");
-                };
-                if let Some(element) = self.playlists.get(row.index() as usize) {
+                if let Some(drop) = self.selected_droppable() {
                     sender
-                        .output(PlaylistsViewOut::AppendToQueue(element.info().clone()))
+                        .output(PlaylistsViewOut::AppendToQueue(drop))
                         .unwrap();
                 }
             }
@@ -772,6 +865,78 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                 widgets.track_stack.set_visible_child_name("tracks-stock");
                 self.playlists.guard().remove(index.current_index());
             }
+            PlaylistsViewIn::SmartPlaylistsChanged => {
+                if self.selected_smart.is_some() {
+                    self.selected_smart = None;
+                    widgets.track_stack.set_visible_child_name("tracks-stock");
+                }
+                self.show_smart_playlists(&sender);
+                let empty = self.subsonic.borrow().smart_playlists().lists().is_empty();
+                widgets.smart_section.set_visible(!empty);
+            }
+            PlaylistsViewIn::SmartSelected(index) => {
+                self.selected_smart = Some(index as usize);
+                let Some(songs) = self.smart_songs() else {
+                    self.selected_smart = None;
+                    return;
+                };
+                let subsonic = self.subsonic.borrow();
+                let Some(list) = subsonic.smart_playlists().lists().get(index as usize) else {
+                    return;
+                };
+
+                // only one playlist is shown at a time
+                self.selected_playlist = None;
+                self.playlists.widget().unselect_all();
+                for element in self.playlists.iter() {
+                    element.change_state(&State::Normal);
+                    element.set_edit_area(EditState::NotActive);
+                }
+
+                // smart playlists can only be changed through their filters
+                self.drop_target_copy.set_types(&[]);
+                self.drop_target_move.set_types(&[]);
+                widgets.pin_playlist.set_active(false);
+                widgets.pin_playlist.set_sensitive(false);
+
+                // set info
+                widgets.track_stack.set_visible_child_name("tracks");
+                self.info_cover.emit(CoverIn::LoadId(
+                    songs.first().and_then(|song| song.cover_art.clone()),
+                ));
+                widgets.info_title.set_text(&list.name);
+                let duration: i64 = songs
+                    .iter()
+                    .map(|song| i64::from(song.duration.unwrap_or(0)))
+                    .sum();
+                widgets.info_details.set_text(&format!(
+                    "{}: {} • {}: {} • {}: {}",
+                    gettext("Songs"),
+                    songs.len(),
+                    gettext("Length"),
+                    convert_for_label(duration * 1000),
+                    gettext("Sorted by"),
+                    list.sort.translate(),
+                ));
+
+                // cover drags the evaluated songs
+                let drop = Droppable::Queue(songs.clone());
+                let content = gtk::gdk::ContentProvider::for_value(&drop.to_value());
+                self.info_cover_controller.set_content(Some(&content));
+                std::mem::drop(subsonic);
+
+                //set tracks
+                self.tracks.clear();
+                for track in songs {
+                    self.tracks
+                        .append(PlaylistRow::new(&self.subsonic, track, sender.clone()));
+                }
+            }
+            PlaylistsViewIn::DeleteSmartPlaylist(index) => {
+                sender
+                    .output(PlaylistsViewOut::DeleteSmartPlaylist(index))
+                    .unwrap();
+            }
             PlaylistsViewIn::UpdateFavoriteSong(id, state) => {
                 (0..self.tracks.len())
                     .filter_map(|i| self.tracks.get(i))
@@ -797,12 +962,17 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                 .filter(|t| t.borrow().item().id == id)
                 .for_each(|track| track.borrow_mut().set_play_count(play_count)),
             PlaylistsViewIn::DownloadClicked => {
+                if let Some(drop) = self.selected_droppable() {
+                    sender.output(PlaylistsViewOut::Download(drop)).unwrap();
+                }
+            }
+            PlaylistsViewIn::PinClicked(pin) => {
                 let Some(row) = self.playlists.widget().selected_row() else {
-                    unreachable!("add to queue should not be possible when no playlists selected");
+                    unreachable!("pinning should not be possible when no playlists selected");
                 };
                 if let Some(element) = self.playlists.get(row.index() as usize) {
                     let drop = Droppable::Playlist(Box::new(element.info().clone()));
-                    sender.output(PlaylistsViewOut::Download(drop)).unwrap();
+                    sender.output(PlaylistsViewOut::Pin(drop, pin)).unwrap();
                 }
             }
             PlaylistsViewIn::Selected(index) => {
@@ -815,6 +985,10 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                 let is_write_protected = element.write_protected();
                 drop(guard);
 
+                self.selected_smart = None;
+                self.smart_playlists.unselect_all();
+                widgets.pin_playlist.set_sensitive(true);
+
                 // check for smart playlist
                 if is_write_protected {
                     // update list
@@ -882,6 +1056,12 @@ impl relm4::component::AsyncComponent for PlaylistsView {
                     .emit(CoverIn::LoadPlaylist(Box::new(list.clone())));
                 widgets.info_title.set_text(&list.base.name);
                 widgets.info_details.set_text(&build_info_string(list));
+                let pinned = self
+                    .subsonic
+                    .borrow()
+                    .offline()
+                    .is_pinned(&Id::playlist(&list.base.id));
+                widgets.pin_playlist.set_active(pinned);
 
                 //update drag controller for cover
                 let drop = Droppable::Playlist(Box::new(list.clone()));
@@ -0,0 +1,468 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, EditableExt, OrientableExt, SelectionModelExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common::{self, types::Droppable},
+    factory::podcast_row::{
+        ChannelColumn, ChannelRow, EpisodeRow, LengthColumn, PublishedColumn, StatusColumn,
+        TitleColumn,
+    },
+    settings::Settings,
+    subsonic::Subsonic,
+    subsonic_podcasts::SubsonicPodcasts,
+};
+
+/// how many episodes are shown as newest episodes
+const NEWEST_EPISODES: i32 = 30;
+
+#[derive(Debug)]
+pub struct PodcastsView {
+    subsonic: Rc<RefCell<Subsonic>>,
+    channels: relm4::typed_view::column::TypedColumnView<ChannelRow, gtk::SingleSelection>,
+    episodes: relm4::typed_view::column::TypedColumnView<EpisodeRow, gtk::MultiSelection>,
+    /// channel whose episodes are shown, None shows the newest episodes
+    channel: Option<submarine::data::PodcastChannel>,
+}
+
+impl PodcastsView {
+    fn selected_episodes(&self) -> Vec<submarine::data::PodcastEpisode> {
+        let model = self.episodes.view.model().unwrap();
+        (0..self.episodes.selection_model.n_items())
+            .filter(|i| model.is_selected(*i))
+            .filter_map(|i| self.episodes.get_visible(i))
+            .map(|row| row.borrow().item().clone())
+            .collect()
+    }
+
+    /// returns the selected episodes as songs, episodes that are not downloaded are skipped
+    fn selected_songs(&self) -> Option<Droppable> {
+        let episodes = self.selected_episodes();
+        let songs: Vec<submarine::data::Child> = episodes
+            .iter()
+            .filter_map(SubsonicPodcasts::playable)
+            .collect();
+        if songs.is_empty() {
+            return None;
+        }
+
+        // remember position of episodes
+        let mut subsonic = self.subsonic.borrow_mut();
+        for song in &songs {
+            subsonic.podcasts_mut().add_episode(&song.id);
+        }
+        Some(Droppable::Queue(songs))
+    }
+
+    fn show_episodes(&mut self, episodes: Vec<submarine::data::PodcastEpisode>) {
+        let subsonic = self.subsonic.borrow();
+        self.episodes.clear();
+        for episode in episodes {
+            let position = episode
+                .stream_id
+                .as_ref()
+                .and_then(|id| subsonic.podcasts().position(id));
+            self.episodes.append(EpisodeRow::new(episode, position));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PodcastsViewIn {
+    SearchChanged,
+    Reload,
+    ChannelSelected(u32),
+    ShowNewest,
+    ReloadEpisodes,
+    Subscribe,
+    Unsubscribe,
+    Refresh,
+    DownloadEpisodes,
+    AppendToQueue,
+    AddToQueue,
+    ReplaceQueue,
+}
+
+#[derive(Debug)]
+pub enum PodcastsViewOut {
+    DisplayToast(String),
+    AppendToQueue(Droppable),
+    AddToQueue(Droppable),
+    ReplaceQueue(Droppable),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for PodcastsView {
+    type Init = Rc<RefCell<Subsonic>>;
+    type Input = PodcastsViewIn;
+    type Output = PodcastsViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        subsonic: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut channels =
+            relm4::typed_view::column::TypedColumnView::<ChannelRow, gtk::SingleSelection>::new();
+        channels.append_column::<ChannelColumn>();
+        channels
+            .get_columns()
+            .get("Channel")
+            .unwrap()
+            .set_title(Some(&gettext("Channel")));
+
+        let mut episodes =
+            relm4::typed_view::column::TypedColumnView::<EpisodeRow, gtk::MultiSelection>::new();
+        episodes.append_column::<TitleColumn>();
+        episodes.append_column::<PublishedColumn>();
+        episodes.append_column::<LengthColumn>();
+        episodes.append_column::<StatusColumn>();
+
+        let columns = episodes.get_columns();
+        columns
+            .get("Title")
+            .unwrap()
+            .set_title(Some(&gettext("Title")));
+        columns
+            .get("Published")
+            .unwrap()
+            .set_title(Some(&gettext("Published")));
+        columns
+            .get("Length")
+            .unwrap()
+            .set_title(Some(&gettext("Length")));
+        columns
+            .get("Status")
+            .unwrap()
+            .set_title(Some(&gettext("Status")));
+
+        // add search filter
+        episodes.add_filter(move |row| {
+            let search = Settings::get().lock().unwrap().search_text.clone();
+            common::search_matching(row.item().child.title.clone(), search)
+        });
+
+        let model = Self {
+            subsonic,
+            channels,
+            episodes,
+            channel: None,
+        };
+
+        let widgets = view_output!();
+
+        sender.input(PodcastsViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            gtk::Paned {
+                add_css_class: "tracks-view",
+                set_hexpand: true,
+                set_position: 300,
+                set_shrink_start_child: false,
+                set_shrink_end_child: false,
+
+                // channels
+                #[wrap(Some)]
+                set_start_child = &gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 8,
+
+                    gtk::Box {
+                        set_spacing: 7,
+                        set_margin_horizontal: 7,
+
+                        append: subscribe_url = &gtk::Entry {
+                            set_hexpand: true,
+                            set_placeholder_text: Some(&gettext("Url of podcast feed")),
+                            connect_activate => PodcastsViewIn::Subscribe,
+                        },
+                        gtk::Button {
+                            set_icon_name: "list-add-symbolic",
+                            set_tooltip: &gettext("Subscribe to podcast"),
+                            connect_clicked => PodcastsViewIn::Subscribe,
+                        },
+                    },
+                    gtk::Box {
+                        set_spacing: 7,
+                        set_margin_horizontal: 7,
+
+                        gtk::Button {
+                            set_label: &gettext("Newest episodes"),
+                            connect_clicked => PodcastsViewIn::ShowNewest,
+                        },
+                        gtk::Button {
+                            set_icon_name: "view-refresh-symbolic",
+                            set_tooltip: &gettext("Check all podcasts for new episodes"),
+                            connect_clicked => PodcastsViewIn::Refresh,
+                        },
+                        gtk::Button {
+                            set_icon_name: "user-trash-symbolic",
+                            set_tooltip: &gettext("Unsubscribe from selected podcast"),
+                            connect_clicked => PodcastsViewIn::Unsubscribe,
+                        },
+                    },
+                    gtk::ScrolledWindow {
+                        set_vexpand: true,
+
+                        model.channels.view.clone() {
+                            set_single_click_activate: true,
+
+                            connect_activate[sender] => move |_column_view, index| {
+                                sender.input(PodcastsViewIn::ChannelSelected(index));
+                            },
+                        }
+                    }
+                },
+
+                // episodes
+                #[wrap(Some)]
+                set_end_child = &gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 8,
+
+                    gtk::WindowHandle {
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 8,
+                            set_margin_horizontal: 7,
+
+                            #[name = "channel_title"]
+                            gtk::Label {
+                                add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                                set_halign: gtk::Align::Start,
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_text: &gettext("Podcasts"),
+                            },
+                            gtk::Box {
+                                set_spacing: 15,
+
+                                gtk::Button {
+                                    gtk::Image {
+                                        set_icon_name: Some("queue-append-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                    set_tooltip: &gettext("Append selected episodes to end of queue"),
+                                    connect_clicked => PodcastsViewIn::AppendToQueue,
+                                },
+                                gtk::Button {
+                                    gtk::Image {
+                                        set_icon_name: Some("queue-insert-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                    set_tooltip: &gettext("Insert selected episodes after currently played or paused item"),
+                                    connect_clicked => PodcastsViewIn::AddToQueue,
+                                },
+                                gtk::Button {
+                                    gtk::Image {
+                                        set_icon_name: Some("queue-replace-symbolic"),
+                                        set_pixel_size: 20,
+                                    },
+                                    set_tooltip: &gettext("Replaces current queue with selected episodes"),
+                                    connect_clicked => PodcastsViewIn::ReplaceQueue,
+                                },
+                                gtk::Button {
+                                    set_icon_name: "folder-download-symbolic",
+                                    set_tooltip: &gettext("Let the server download selected episodes"),
+                                    connect_clicked => PodcastsViewIn::DownloadEpisodes,
+                                },
+                            }
+                        }
+                    },
+                    gtk::ScrolledWindow {
+                        set_vexpand: true,
+
+                        model.episodes.view.clone() {
+                            set_widget_name: "podcasts-view-episodes",
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            PodcastsViewIn::SearchChanged => _ = self.episodes.notify_filter_changed(0),
+            PodcastsViewIn::Reload => {
+                let client = Client::get().unwrap();
+                let channels = match client.get_podcasts(Some(false), None::<String>).await {
+                    Ok(channels) => channels,
+                    Err(e) => {
+                        sender
+                            .output(PodcastsViewOut::DisplayToast(format!(
+                                "could not fetch podcasts: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+
+                self.channels.clear();
+                for channel in channels {
+                    self.channels.append(ChannelRow::new(channel));
+                }
+                sender.input(PodcastsViewIn::ShowNewest);
+            }
+            PodcastsViewIn::ChannelSelected(index) => {
+                let Some(channel) = self.channels.get_visible(index) else {
+                    return;
+                };
+                self.channel = Some(channel.borrow().item().clone());
+                sender.input(PodcastsViewIn::ReloadEpisodes);
+            }
+            PodcastsViewIn::ShowNewest => {
+                self.channel = None;
+                sender.input(PodcastsViewIn::ReloadEpisodes);
+            }
+            PodcastsViewIn::ReloadEpisodes => {
+                let client = Client::get().unwrap();
+                let (title, episodes) = match &self.channel {
+                    None => (
+                        gettext("Newest episodes"),
+                        client.get_newest_podcasts(Some(NEWEST_EPISODES)).await,
+                    ),
+                    Some(channel) => (
+                        channel.title.clone().unwrap_or(channel.url.clone()),
+                        client
+                            .get_podcasts(Some(true), Some(&channel.id))
+                            .await
+                            .map(|channels| {
+                                channels
+                                    .into_iter()
+                                    .flat_map(|channel| channel.episode)
+                                    .collect()
+                            }),
+                    ),
+                };
+                match episodes {
+                    Ok(episodes) => {
+                        widgets.channel_title.set_text(&title);
+                        self.show_episodes(episodes);
+                    }
+                    Err(e) => sender
+                        .output(PodcastsViewOut::DisplayToast(format!(
+                            "could not fetch episodes: {e:?}"
+                        )))
+                        .unwrap(),
+                }
+            }
+            PodcastsViewIn::Subscribe => {
+                let url = widgets.subscribe_url.text().trim().to_string();
+                if url::Url::parse(&url).is_err() {
+                    sender
+                        .output(PodcastsViewOut::DisplayToast(gettext(
+                            "The url of the podcast is not valid",
+                        )))
+                        .unwrap();
+                    return;
+                }
+
+                let client = Client::get().unwrap();
+                if let Err(e) = client.create_podcast_channel(&url).await {
+                    sender
+                        .output(PodcastsViewOut::DisplayToast(format!(
+                            "could not subscribe to podcast: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                widgets.subscribe_url.set_text("");
+                sender.input(PodcastsViewIn::Reload);
+            }
+            PodcastsViewIn::Unsubscribe => {
+                let index = self.channels.selection_model.selected();
+                let Some(channel) = self.channels.get_visible(index) else {
+                    return;
+                };
+                let id = channel.borrow().item().id.clone();
+
+                let client = Client::get().unwrap();
+                if let Err(e) = client.delete_podcast_channel(&id).await {
+                    sender
+                        .output(PodcastsViewOut::DisplayToast(format!(
+                            "could not unsubscribe from podcast: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                sender.input(PodcastsViewIn::Reload);
+            }
+            PodcastsViewIn::Refresh => {
+                let client = Client::get().unwrap();
+                if let Err(e) = client.refresh_podcasts().await {
+                    sender
+                        .output(PodcastsViewOut::DisplayToast(format!(
+                            "could not refresh podcasts: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                sender
+                    .output(PodcastsViewOut::DisplayToast(gettext(
+                        "The server checks for new episodes",
+                    )))
+                    .unwrap();
+            }
+            PodcastsViewIn::DownloadEpisodes => {
+                let client = Client::get().unwrap();
+                let episodes = self.selected_episodes();
+                for episode in &episodes {
+                    if let Err(e) = client.download_podcast_episode(&episode.child.id).await {
+                        sender
+                            .output(PodcastsViewOut::DisplayToast(format!(
+                                "could not download episode: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                }
+
+                // show new status
+                sender.input(PodcastsViewIn::ReloadEpisodes);
+            }
+            PodcastsViewIn::AppendToQueue => match self.selected_songs() {
+                Some(drop) => sender.output(PodcastsViewOut::AppendToQueue(drop)).unwrap(),
+                None => sender
+                    .output(PodcastsViewOut::DisplayToast(gettext(
+                        "Selected episodes are not downloaded on the server",
+                    )))
+                    .unwrap(),
+            },
+            PodcastsViewIn::AddToQueue => match self.selected_songs() {
+                Some(drop) => sender.output(PodcastsViewOut::AddToQueue(drop)).unwrap(),
+                None => sender
+                    .output(PodcastsViewOut::DisplayToast(gettext(
+                        "Selected episodes are not downloaded on the server",
+                    )))
+                    .unwrap(),
+            },
+            PodcastsViewIn::ReplaceQueue => match self.selected_songs() {
+                Some(drop) => sender.output(PodcastsViewOut::ReplaceQueue(drop)).unwrap(),
+                None => sender
+                    .output(PodcastsViewOut::DisplayToast(gettext(
+                        "Selected episodes are not downloaded on the server",
+                    )))
+                    .unwrap(),
+            },
+        }
+    }
+}
@@ -27,6 +27,7 @@ use crate::{
         DragIndicatable,
     },
     gtk_helper::{
+        context_menu::add_context_menu,
         scroll::{AutomaticScrolling, ScrolledWindowExt},
         stack::StackExt,
     },
@@ -34,13 +35,15 @@ use crate::{
     subsonic::Subsonic,
 };
 
-const PREFIX: &str = "Buoy";
 const QUEUE_INFOS: &str = "Queue-Infos";
 
 #[derive(Debug, Serialize, Deserialize)]
 struct QueueCache {
     songs: Vec<submarine::data::Child>,
     current: Option<usize>,
+    /// position in ms of the current song
+    #[serde(default)]
+    position: i64,
 }
 
 #[derive(Debug, PartialEq, PartialOrd)]
@@ -74,8 +77,13 @@ impl TryFrom<String> for QueueStack {
 pub struct Queue {
     subsonic: Rc<RefCell<Subsonic>>,
     randomized_indices: Vec<usize>,
+    prepared: Option<usize>,
     tracks: relm4::typed_view::list::TypedListView<QueueSongRow, gtk::MultiSelection>,
     scrolling: Rc<RefCell<AutomaticScrolling>>,
+    /// cache folder of the profile the queue belongs to
+    cache_dir: Option<std::path::PathBuf>,
+    /// position in ms of the current song loaded from cache
+    position: Option<i64>,
 }
 
 impl Queue {
@@ -142,6 +150,49 @@ impl Queue {
         }
     }
 
+    /// index of the song that plays after the current one, respects repeat and shuffle
+    pub fn next_index(&self) -> Option<u32> {
+        if self.tracks.is_empty() {
+            return None;
+        }
+
+        let settings = Settings::get().lock().unwrap();
+        let repeat = settings.repeat.clone();
+        let shuffle = settings.shuffle.clone();
+        drop(settings);
+
+        match self.current() {
+            None => Some(0),
+            Some((index, _track)) => match index as u32 {
+                // repeat current song
+                i if repeat == Repeat::One => Some(i),
+                // at end of queue with repeat queue
+                i if i + 1 == self.tracks.len()
+                    && shuffle != Shuffle::Shuffle
+                    && repeat == Repeat::All =>
+                {
+                    Some(0)
+                }
+                // shuffle ignores repeat all
+                i if shuffle == Shuffle::Shuffle => {
+                    let idx = self
+                        .randomized_indices
+                        .iter()
+                        .position(|idx| i == *idx as u32)?;
+                    self.randomized_indices
+                        .iter()
+                        .cycle()
+                        .nth(idx + 1)
+                        .map(|idx| *idx as u32)
+                }
+                // at end of queue
+                i if i + 1 == self.tracks.len() => None,
+                // play next song
+                i => Some(i + 1),
+            },
+        }
+    }
+
     pub fn current(&self) -> Option<(usize, QueueSongRow)> {
         self.iter_tracks()
             .enumerate()
@@ -179,29 +230,40 @@ impl Queue {
             .map(|(i, track)| (i, track.borrow().clone()))
     }
 
-    pub fn save(&self) -> anyhow::Result<()> {
+    fn cache_path(&self) -> anyhow::Result<std::path::PathBuf> {
+        let dir = self
+            .cache_dir
+            .as_ref()
+            .ok_or(std::io::Error::other("cant find queue cache dir"))?;
+        Ok(dir.join(QUEUE_INFOS))
+    }
+
+    /// position in ms of the current song when the queue was saved
+    pub fn position(&self) -> Option<i64> {
+        self.position
+    }
+
+    pub fn save(&self, position: i64) -> anyhow::Result<()> {
         let songs = self.songs();
         let current = self.current().map(|(i, _t)| i);
 
-        let queue = QueueCache { songs, current };
+        let queue = QueueCache {
+            songs,
+            current,
+            position,
+        };
 
         let mut cache = vec![];
         let mut serializer = rmp_serde::Serializer::new(&mut cache);
         queue.serialize(&mut serializer)?;
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(QUEUE_INFOS);
+        let cache_path = self.cache_path()?;
         std::fs::write(cache_path, cache)?;
 
         Ok(())
     }
 
     pub fn load(&mut self, sender: &relm4::ComponentSender<Queue>) -> anyhow::Result<()> {
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(QUEUE_INFOS);
+        let cache_path = self.cache_path()?;
 
         // if there is no cache start empty
         if !cache_path.exists() {
@@ -222,6 +284,7 @@ impl Queue {
         if let Some(index) = queue_cache.current {
             if let Some(track) = self.tracks.get(index as u32) {
                 track.borrow_mut().set_play_state(&PlayState::Pause);
+                self.position = Some(queue_cache.position);
             }
         }
 
@@ -230,10 +293,7 @@ impl Queue {
 
     pub fn delete_cache(&self) -> anyhow::Result<()> {
         // delete queue info
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant find queue cache info"))?
-            .join(PREFIX)
-            .join(QUEUE_INFOS);
+        let cache_path = self.cache_path()?;
 
         // if there is no cache then there is no cache to delete
         if !cache_path.exists() {
@@ -255,8 +315,12 @@ pub enum QueueIn {
     ToggleShuffle(Shuffle),
     PlayNext,
     PlayPrevious,
+    PrepareNext,
+    TrackChanged,
     Append(Droppable),
     InsertAfterCurrentlyPlayed(Droppable),
+    /// inserts songs after the current one and plays the first of them
+    InsertAndPlay(Droppable),
     Replace(Droppable),
     UpdateFavoriteSong(String, bool),
     UpdatePlayCountSong(String, Option<i64>),
@@ -273,12 +337,17 @@ pub enum QueueIn {
     DropInsert(Droppable, f64, f64),
     SelectionChanged,
     SetCurrent(Option<usize>),
+    /// replaces the queue with songs, current index and position in ms, e.g. from the server
+    Restore(Vec<submarine::data::Child>, Option<usize>, i64),
     DisableJumpToCurrent,
+    ShareSelected,
 }
 
 #[derive(Debug)]
 pub enum QueueOut {
     Play(Box<submarine::data::Child>),
+    PrepareNext(Box<Option<submarine::data::Child>>),
+    TrackChanged(Box<submarine::data::Child>),
     QueueEmpty,
     QueueNotEmpty,
     Player(Command),
@@ -287,6 +356,9 @@ pub enum QueueOut {
     FavoriteClicked(String, bool),
     SongChanged,
     QueueSongsChanged,
+    /// current song and position in ms of a restored queue
+    Restored(Box<submarine::data::Child>, i64),
+    Share(Droppable),
 }
 
 #[relm4::component(pub)]
@@ -308,8 +380,11 @@ impl relm4::Component for Queue {
         let mut model = Queue {
             subsonic,
             randomized_indices: vec![],
+            prepared: None,
             tracks,
             scrolling: Rc::new(RefCell::new(AutomaticScrolling::default())),
+            cache_dir: Settings::cache_dir().ok(),
+            position: None,
         };
 
         //init queue
@@ -332,6 +407,16 @@ impl relm4::Component for Queue {
                 send.input(QueueIn::SelectionChanged);
             });
 
+        // context menu of selected songs
+        let send = sender.clone();
+        add_context_menu(
+            &model.tracks.view,
+            vec![(
+                gettext("Share"),
+                Box::new(move || send.input(QueueIn::ShareSelected)),
+            )],
+        );
+
         // connect signal ScrolledWindow scrolled
         let scroll_status = model.scrolling.clone();
         let send = sender.clone();
@@ -595,6 +680,14 @@ impl relm4::Component for Queue {
                 sender.input(QueueIn::DragCssReset);
                 sender.output(QueueOut::QueueSongsChanged).unwrap();
             }
+            QueueIn::InsertAndPlay(drop) => {
+                let index = match self.current() {
+                    Some((index, _track)) => index + 1,
+                    None => self.tracks.len() as usize,
+                };
+                sender.input(QueueIn::InsertAfterCurrentlyPlayed(drop));
+                sender.input(QueueIn::Activate(index as u32));
+            }
             QueueIn::InsertAfterCurrentlyPlayed(drop) => {
                 let songs = drop.get_songs(&self.subsonic);
 
@@ -634,6 +727,18 @@ impl relm4::Component for Queue {
                 sender.output(QueueOut::QueueEmpty).unwrap();
                 sender.output(QueueOut::QueueSongsChanged).unwrap();
             }
+            QueueIn::ShareSelected => {
+                let songs: Vec<submarine::data::Child> = (0..self.tracks.len())
+                    .filter(|i| self.tracks.view.model().unwrap().is_selected(*i))
+                    .filter_map(|i| self.tracks.get(i))
+                    .map(|track| track.borrow().item().clone())
+                    .collect();
+                if !songs.is_empty() {
+                    sender
+                        .output(QueueOut::Share(Droppable::Queue(songs)))
+                        .unwrap();
+                }
+            }
             QueueIn::Remove => {
                 let selected_rows: Vec<u32> = (0..self.tracks.len())
                     .filter(|i| self.tracks.view.model().unwrap().is_selected(*i))
@@ -681,56 +786,41 @@ impl relm4::Component for Queue {
                     .unwrap();
             }
             QueueIn::PlayNext => {
-                if self.tracks.is_empty() {
-                    return;
-                }
-
-                let settings = Settings::get().lock().unwrap();
-                let repeat = settings.repeat.clone();
-                let shuffle = settings.shuffle.clone();
-                drop(settings);
-
-                match self.current() {
-                    None => self.tracks.get(0).unwrap().borrow_mut().activate(),
-                    Some((index, _track)) => {
-                        match index as u32 {
-                            // at end of queue with repeat current song
-                            i if repeat == Repeat::One => {
-                                self.tracks.get(i).unwrap().borrow_mut().activate();
-                            }
-                            // at end of queue with repeat queue
-                            i if i + 1 == self.tracks.len()
-                                && shuffle != Shuffle::Shuffle
-                                && repeat == Repeat::All =>
-                            {
-                                self.tracks.get(0).unwrap().borrow_mut().activate();
-                            }
-                            // shuffle ignores repeat all
-                            i if shuffle == Shuffle::Shuffle => {
-                                let idx = self
-                                    .randomized_indices
-                                    .iter()
-                                    .position(|idx| i == *idx as u32)
-                                    .unwrap();
-                                let idx =
-                                    self.randomized_indices.iter().cycle().nth(idx + 1).unwrap();
-                                self.tracks
-                                    .get(*idx as u32)
-                                    .unwrap()
-                                    .borrow_mut()
-                                    .activate();
-                            }
-                            // at end of queue
-                            i if i + 1 == self.tracks.len() => {
-                                self.iter_tracks().for_each(|track| {
-                                    track.borrow_mut().set_play_state(&PlayState::Stop)
-                                });
-                            }
-                            // play next song
-                            i => self.tracks.get(i + 1).unwrap().borrow_mut().activate(),
-                        }
+                match self.next_index() {
+                    Some(i) => self.tracks.get(i).unwrap().borrow_mut().activate(),
+                    // at end of queue
+                    None => {
+                        self.iter_tracks()
+                            .for_each(|track| track.borrow_mut().set_play_state(&PlayState::Stop));
                     }
+                }
+                sender.output(QueueOut::SongChanged).unwrap();
+            }
+            QueueIn::PrepareNext => {
+                let next = self.next_index().and_then(|i| self.tracks.get(i));
+                self.prepared = next.as_ref().map(|track| *track.borrow().uid());
+                let child = next.map(|track| track.borrow().item().clone());
+                sender
+                    .output(QueueOut::PrepareNext(Box::new(child)))
+                    .unwrap();
+            }
+            QueueIn::TrackChanged => {
+                let index = match self.prepared.take().and_then(|uid| self.index_of_uid(uid)) {
+                    Some((index, _track)) => Some(index as u32),
+                    None => self.next_index(),
                 };
+                let Some(track) = index.and_then(|i| self.tracks.get(i)) else {
+                    return;
+                };
+
+                self.iter_tracks()
+                    .for_each(|track| track.borrow_mut().set_play_state(&PlayState::Stop));
+                track.borrow_mut().set_play_state(&PlayState::Play);
+                sender
+                    .output(QueueOut::TrackChanged(Box::new(
+                        track.borrow().item().clone(),
+                    )))
+                    .unwrap();
                 sender.output(QueueOut::SongChanged).unwrap();
             }
             QueueIn::PlayPrevious => {
@@ -1025,6 +1115,39 @@ impl relm4::Component for Queue {
                     }
                 }
             }
+            QueueIn::Restore(songs, current, position) => {
+                self.tracks.clear();
+                for song in &songs {
+                    self.tracks
+                        .append(QueueSongRow::new(&self.subsonic, song, &sender));
+                }
+                sender.input(QueueIn::Rerandomize);
+
+                widgets.clear_items.set_sensitive(!self.tracks.is_empty());
+                if self.tracks.is_empty() {
+                    widgets
+                        .queue_stack
+                        .set_visible_child_enum(&QueueStack::Placeholder);
+                    sender.output(QueueOut::QueueEmpty).unwrap();
+                } else {
+                    widgets
+                        .queue_stack
+                        .set_visible_child_enum(&QueueStack::Queue);
+                    sender.output(QueueOut::QueueNotEmpty).unwrap();
+                }
+                sender.output(QueueOut::QueueSongsChanged).unwrap();
+
+                if let Some(index) = current {
+                    if let Some(track) = self.tracks.get(index as u32) {
+                        track.borrow_mut().set_play_state(&PlayState::Pause);
+                        let child = track.borrow().item().clone();
+                        sender
+                            .output(QueueOut::Restored(Box::new(child), position))
+                            .unwrap();
+                    }
+                }
+                sender.input(QueueIn::DirectJumpToCurrent);
+            }
             QueueIn::DisableJumpToCurrent => {
                 self.scrolling.replace(AutomaticScrolling::Ready);
                 widgets.jump_toggle.set_active(false);
@@ -0,0 +1,374 @@
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, EditableExt, GridExt, OrientableExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common,
+    factory::radio_row::{HomepageColumn, NameColumn, RadioRow, StreamColumn},
+    settings::Settings,
+};
+
+/// station that is shown in the edit panel
+#[derive(Debug)]
+enum Editing {
+    New,
+    Station(String),
+}
+
+#[derive(Debug)]
+pub struct RadioView {
+    stations: relm4::typed_view::column::TypedColumnView<RadioRow, gtk::SingleSelection>,
+    editing: Option<Editing>,
+}
+
+impl RadioView {
+    fn selected(&self) -> Option<submarine::data::InternetRadioStation> {
+        let index = self.stations.selection_model.selected();
+        self.stations
+            .get_visible(index)
+            .map(|row| row.borrow().item().clone())
+    }
+}
+
+#[derive(Debug)]
+pub enum RadioViewIn {
+    SearchChanged,
+    Reload,
+    Activated(u32),
+    PlayClicked,
+    AddClicked,
+    EditClicked,
+    DeleteClicked,
+    SaveClicked,
+    CancelClicked,
+}
+
+#[derive(Debug)]
+pub enum RadioViewOut {
+    DisplayToast(String),
+    Play(Box<submarine::data::InternetRadioStation>),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for RadioView {
+    type Init = ();
+    type Input = RadioViewIn;
+    type Output = RadioViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut stations =
+            relm4::typed_view::column::TypedColumnView::<RadioRow, gtk::SingleSelection>::new();
+        stations.append_column::<NameColumn>();
+        stations.append_column::<StreamColumn>();
+        stations.append_column::<HomepageColumn>();
+
+        let columns = stations.get_columns();
+        columns
+            .get("Name")
+            .unwrap()
+            .set_title(Some(&gettext("Name")));
+        columns
+            .get("Stream")
+            .unwrap()
+            .set_title(Some(&gettext("Stream")));
+        columns
+            .get("Homepage")
+            .unwrap()
+            .set_title(Some(&gettext("Homepage")));
+
+        // add search filter
+        stations.add_filter(move |row| {
+            let search = Settings::get().lock().unwrap().search_text.clone();
+            common::search_matching(row.item().name.clone(), search)
+        });
+
+        let model = Self {
+            stations,
+            editing: None,
+        };
+
+        let widgets = view_output!();
+
+        sender.input(RadioViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            gtk::Box {
+                add_css_class: "tracks-view",
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 8,
+
+                // info
+                gtk::WindowHandle {
+                    gtk::CenterBox {
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 8,
+                            set_margin_horizontal: 7,
+
+                            append: station_count = &gtk::Label {
+                                set_halign: gtk::Align::Start,
+                            },
+                            gtk::Box {
+                                set_spacing: 15,
+
+                                #[name = "play_btn"]
+                                gtk::Button {
+                                    set_icon_name: "media-playback-start-symbolic",
+                                    set_tooltip: &gettext("Play selected station"),
+                                    connect_clicked => RadioViewIn::PlayClicked,
+                                },
+                                gtk::Button {
+                                    set_icon_name: "list-add-symbolic",
+                                    set_tooltip: &gettext("Add a new station"),
+                                    connect_clicked => RadioViewIn::AddClicked,
+                                },
+                                #[name = "edit_btn"]
+                                gtk::Button {
+                                    set_icon_name: "document-edit-symbolic",
+                                    set_tooltip: &gettext("Edit selected station"),
+                                    connect_clicked => RadioViewIn::EditClicked,
+                                },
+                                #[name = "delete_btn"]
+                                gtk::Button {
+                                    set_icon_name: "user-trash-symbolic",
+                                    set_tooltip: &gettext("Delete selected station from server"),
+                                    connect_clicked => RadioViewIn::DeleteClicked,
+                                },
+                            }
+                        },
+                    }
+                },
+
+                gtk::ScrolledWindow {
+                    set_hexpand: true,
+                    set_vexpand: true,
+
+                    model.stations.view.clone() {
+                        set_widget_name: "radio-view-stations",
+                        set_vexpand: true,
+
+                        connect_activate[sender] => move |_column_view, index| {
+                            sender.input(RadioViewIn::Activated(index));
+                        },
+                    }
+                }
+            },
+
+            // edit panel
+            append: edit = &gtk::Revealer {
+                set_transition_duration: 200,
+                set_transition_type: gtk::RevealerTransitionType::SlideLeft,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_size_request: (400, -1),
+                    set_spacing: 8,
+                    set_margin_all: 7,
+
+                    gtk::WindowHandle {
+                        #[name = "edit_title"]
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                        }
+                    },
+
+                    gtk::Grid {
+                        set_row_spacing: 7,
+                        set_column_spacing: 7,
+
+                        attach[0, 0, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Name"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 0, 1, 1]: name = &gtk::Entry {
+                            set_hexpand: true,
+                        },
+                        attach[0, 1, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Stream"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 1, 1, 1]: stream_url = &gtk::Entry {
+                            set_placeholder_text: Some("http(s)://..."),
+                        },
+                        attach[0, 2, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Homepage"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 2, 1, 1]: homepage = &gtk::Entry {
+                            set_placeholder_text: Some(&gettext("optional")),
+                        },
+                    },
+
+                    gtk::Box {
+                        set_halign: gtk::Align::End,
+                        set_spacing: 7,
+
+                        gtk::Button {
+                            set_label: &gettext("Cancel"),
+                            connect_clicked => RadioViewIn::CancelClicked,
+                        },
+                        gtk::Button {
+                            add_css_class: "suggested-action",
+                            set_label: &gettext("Save"),
+                            connect_clicked => RadioViewIn::SaveClicked,
+                        },
+                    }
+                }
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            RadioViewIn::SearchChanged => _ = self.stations.notify_filter_changed(0),
+            RadioViewIn::Reload => {
+                let client = Client::get().unwrap();
+                let stations = match client.get_internet_radio_stations().await {
+                    Ok(stations) => stations,
+                    Err(e) => {
+                        sender
+                            .output(RadioViewOut::DisplayToast(format!(
+                                "could not fetch radio stations: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+
+                self.stations.clear();
+                for station in stations {
+                    self.stations.append(RadioRow::new(station));
+                }
+
+                widgets.station_count.set_label(&format!(
+                    "{}: {}",
+                    gettext("Stations"),
+                    self.stations.len()
+                ));
+                let has_stations = !self.stations.is_empty();
+                widgets.play_btn.set_sensitive(has_stations);
+                widgets.edit_btn.set_sensitive(has_stations);
+                widgets.delete_btn.set_sensitive(has_stations);
+            }
+            RadioViewIn::Activated(index) => {
+                if let Some(row) = self.stations.get_visible(index) {
+                    let station = row.borrow().item().clone();
+                    sender
+                        .output(RadioViewOut::Play(Box::new(station)))
+                        .unwrap();
+                }
+            }
+            RadioViewIn::PlayClicked => {
+                if let Some(station) = self.selected() {
+                    sender
+                        .output(RadioViewOut::Play(Box::new(station)))
+                        .unwrap();
+                }
+            }
+            RadioViewIn::AddClicked => {
+                self.editing = Some(Editing::New);
+                widgets.edit_title.set_label(&gettext("New Station"));
+                widgets.name.set_text("");
+                widgets.stream_url.set_text("");
+                widgets.homepage.set_text("");
+                widgets.edit.set_reveal_child(true);
+            }
+            RadioViewIn::EditClicked => {
+                let Some(station) = self.selected() else {
+                    return;
+                };
+                self.editing = Some(Editing::Station(station.id));
+                widgets.edit_title.set_label(&gettext("Edit Station"));
+                widgets.name.set_text(&station.name);
+                widgets.stream_url.set_text(&station.stream_url);
+                widgets
+                    .homepage
+                    .set_text(station.home_page_url.as_deref().unwrap_or_default());
+                widgets.edit.set_reveal_child(true);
+            }
+            RadioViewIn::DeleteClicked => {
+                let Some(station) = self.selected() else {
+                    return;
+                };
+                let client = Client::get().unwrap();
+                if let Err(e) = client.delete_internet_radio_station(&station.id).await {
+                    sender
+                        .output(RadioViewOut::DisplayToast(format!(
+                            "could not delete radio station: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                sender.input(RadioViewIn::Reload);
+            }
+            RadioViewIn::SaveClicked => {
+                let name = widgets.name.text().trim().to_string();
+                let stream_url = widgets.stream_url.text().trim().to_string();
+                let homepage = widgets.homepage.text().trim().to_string();
+                let homepage = (!homepage.is_empty()).then_some(homepage);
+                if name.is_empty() || url::Url::parse(&stream_url).is_err() {
+                    sender
+                        .output(RadioViewOut::DisplayToast(gettext(
+                            "A station needs a name and a valid stream url",
+                        )))
+                        .unwrap();
+                    return;
+                }
+
+                let Some(editing) = &self.editing else {
+                    return;
+                };
+                let client = Client::get().unwrap();
+                let result = match editing {
+                    Editing::New => {
+                        client
+                            .create_internet_radio_station(&stream_url, &name, homepage)
+                            .await
+                    }
+                    Editing::Station(id) => {
+                        client
+                            .update_internet_radio_station(id, &stream_url, &name, homepage)
+                            .await
+                    }
+                };
+                if let Err(e) = result {
+                    sender
+                        .output(RadioViewOut::DisplayToast(format!(
+                            "could not save radio station: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                self.editing = None;
+                widgets.edit.set_reveal_child(false);
+                sender.input(RadioViewIn::Reload);
+            }
+            RadioViewIn::CancelClicked => {
+                self.editing = None;
+                widgets.edit.set_reveal_child(false);
+            }
+        }
+    }
+}
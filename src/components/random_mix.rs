@@ -0,0 +1,374 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gettextrs::gettext;
+use rand::prelude::SliceRandom;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{
+            BoxExt, ButtonExt, EditableExt, GtkApplicationExt, GtkWindowExt, OrientableExt,
+            WidgetExt,
+        },
+    },
+    RelmWidgetExt,
+};
+
+use crate::{client::Client, common::types::Droppable, settings::Settings, subsonic::Subsonic};
+
+/// constraints of a random mix, None means unconstrained
+#[derive(Debug, Clone, Default)]
+struct MixOptions {
+    size: usize,
+    genre: Option<String>,
+    from_year: Option<usize>,
+    to_year: Option<usize>,
+    folder: Option<String>,
+}
+
+/// dialog that creates a mix of random songs
+#[derive(Debug)]
+pub struct RandomMix {
+    subsonic: Rc<RefCell<Subsonic>>,
+    /// genres in the order of the genre dropdown after the entry for all genres
+    genres: Vec<String>,
+    /// music folder ids in the order of the folder dropdown after the entry for all folders
+    folders: Vec<String>,
+}
+
+impl RandomMix {
+    fn options(&self, widgets: &<Self as relm4::component::AsyncComponent>::Widgets) -> MixOptions {
+        let year = |spin: &gtk::SpinButton| match spin.value() as usize {
+            0 => None,
+            year => Some(year),
+        };
+        let selected = |list: &[String], drop_down: &gtk::DropDown| match drop_down.selected() {
+            0 => None,
+            i => list.get(i as usize - 1).cloned(),
+        };
+
+        MixOptions {
+            size: widgets.size.value() as usize,
+            genre: selected(&self.genres, &widgets.genre),
+            from_year: year(&widgets.from_year),
+            to_year: year(&widgets.to_year),
+            folder: selected(&self.folders, &widgets.folder),
+        }
+    }
+
+    /// asks the server for random songs and samples from the cache when it is not reachable
+    async fn generate(&self, options: &MixOptions) -> Vec<submarine::data::Child> {
+        let client = Client::get().unwrap();
+        match client
+            .get_random_songs(
+                Some(options.size),
+                options.genre.clone(),
+                options.from_year,
+                options.to_year,
+                options.folder.clone(),
+            )
+            .await
+        {
+            Ok(songs) => songs,
+            Err(e) => {
+                tracing::warn!("could not fetch random songs, sampling from cache: {e}");
+                self.sample(options)
+            }
+        }
+    }
+
+    /// random songs of the cache
+    ///
+    /// songs in the cache do not know their music folder, so a folder can only be sampled
+    /// when the cache contains nothing else
+    fn sample(&self, options: &MixOptions) -> Vec<submarine::data::Child> {
+        if let Some(folder) = &options.folder {
+            if Settings::get().lock().unwrap().music_folders() != [folder.clone()] {
+                tracing::warn!("cache can not be filtered by music folder {folder}");
+                return vec![];
+            }
+        }
+
+        let year_matches = |year: Option<i32>| match year {
+            None => options.from_year.is_none() && options.to_year.is_none(),
+            Some(year) => {
+                let year = year as usize;
+                options.from_year.is_none_or(|from| year >= from)
+                    && options.to_year.is_none_or(|to| year <= to)
+            }
+        };
+
+        let mut songs: Vec<submarine::data::Child> = self
+            .subsonic
+            .borrow()
+            .tracks()
+            .iter()
+            .filter(|track| options.genre.is_none() || track.genre == options.genre)
+            .filter(|track| year_matches(track.year))
+            .cloned()
+            .collect();
+        songs.shuffle(&mut rand::rng());
+        songs.truncate(options.size);
+        songs
+    }
+}
+
+#[derive(Debug)]
+pub enum RandomMixIn {
+    Show,
+    ReplaceQueue,
+    AppendToQueue,
+    SavePlaylist,
+}
+
+#[derive(Debug)]
+pub enum RandomMixOut {
+    DisplayToast(String),
+    ReplaceQueue(Droppable),
+    AppendToQueue(Droppable),
+    /// name and songs of a new playlist
+    CreatePlaylist(String, Vec<submarine::data::Child>),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for RandomMix {
+    type Init = Rc<RefCell<Subsonic>>;
+    type Input = RandomMixIn;
+    type Output = RandomMixOut;
+    type CommandOutput = ();
+
+    async fn init(
+        subsonic: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let model = Self {
+            subsonic,
+            genres: vec![],
+            folders: vec![],
+        };
+        let widgets = view_output!();
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        #[name = "random_mix_window"]
+        gtk::Window {
+            set_widget_name: "random-mix-window",
+            set_modal: true,
+            set_transient_for: Some(&relm4::main_application().windows()[0]),
+            set_hide_on_close: true,
+
+            #[wrap(Some)]
+            set_titlebar = &gtk::HeaderBar {
+                add_css_class: granite::STYLE_CLASS_FLAT,
+                add_css_class: granite::STYLE_CLASS_DEFAULT_DECORATION,
+                set_show_title_buttons: true,
+
+                #[wrap(Some)]
+                set_title_widget = &gtk::Label {
+                    add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                    set_label: &gettext("Random mix"),
+                }
+            },
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 15,
+                set_spacing: 15,
+
+                gtk::CenterBox {
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Label {
+                        set_text: &gettext("Number of songs"),
+                    },
+                    #[wrap(Some)]
+                    set_end_widget: size = &gtk::SpinButton {
+                        set_width_request: 100,
+                        set_range: (1f64, 500f64),
+                        set_increments: (1f64, 10f64),
+                        set_digits: 0,
+                        set_value: 50f64,
+                    },
+                },
+
+                gtk::CenterBox {
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Label {
+                        set_text: &gettext("Genre"),
+                    },
+                    #[wrap(Some)]
+                    set_end_widget: genre = &gtk::DropDown {
+                        set_model: Some(&gtk::StringList::new(&[&gettext("All genres")])),
+                    },
+                },
+
+                gtk::CenterBox {
+                    set_tooltip: &gettext("0 does not restrict the year"),
+
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Label {
+                        set_text: &gettext("From year"),
+                    },
+                    #[wrap(Some)]
+                    set_end_widget: from_year = &gtk::SpinButton {
+                        set_width_request: 100,
+                        set_range: (0f64, 3000f64),
+                        set_increments: (1f64, 10f64),
+                        set_digits: 0,
+                    },
+                },
+
+                gtk::CenterBox {
+                    set_tooltip: &gettext("0 does not restrict the year"),
+
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Label {
+                        set_text: &gettext("To year"),
+                    },
+                    #[wrap(Some)]
+                    set_end_widget: to_year = &gtk::SpinButton {
+                        set_width_request: 100,
+                        set_range: (0f64, 3000f64),
+                        set_increments: (1f64, 10f64),
+                        set_digits: 0,
+                    },
+                },
+
+                gtk::CenterBox {
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Label {
+                        set_text: &gettext("Music folder"),
+                    },
+                    #[wrap(Some)]
+                    set_end_widget: folder = &gtk::DropDown {
+                        set_model: Some(&gtk::StringList::new(&[&gettext("All folders")])),
+                    },
+                },
+
+                gtk::CenterBox {
+                    #[wrap(Some)]
+                    set_start_widget = &gtk::Label {
+                        set_text: &gettext("Playlist name"),
+                    },
+                    #[wrap(Some)]
+                    set_end_widget: playlist_name = &gtk::Entry {
+                        set_text: &gettext("Random mix"),
+                    },
+                },
+
+                gtk::Box {
+                    set_halign: gtk::Align::End,
+                    set_spacing: 10,
+
+                    gtk::Button {
+                        set_label: &gettext("Save as playlist"),
+                        connect_clicked => RandomMixIn::SavePlaylist,
+                    },
+                    gtk::Button {
+                        set_label: &gettext("Append to queue"),
+                        connect_clicked => RandomMixIn::AppendToQueue,
+                    },
+                    gtk::Button {
+                        add_css_class: "suggested-action",
+                        set_label: &gettext("Replace queue"),
+                        connect_clicked => RandomMixIn::ReplaceQueue,
+                    },
+                },
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            RandomMixIn::Show => {
+                let client = Client::get().unwrap();
+
+                // genres come from the cache when the server is not reachable
+                self.genres = match client.get_genres().await {
+                    Ok(genres) => genres.into_iter().map(|genre| genre.value).collect(),
+                    Err(e) => {
+                        tracing::warn!("could not fetch genres: {e}");
+                        let mut genres: Vec<String> = self
+                            .subsonic
+                            .borrow()
+                            .tracks()
+                            .iter()
+                            .filter_map(|track| track.genre.clone())
+                            .collect();
+                        genres.sort();
+                        genres.dedup();
+                        genres
+                    }
+                };
+                let all_genres = gettext("All genres");
+                let names: Vec<&str> = std::iter::once(all_genres.as_str())
+                    .chain(self.genres.iter().map(|genre| genre.as_str()))
+                    .collect();
+                widgets.genre.set_model(Some(&gtk::StringList::new(&names)));
+
+                // the cache can not be filtered by folder, so only offer folders when online
+                let folders = match client.get_music_folders().await {
+                    Ok(folders) => {
+                        widgets.folder.set_sensitive(true);
+                        folders
+                    }
+                    Err(e) => {
+                        tracing::warn!("could not fetch music folders: {e}");
+                        widgets.folder.set_sensitive(false);
+                        vec![]
+                    }
+                };
+                self.folders = folders.iter().map(|folder| folder.id.to_string()).collect();
+                let folder_names: Vec<String> = std::iter::once(gettext("All folders"))
+                    .chain(
+                        folders
+                            .into_iter()
+                            .map(|folder| folder.name.unwrap_or(folder.id.to_string())),
+                    )
+                    .collect();
+                let folder_names: Vec<&str> =
+                    folder_names.iter().map(|name| name.as_str()).collect();
+                widgets
+                    .folder
+                    .set_model(Some(&gtk::StringList::new(&folder_names)));
+
+                widgets.random_mix_window.set_visible(true);
+                widgets.random_mix_window.present();
+            }
+            RandomMixIn::ReplaceQueue | RandomMixIn::AppendToQueue | RandomMixIn::SavePlaylist => {
+                let options = self.options(widgets);
+                let songs = self.generate(&options).await;
+                if songs.is_empty() {
+                    sender
+                        .output(RandomMixOut::DisplayToast(gettext(
+                            "No songs match the random mix",
+                        )))
+                        .unwrap();
+                    return;
+                }
+
+                let out = match msg {
+                    RandomMixIn::ReplaceQueue => {
+                        RandomMixOut::ReplaceQueue(Droppable::Queue(songs))
+                    }
+                    RandomMixIn::AppendToQueue => {
+                        RandomMixOut::AppendToQueue(Droppable::Queue(songs))
+                    }
+                    _ => {
+                        let name = widgets.playlist_name.text().to_string();
+                        RandomMixOut::CreatePlaylist(name, songs)
+                    }
+                };
+                sender.output(out).unwrap();
+                widgets.random_mix_window.set_visible(false);
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
+use gettextrs::gettext;
 use relm4::{
     component,
     gtk::{
         self,
-        prelude::{BoxExt, RangeExt, WidgetExt},
+        prelude::{BoxExt, ButtonExt, RangeExt, ScaleExt, WidgetExt},
     },
     RelmWidgetExt,
 };
@@ -31,6 +32,9 @@ pub enum SeekbarIn {
     SeekbarDragged(f64),
     NewRange(i64), // in ms
     SeekTo(i64),   // in ms
+    /// positions in ms of bookmarks in the current song
+    Bookmarks(Vec<i64>),
+    BookmarkClicked,
     Disable,
 }
 
@@ -43,6 +47,8 @@ pub struct SeekbarCurrent {
 #[derive(Debug)]
 pub enum SeekbarOut {
     SeekDragged(i64),
+    /// position in ms to bookmark
+    CreateBookmark(i64),
 }
 
 impl SeekbarCurrent {
@@ -110,6 +116,18 @@ impl relm4::SimpleComponent for Seekbar {
                 #[watch]
                 set_label: &convert_for_label(model.total),
             },
+
+            #[name = "bookmark"]
+            gtk::Button {
+                add_css_class: "flat",
+                set_margin_start: 3,
+                set_icon_name: "bookmark-new-symbolic",
+                set_tooltip: &gettext("Bookmark current position"),
+                set_focus_on_click: false,
+                #[watch]
+                set_sensitive: model.scale.is_sensitive(),
+                connect_clicked => SeekbarIn::BookmarkClicked,
+            },
         }
     }
 
@@ -123,6 +141,7 @@ impl relm4::SimpleComponent for Seekbar {
                     .unwrap();
             }
             SeekbarIn::NewRange(total) => {
+                self.scale.clear_marks();
                 self.scale.set_sensitive(true);
                 self.scale.set_range(0.0, total as f64);
                 self.total = total;
@@ -131,7 +150,20 @@ impl relm4::SimpleComponent for Seekbar {
                 self.scale.set_value(ms as f64);
                 self.current = ms;
             }
-            SeekbarIn::Disable => self.scale.set_sensitive(false),
+            SeekbarIn::Bookmarks(positions) => {
+                self.scale.clear_marks();
+                for position in positions {
+                    self.scale
+                        .add_mark(position as f64, gtk::PositionType::Bottom, None);
+                }
+            }
+            SeekbarIn::BookmarkClicked => sender
+                .output(SeekbarOut::CreateBookmark(self.scale.value() as i64))
+                .unwrap(),
+            SeekbarIn::Disable => {
+                self.scale.clear_marks();
+                self.scale.set_sensitive(false);
+            }
         }
     }
 }
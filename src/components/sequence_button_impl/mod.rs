@@ -1,2 +1,3 @@
+pub mod queue_end;
 pub mod repeat;
 pub mod shuffle;
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::components::sequence_button::Sequence;
+
+/// what happens when the last song of the queue finished
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueueEnd {
+    #[default]
+    Stop,
+    /// append songs similar to the artist of the last song
+    Similar,
+    /// append random songs of the genre of the last song
+    Genre,
+    /// append the following album of the artist of the last song
+    NextAlbum,
+}
+
+impl std::str::FromStr for QueueEnd {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "media-playback-stop-symbolic" => Ok(QueueEnd::Stop),
+            "emblem-music-symbolic" => Ok(QueueEnd::Similar),
+            "applications-multimedia-symbolic" => Ok(QueueEnd::Genre),
+            "media-optical-symbolic" => Ok(QueueEnd::NextAlbum),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Sequence for QueueEnd {
+    fn current(&self) -> &str {
+        match self {
+            Self::Stop => "media-playback-stop-symbolic",
+            Self::Similar => "emblem-music-symbolic",
+            Self::Genre => "applications-multimedia-symbolic",
+            Self::NextAlbum => "media-optical-symbolic",
+        }
+    }
+
+    fn next(&mut self) {
+        *self = match self {
+            Self::Stop => Self::Similar,
+            Self::Similar => Self::Genre,
+            Self::Genre => Self::NextAlbum,
+            Self::NextAlbum => Self::Stop,
+        };
+    }
+
+    fn tooltip(&self) -> Option<&str> {
+        match self {
+            Self::Stop => Some("stop when queue ends"),
+            Self::Similar => Some("continue with similar songs when queue ends"),
+            Self::Genre => Some("continue with songs of the same genre when queue ends"),
+            Self::NextAlbum => Some("continue with next album of the artist when queue ends"),
+        }
+    }
+}
@@ -7,7 +7,10 @@ use relm4::{
     RelmWidgetExt,
 };
 
-use crate::settings::Settings;
+use crate::{
+    common::{self, replay_gain::ReplayGain, transcoding::TranscodeFormat},
+    settings::Settings,
+};
 
 #[derive(Debug)]
 pub struct SettingsWindow {}
@@ -15,12 +18,16 @@ pub struct SettingsWindow {}
 #[derive(Debug)]
 pub enum SettingsWindowIn {
     Show,
+    /// size of offline songs in bytes
+    OfflineSize(u64),
 }
 
 #[derive(Debug)]
 pub enum SettingsWindowOut {
     ClearCache,
+    DeleteOffline,
     Logout,
+    ReplayGainChanged,
 }
 
 #[relm4::component(pub)]
@@ -36,6 +43,19 @@ impl relm4::component::Component for SettingsWindow {
         sender: relm4::ComponentSender<Self>,
     ) -> relm4::ComponentParts<Self> {
         let model = Self {};
+        let replay_gain_modes: Vec<String> = ReplayGain::ALL
+            .iter()
+            .map(|mode| mode.translate())
+            .collect();
+        let replay_gain_modes: Vec<&str> =
+            replay_gain_modes.iter().map(|mode| mode.as_str()).collect();
+        let replay_gain_modes = gtk::StringList::new(&replay_gain_modes);
+        let formats: Vec<String> = TranscodeFormat::ALL
+            .iter()
+            .map(|format| format.translate())
+            .collect();
+        let formats: Vec<&str> = formats.iter().map(|format| format.as_str()).collect();
+        let formats = gtk::StringList::new(&formats);
         let widgets = view_output!();
 
         relm4::ComponentParts { model, widgets }
@@ -129,6 +149,180 @@ impl relm4::component::Component for SettingsWindow {
                         },
                     },
 
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Normalizes the loudness of songs with the ReplayGain information of the whole album or each track"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("ReplayGain"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::DropDown {
+                            set_valign: gtk::Align::Center,
+                            set_model: Some(&replay_gain_modes),
+                            set_selected: ReplayGain::ALL
+                                .iter()
+                                .position(|mode| *mode == Settings::get().lock().unwrap().replay_gain)
+                                .unwrap_or_default() as u32,
+
+                            connect_selected_notify[sender] => move |drop_down| {
+                                if let Some(mode) = ReplayGain::ALL.get(drop_down.selected() as usize) {
+                                    Settings::get().lock().unwrap().replay_gain = mode.clone();
+                                    sender.output(Self::Output::ReplayGainChanged).unwrap();
+                                }
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Gain in dB that is added to the ReplayGain of songs"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("ReplayGain preamp"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::SpinButton {
+                            set_width_request: 100,
+                            set_range: (-15f64, 15f64),
+                            set_increments: (0.5f64, 1f64),
+                            set_digits: 1,
+                            set_value: Settings::get().lock().unwrap().replay_gain_preamp,
+
+                            connect_value_changed => move |button| {
+                                Settings::get().lock().unwrap().replay_gain_preamp = button.value();
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Seconds of fading from one song into the next one, 0 disables crossfading"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Crossfade duration"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::SpinButton {
+                            set_width_request: 100,
+                            set_range: (0f64, 12f64),
+                            set_increments: (1f64, 1f64),
+                            set_digits: 0,
+                            set_value: Settings::get().lock().unwrap().crossfade_secs as f64,
+
+                            connect_value_changed => move |button| {
+                                Settings::get().lock().unwrap().crossfade_secs = button.value() as u32;
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Play songs of the same album without crossfading"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("No crossfade within albums"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::Switch {
+                            set_valign: gtk::Align::Center,
+                            set_state: Settings::get().lock().unwrap().crossfade_skip_same_album,
+
+                            connect_state_set => move |_switch, value| {
+                                Settings::get().lock().unwrap().crossfade_skip_same_album = value;
+                                gtk::glib::signal::Propagation::Proceed
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Format songs are streamed in on normal networks"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Streaming format on normal networks"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::DropDown {
+                            set_model: Some(&formats),
+                            set_selected: TranscodeFormat::ALL
+                                .iter()
+                                .position(|format| *format == Settings::get().lock().unwrap().stream_unmetered.format)
+                                .unwrap_or_default() as u32,
+
+                            connect_selected_notify => move |drop_down| {
+                                if let Some(format) = TranscodeFormat::ALL.get(drop_down.selected() as usize) {
+                                    Settings::get().lock().unwrap().stream_unmetered.format = format.clone();
+                                }
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Maximum bit rate in kbps of transcoded songs on normal networks, 0 lets the server decide"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Maximum bit rate on normal networks"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::SpinButton {
+                            set_width_request: 100,
+                            set_range: (0f64, 320f64),
+                            set_increments: (32f64, 64f64),
+                            set_digits: 0,
+                            set_value: Settings::get().lock().unwrap().stream_unmetered.max_bit_rate as f64,
+
+                            connect_value_changed => move |button| {
+                                Settings::get().lock().unwrap().stream_unmetered.max_bit_rate = button.value() as i32;
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Format songs are streamed in on metered networks like mobile hotspots"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Streaming format on metered networks"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::DropDown {
+                            set_model: Some(&formats),
+                            set_selected: TranscodeFormat::ALL
+                                .iter()
+                                .position(|format| *format == Settings::get().lock().unwrap().stream_metered.format)
+                                .unwrap_or_default() as u32,
+
+                            connect_selected_notify => move |drop_down| {
+                                if let Some(format) = TranscodeFormat::ALL.get(drop_down.selected() as usize) {
+                                    Settings::get().lock().unwrap().stream_metered.format = format.clone();
+                                }
+                            }
+                        },
+                    },
+
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Maximum bit rate in kbps of transcoded songs on metered networks, 0 lets the server decide"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Maximum bit rate on metered networks"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::SpinButton {
+                            set_width_request: 100,
+                            set_range: (0f64, 320f64),
+                            set_increments: (32f64, 64f64),
+                            set_digits: 0,
+                            set_value: Settings::get().lock().unwrap().stream_metered.max_bit_rate as f64,
+
+                            connect_value_changed => move |button| {
+                                Settings::get().lock().unwrap().stream_metered.max_bit_rate = button.value() as i32;
+                            }
+                        },
+                    },
+
                     gtk::CenterBox {
                         set_tooltip: &gettext("Show an animation for scrolling to currently played song in queue"),
 
@@ -148,7 +342,50 @@ impl relm4::component::Component for SettingsWindow {
                         },
                     },
 
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Saves the queue on the server, so it can be continued on other devices"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Sync queue with server"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget = &gtk::Switch {
+                            set_valign: gtk::Align::Center,
+                            set_state: Settings::get().lock().unwrap().sync_queue,
+
+                            connect_state_set => move |_switch, value| {
+                                Settings::get().lock().unwrap().sync_queue = value;
+                                gtk::glib::signal::Propagation::Proceed
+                            }
+                        },
+                    },
+
                     gtk::Separator {},
+                    gtk::CenterBox {
+                        set_tooltip: &gettext("Songs of pinned albums, artists and playlists are stored on this device"),
+
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Label {
+                            set_text: &gettext("Offline songs"),
+                        },
+                        #[wrap(Some)]
+                        set_end_widget: offline_size = &gtk::Label {
+                            set_text: &common::convert_for_size(0),
+                        },
+                    },
+                    gtk::Box {
+                        set_halign: gtk::Align::End,
+                        gtk::Button {
+                            add_css_class: "destructive-action",
+                            set_label: &gettext("Delete offline songs"),
+                            set_tooltip: &gettext("Deletes all songs that are stored for offline listening and removes all pins"),
+
+                            connect_clicked[sender] => move |_btn| {
+                                sender.output(Self::Output::DeleteOffline).unwrap();
+                            }
+                        }
+                    },
                     gtk::Box {
                         set_halign: gtk::Align::End,
                         gtk::Button {
@@ -191,6 +428,11 @@ impl relm4::component::Component for SettingsWindow {
                 widgets.settings_window.set_visible(true);
                 widgets.settings_window.present();
             }
+            SettingsWindowIn::OfflineSize(size) => {
+                widgets
+                    .offline_size
+                    .set_text(&common::convert_for_size(size));
+            }
         }
     }
 }
@@ -0,0 +1,338 @@
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, EditableExt, GridExt, OrientableExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    client::Client,
+    common,
+    factory::share_row::{
+        CreatedColumn, DescriptionColumn, ExpiresColumn, ShareRow, UrlColumn, VisitsColumn,
+    },
+    settings::Settings,
+    share,
+};
+
+#[derive(Debug)]
+pub struct SharesView {
+    shares: relm4::typed_view::column::TypedColumnView<ShareRow, gtk::SingleSelection>,
+    /// id of share that is shown in the edit panel
+    editing: Option<String>,
+    /// expiry option that was preselected in the edit panel
+    editing_expiry: u32,
+}
+
+impl SharesView {
+    fn selected(&self) -> Option<submarine::data::Share> {
+        let index = self.shares.selection_model.selected();
+        self.shares
+            .get_visible(index)
+            .map(|row| row.borrow().item().clone())
+    }
+}
+
+#[derive(Debug)]
+pub enum SharesViewIn {
+    SearchChanged,
+    Reload,
+    CopyClicked,
+    EditClicked,
+    DeleteClicked,
+    SaveClicked,
+    CancelClicked,
+}
+
+#[derive(Debug)]
+pub enum SharesViewOut {
+    DisplayToast(String),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for SharesView {
+    type Init = ();
+    type Input = SharesViewIn;
+    type Output = SharesViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let mut shares =
+            relm4::typed_view::column::TypedColumnView::<ShareRow, gtk::SingleSelection>::new();
+        shares.append_column::<DescriptionColumn>();
+        shares.append_column::<UrlColumn>();
+        shares.append_column::<CreatedColumn>();
+        shares.append_column::<ExpiresColumn>();
+        shares.append_column::<VisitsColumn>();
+
+        let columns = shares.get_columns();
+        columns
+            .get("Description")
+            .unwrap()
+            .set_title(Some(&gettext("Description")));
+        columns
+            .get("Link")
+            .unwrap()
+            .set_title(Some(&gettext("Link")));
+        columns
+            .get("Created")
+            .unwrap()
+            .set_title(Some(&gettext("Created")));
+        columns
+            .get("Expires")
+            .unwrap()
+            .set_title(Some(&gettext("Expires")));
+        columns
+            .get("Visits")
+            .unwrap()
+            .set_title(Some(&gettext("Visits")));
+
+        // add search filter
+        shares.add_filter(move |row| {
+            let search = Settings::get().lock().unwrap().search_text.clone();
+            let test = row.item().description.clone().unwrap_or_default();
+            common::search_matching(test, search)
+        });
+
+        let model = Self {
+            shares,
+            editing: None,
+            editing_expiry: 0,
+        };
+
+        let widgets = view_output!();
+
+        sender.input(SharesViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            gtk::Box {
+                add_css_class: "tracks-view",
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 8,
+
+                // info
+                gtk::WindowHandle {
+                    gtk::CenterBox {
+                        #[wrap(Some)]
+                        set_start_widget = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 8,
+                            set_margin_horizontal: 7,
+
+                            append: share_count = &gtk::Label {
+                                set_halign: gtk::Align::Start,
+                            },
+                            gtk::Box {
+                                set_spacing: 15,
+
+                                #[name = "copy_btn"]
+                                gtk::Button {
+                                    set_icon_name: "edit-copy-symbolic",
+                                    set_tooltip: &gettext("Copy link of selected share"),
+                                    connect_clicked => SharesViewIn::CopyClicked,
+                                },
+                                #[name = "edit_btn"]
+                                gtk::Button {
+                                    set_icon_name: "document-edit-symbolic",
+                                    set_tooltip: &gettext("Edit selected share"),
+                                    connect_clicked => SharesViewIn::EditClicked,
+                                },
+                                #[name = "delete_btn"]
+                                gtk::Button {
+                                    set_icon_name: "user-trash-symbolic",
+                                    set_tooltip: &gettext("Delete selected share from server"),
+                                    connect_clicked => SharesViewIn::DeleteClicked,
+                                },
+                            }
+                        },
+                    }
+                },
+
+                gtk::ScrolledWindow {
+                    set_hexpand: true,
+                    set_vexpand: true,
+
+                    model.shares.view.clone() {
+                        set_widget_name: "shares-view-shares",
+                        set_vexpand: true,
+
+                        connect_activate[sender] => move |_column_view, _index| {
+                            sender.input(SharesViewIn::CopyClicked);
+                        },
+                    }
+                }
+            },
+
+            // edit panel
+            append: edit = &gtk::Revealer {
+                set_transition_duration: 200,
+                set_transition_type: gtk::RevealerTransitionType::SlideLeft,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_size_request: (400, -1),
+                    set_spacing: 8,
+                    set_margin_all: 7,
+
+                    gtk::WindowHandle {
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                            set_label: &gettext("Edit Share"),
+                        }
+                    },
+
+                    gtk::Grid {
+                        set_row_spacing: 7,
+                        set_column_spacing: 7,
+
+                        attach[0, 0, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Description"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 0, 1, 1]: description = &gtk::Entry {
+                            set_hexpand: true,
+                        },
+                        attach[0, 1, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Expires"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 1, 1, 1]: expiry = &share::expiry_options() -> gtk::DropDown {},
+                    },
+
+                    gtk::Box {
+                        set_halign: gtk::Align::End,
+                        set_spacing: 7,
+
+                        gtk::Button {
+                            set_label: &gettext("Cancel"),
+                            connect_clicked => SharesViewIn::CancelClicked,
+                        },
+                        gtk::Button {
+                            add_css_class: "suggested-action",
+                            set_label: &gettext("Save"),
+                            connect_clicked => SharesViewIn::SaveClicked,
+                        },
+                    }
+                }
+            }
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            SharesViewIn::SearchChanged => _ = self.shares.notify_filter_changed(0),
+            SharesViewIn::Reload => {
+                let client = Client::get().unwrap();
+                let shares = match client.get_shares().await {
+                    Ok(shares) => shares,
+                    Err(e) => {
+                        sender
+                            .output(SharesViewOut::DisplayToast(format!(
+                                "could not fetch shares: {e:?}"
+                            )))
+                            .unwrap();
+                        return;
+                    }
+                };
+
+                self.shares.clear();
+                for share in shares {
+                    self.shares.append(ShareRow::new(share));
+                }
+
+                widgets.share_count.set_label(&format!(
+                    "{}: {}",
+                    gettext("Shares"),
+                    self.shares.len()
+                ));
+                let has_shares = !self.shares.is_empty();
+                widgets.copy_btn.set_sensitive(has_shares);
+                widgets.edit_btn.set_sensitive(has_shares);
+                widgets.delete_btn.set_sensitive(has_shares);
+            }
+            SharesViewIn::CopyClicked => {
+                if let Some(share) = self.selected() {
+                    share::copy_to_clipboard(&share.url);
+                    sender
+                        .output(SharesViewOut::DisplayToast(gettext(
+                            "Copied link to clipboard",
+                        )))
+                        .unwrap();
+                }
+            }
+            SharesViewIn::EditClicked => {
+                let Some(share) = self.selected() else {
+                    return;
+                };
+                widgets
+                    .description
+                    .set_text(share.description.as_deref().unwrap_or_default());
+                self.editing_expiry =
+                    share::expiry_option(share.expires.map(|date| date.timestamp_millis()));
+                widgets.expiry.set_selected(self.editing_expiry);
+                self.editing = Some(share.id);
+                widgets.edit.set_reveal_child(true);
+            }
+            SharesViewIn::DeleteClicked => {
+                let Some(share) = self.selected() else {
+                    return;
+                };
+                let client = Client::get().unwrap();
+                if let Err(e) = client.delete_share(&share.id).await {
+                    sender
+                        .output(SharesViewOut::DisplayToast(format!(
+                            "could not delete share: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                sender.input(SharesViewIn::Reload);
+            }
+            SharesViewIn::SaveClicked => {
+                let Some(id) = &self.editing else {
+                    return;
+                };
+                let description = widgets.description.text().trim().to_string();
+                let description = (!description.is_empty()).then_some(description);
+                // keep the expiry date unless another option was chosen; 0 removes it
+                let expires = match widgets.expiry.selected() {
+                    selected if selected == self.editing_expiry => None,
+                    selected => Some(share::expires_at(selected).unwrap_or(0)),
+                };
+
+                let client = Client::get().unwrap();
+                if let Err(e) = client.update_share(id, description, expires).await {
+                    sender
+                        .output(SharesViewOut::DisplayToast(format!(
+                            "could not update share: {e:?}"
+                        )))
+                        .unwrap();
+                    return;
+                }
+                self.editing = None;
+                widgets.edit.set_reveal_child(false);
+                sender.input(SharesViewIn::Reload);
+            }
+            SharesViewIn::CancelClicked => {
+                self.editing = None;
+                widgets.edit.set_reveal_child(false);
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{
+            BoxExt, ButtonExt, DialogExt, FileChooserExt, FileExt, GtkApplicationExt, GtkWindowExt,
+            OrientableExt, WidgetExt,
+        },
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    common::{
+        self,
+        statistics::{self, Period, Statistics, GROWTH_MONTHS},
+    },
+    subsonic::Subsonic,
+};
+
+/// replaces the rows of list with a bar for every entry
+fn fill_list(list: &gtk::ListBox, entries: &[(String, usize)]) {
+    while let Some(row) = list.row_at_index(0) {
+        list.remove(&row);
+    }
+
+    let max = entries.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    for (name, count) in entries {
+        let line = gtk::Box::new(gtk::Orientation::Horizontal, 5);
+        let name = gtk::Label::builder()
+            .label(name)
+            .halign(gtk::Align::Start)
+            .hexpand(true)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        line.append(&name);
+        line.append(&gtk::Label::new(Some(&count.to_string())));
+
+        let bar = gtk::LevelBar::for_interval(0.0, max as f64);
+        bar.set_value(*count as f64);
+
+        let row = gtk::Box::new(gtk::Orientation::Vertical, 2);
+        row.set_margin_all(3);
+        row.append(&line);
+        row.append(&bar);
+        list.append(&row);
+    }
+}
+
+#[derive(Debug)]
+pub struct StatisticsView {
+    subsonic: Rc<RefCell<Subsonic>>,
+    period: Period,
+    /// years in the order of the export year dropdown
+    years: Vec<i32>,
+}
+
+impl StatisticsView {
+    fn statistics(&self, period: Period, reference: &chrono::NaiveDate) -> Statistics {
+        let subsonic = self.subsonic.borrow();
+        Statistics::new(
+            period,
+            reference,
+            subsonic.history().records(),
+            subsonic.tracks(),
+            subsonic.albums(),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum StatisticsViewIn {
+    SearchChanged,
+    Reload,
+    PeriodSelected(u32),
+    Export,
+    /// writes the review of the year to given file
+    ExportTo(i32, std::path::PathBuf),
+}
+
+#[derive(Debug)]
+pub enum StatisticsViewOut {
+    DisplayToast(String),
+}
+
+#[relm4::component(async, pub)]
+impl relm4::component::AsyncComponent for StatisticsView {
+    type Init = Rc<RefCell<Subsonic>>;
+    type Input = StatisticsViewIn;
+    type Output = StatisticsViewOut;
+    type CommandOutput = ();
+
+    async fn init(
+        subsonic: Self::Init,
+        root: Self::Root,
+        sender: relm4::AsyncComponentSender<Self>,
+    ) -> relm4::component::AsyncComponentParts<Self> {
+        let model = Self {
+            subsonic,
+            period: Period::default(),
+            years: vec![],
+        };
+        let periods: Vec<String> = Period::ALL
+            .iter()
+            .map(|period| period.translate())
+            .collect();
+        let periods: Vec<&str> = periods.iter().map(|period| period.as_str()).collect();
+        let periods = gtk::StringList::new(&periods);
+
+        let widgets = view_output!();
+
+        sender.input(StatisticsViewIn::Reload);
+
+        relm4::component::AsyncComponentParts { model, widgets }
+    }
+
+    view! {
+        gtk::Box {
+            add_css_class: "tracks-view",
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 8,
+
+            gtk::WindowHandle {
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 8,
+                    set_margin_horizontal: 7,
+
+                    gtk::Label {
+                        add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                        set_halign: gtk::Align::Start,
+                        set_text: &gettext("Statistics"),
+                    },
+                    #[name = "listening_time"]
+                    gtk::Label {
+                        set_halign: gtk::Align::Start,
+                    },
+                    gtk::Box {
+                        set_spacing: 15,
+
+                        gtk::DropDown {
+                            set_model: Some(&periods),
+                            set_selected: Period::ALL
+                                .iter()
+                                .position(|period| *period == model.period)
+                                .unwrap_or_default() as u32,
+
+                            connect_selected_notify[sender] => move |drop_down| {
+                                sender.input(StatisticsViewIn::PeriodSelected(drop_down.selected()));
+                            }
+                        },
+                        #[name = "export_year"]
+                        gtk::DropDown {
+                            set_tooltip: &gettext("Year of the exported summary"),
+                        },
+                        gtk::Button {
+                            set_icon_name: "document-save-symbolic",
+                            set_tooltip: &gettext("Export a summary of the chosen year as html"),
+                            connect_clicked => StatisticsViewIn::Export,
+                        },
+                    },
+                }
+            },
+
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+
+                gtk::FlowBox {
+                    set_valign: gtk::Align::Start,
+                    set_selection_mode: gtk::SelectionMode::None,
+                    set_homogeneous: true,
+                    set_max_children_per_line: 3,
+                    set_row_spacing: 15,
+                    set_column_spacing: 15,
+                    set_margin_all: 7,
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                            set_halign: gtk::Align::Start,
+                            set_text: &gettext("Top artists"),
+                        },
+                        #[name = "top_artists"]
+                        gtk::ListBox {
+                            set_selection_mode: gtk::SelectionMode::None,
+                            set_placeholder: Some(&gtk::Label::new(Some(&gettext("Nothing played yet")))),
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                            set_halign: gtk::Align::Start,
+                            set_text: &gettext("Top albums"),
+                        },
+                        #[name = "top_albums"]
+                        gtk::ListBox {
+                            set_selection_mode: gtk::SelectionMode::None,
+                            set_placeholder: Some(&gtk::Label::new(Some(&gettext("Nothing played yet")))),
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                            set_halign: gtk::Align::Start,
+                            set_text: &gettext("Top songs"),
+                        },
+                        #[name = "top_tracks"]
+                        gtk::ListBox {
+                            set_selection_mode: gtk::SelectionMode::None,
+                            set_placeholder: Some(&gtk::Label::new(Some(&gettext("Nothing played yet")))),
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                            set_halign: gtk::Align::Start,
+                            set_text: &gettext("Top genres"),
+                        },
+                        #[name = "top_genres"]
+                        gtk::ListBox {
+                            set_selection_mode: gtk::SelectionMode::None,
+                            set_placeholder: Some(&gtk::Label::new(Some(&gettext("Nothing played yet")))),
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                            set_halign: gtk::Align::Start,
+                            set_text: &gettext("Added albums per month"),
+                        },
+                        #[name = "growth"]
+                        gtk::ListBox {
+                            set_selection_mode: gtk::SelectionMode::None,
+                            set_placeholder: Some(&gtk::Label::new(Some(&gettext("No albums found")))),
+                        },
+                    },
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: granite::STYLE_CLASS_H3_LABEL,
+                            set_halign: gtk::Align::Start,
+                            set_text: &gettext("Songs per decade"),
+                        },
+                        #[name = "decades"]
+                        gtk::ListBox {
+                            set_selection_mode: gtk::SelectionMode::None,
+                            set_placeholder: Some(&gtk::Label::new(Some(&gettext("No songs found")))),
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        msg: Self::Input,
+        sender: relm4::AsyncComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match msg {
+            // statistics are not searchable
+            StatisticsViewIn::SearchChanged => {}
+            StatisticsViewIn::Reload => {
+                let today = chrono::Local::now().date_naive();
+                let years =
+                    statistics::history_years(self.subsonic.borrow().history().records(), &today);
+                if years != self.years {
+                    let names: Vec<String> = years.iter().map(|year| year.to_string()).collect();
+                    let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+                    widgets
+                        .export_year
+                        .set_model(Some(&gtk::StringList::new(&names)));
+                    let review_year = statistics::review_year(&today);
+                    widgets.export_year.set_selected(
+                        years
+                            .iter()
+                            .position(|year| *year == review_year)
+                            .unwrap_or_default() as u32,
+                    );
+                    self.years = years;
+                }
+
+                let statistics = self.statistics(self.period, &today);
+                widgets.listening_time.set_text(&format!(
+                    "{}: {}",
+                    gettext("Listening time"),
+                    common::convert_for_label(statistics.listening_time)
+                ));
+                fill_list(&widgets.top_artists, &statistics.top_artists);
+                fill_list(&widgets.top_albums, &statistics.top_albums);
+                fill_list(&widgets.top_tracks, &statistics.top_tracks);
+                fill_list(&widgets.top_genres, &statistics.top_genres);
+                let growth_start = statistics.growth.len().saturating_sub(GROWTH_MONTHS);
+                fill_list(&widgets.growth, &statistics.growth[growth_start..]);
+                fill_list(&widgets.decades, &statistics.decades);
+            }
+            StatisticsViewIn::PeriodSelected(index) => {
+                if let Some(period) = Period::ALL.get(index as usize) {
+                    self.period = *period;
+                    sender.input(StatisticsViewIn::Reload);
+                }
+            }
+            StatisticsViewIn::Export => {
+                let Some(&year) = self.years.get(widgets.export_year.selected() as usize) else {
+                    return;
+                };
+                let file_dialog = gtk::FileChooserDialog::builder()
+                    .name(gettext("Choose location for year in review"))
+                    .modal(true)
+                    .use_header_bar(1)
+                    .action(gtk::FileChooserAction::Save)
+                    .transient_for(&relm4::main_application().windows()[0])
+                    .build();
+                file_dialog.set_current_name(&format!("year-in-review-{year}.html"));
+                file_dialog.add_button(&gettext("Save"), gtk::ResponseType::Accept);
+                file_dialog.add_button(&gettext("Cancel"), gtk::ResponseType::Cancel);
+                file_dialog.show();
+
+                file_dialog.connect_response(move |dialog, response| {
+                    dialog.close();
+                    if response != gtk::ResponseType::Accept {
+                        return;
+                    }
+                    if let Some(path) = dialog.file().and_then(|file| file.path()) {
+                        sender.input(StatisticsViewIn::ExportTo(year, path));
+                    }
+                });
+            }
+            StatisticsViewIn::ExportTo(year, path) => {
+                let Some(reference) = chrono::NaiveDate::from_ymd_opt(year, 1, 1) else {
+                    return;
+                };
+                let title = format!("{} {year}", gettext("Year in review"));
+                let html = self.statistics(Period::Year, &reference).to_html(&title);
+                match std::fs::write(&path, html) {
+                    Ok(()) => sender
+                        .output(StatisticsViewOut::DisplayToast(format!(
+                            "{} {}",
+                            gettext("Exported year in review to"),
+                            path.display()
+                        )))
+                        .unwrap(),
+                    Err(e) => sender
+                        .output(StatisticsViewOut::DisplayToast(format!(
+                            "could not export year in review: {e:?}"
+                        )))
+                        .unwrap(),
+                }
+            }
+        }
+    }
+}
@@ -5,8 +5,8 @@ use relm4::{
     gtk::{
         self, glib,
         prelude::{
-            BoxExt, ButtonExt, GtkWindowExt, ListModelExt, OrientableExt, SelectionModelExt,
-            WidgetExt,
+            BoxExt, ButtonExt, CheckButtonExt, EditableExt, GtkWindowExt, ListModelExt,
+            OrientableExt, PopoverExt, SelectionModelExt, WidgetExt,
         },
     },
     ComponentController, RelmWidgetExt,
@@ -26,11 +26,13 @@ use crate::{
         filter_row::{FilterRow, FilterRowIn, FilterRowOut},
         track_row::{
             AlbumColumn, ArtistColumn, BitRateColumn, FavColumn, GenreColumn, LengthColumn,
-            PlayCountColumn, PositionColumn, TitleColumn, TrackRow,
+            PlayCountColumn, PositionColumn, RatingColumn, TitleColumn, TrackRow,
         },
     },
+    gtk_helper::context_menu::add_context_menu,
     settings::Settings,
     subsonic::Subsonic,
+    subsonic_smart_playlists::{SmartPlaylist, SmartSort},
 };
 
 #[derive(Debug)]
@@ -106,6 +108,7 @@ pub enum TracksViewIn {
     FilterChanged,
     UpdateWidgetsSearchFilterChanged,
     UpdateFavoriteSong(String, bool),
+    UpdateRatingSong(String, Option<i32>),
     UpdatePlayCountSong(String, Option<i64>),
     FilterAdd,
     FilterRow(FilterRowOut),
@@ -117,6 +120,8 @@ pub enum TracksViewIn {
     TrackClicked(usize),
     RecalcDragSource,
     CreatePlaylist,
+    SaveSmartPlaylist,
+    ShareSelected,
 }
 
 #[derive(Debug)]
@@ -126,10 +131,13 @@ pub enum TracksViewOut {
     AppendToQueue(Droppable),
     ReplaceQueue(Droppable),
     Download(Droppable),
+    Share(Droppable),
     FavoriteClicked(String, bool),
+    RatingClicked(String, i32),
     ClickedArtist(Id),
     ClickedAlbum(Id),
     CreatePlaylist(String, Vec<submarine::data::Child>),
+    CreateSmartPlaylist(SmartPlaylist),
 }
 
 #[derive(Debug)]
@@ -159,6 +167,7 @@ impl relm4::Component for TracksView {
         tracks.append_column::<LengthColumn>();
         tracks.append_column::<PlayCountColumn>();
         tracks.append_column::<BitRateColumn>();
+        tracks.append_column::<RatingColumn>();
         tracks.append_column::<FavColumn>();
 
         let columns = tracks.get_columns();
@@ -186,6 +195,10 @@ impl relm4::Component for TracksView {
             .get("Bitrate")
             .unwrap()
             .set_title(Some(&gettext("Bitrate")));
+        columns
+            .get("Rating")
+            .unwrap()
+            .set_title(Some(&gettext("Rating")));
         columns
             .get("Favorite")
             .unwrap()
@@ -217,6 +230,10 @@ impl relm4::Component for TracksView {
             TracksViewCmd::AddTracks(list)
         });
 
+        let sorts: Vec<String> = SmartSort::ALL.iter().map(SmartSort::translate).collect();
+        let smart_sorts =
+            gtk::StringList::new(&sorts.iter().map(String::as_str).collect::<Vec<_>>());
+
         let widgets = view_output!();
 
         // setup filter view
@@ -227,6 +244,16 @@ impl relm4::Component for TracksView {
             .push_back(Category::Favorite);
         model.calc_sensitivity_of_buttons(&widgets);
 
+        // context menu of selected tracks
+        let menu_sender = sender.clone();
+        add_context_menu(
+            &model.tracks.view,
+            vec![(
+                gettext("Share"),
+                Box::new(move || menu_sender.input(TracksViewIn::ShareSelected)),
+            )],
+        );
+
         // send signal on selection change
         model
             .tracks
@@ -348,6 +375,61 @@ impl relm4::Component for TracksView {
                                             set_tooltip: &gettext("Create a playlist with shown tracks"),
                                             connect_clicked => TracksViewIn::CreatePlaylist,
                                         },
+                                        gtk::MenuButton {
+                                            set_icon_name: "playlist-symbolic",
+                                            set_tooltip: &gettext("Save active filters as a smart playlist"),
+
+                                            #[wrap(Some)]
+                                            set_popover: smart_popover = &gtk::Popover {
+                                                gtk::Box {
+                                                    set_orientation: gtk::Orientation::Vertical,
+                                                    set_spacing: 7,
+                                                    set_margin_all: 5,
+
+                                                    gtk::Label {
+                                                        add_css_class: granite::STYLE_CLASS_H4_LABEL,
+                                                        set_halign: gtk::Align::Start,
+                                                        set_label: &gettext("Smart playlist"),
+                                                    },
+                                                    append: smart_name = &gtk::Entry {
+                                                        set_placeholder_text: Some(&gettext("Name")),
+                                                    },
+                                                    gtk::Box {
+                                                        set_spacing: 7,
+
+                                                        gtk::Label {
+                                                            set_label: &gettext("Sort by"),
+                                                        },
+                                                        append: smart_sort = &gtk::DropDown {
+                                                            set_hexpand: true,
+                                                            set_model: Some(&smart_sorts),
+                                                        },
+                                                    },
+                                                    gtk::Box {
+                                                        set_spacing: 7,
+                                                        set_tooltip: &gettext("0 keeps all matching songs"),
+
+                                                        gtk::Label {
+                                                            set_label: &gettext("Limit"),
+                                                        },
+                                                        append: smart_limit = &gtk::SpinButton {
+                                                            set_hexpand: true,
+                                                            set_range: (0f64, 10000f64),
+                                                            set_increments: (25f64, 100f64),
+                                                            set_digits: 0,
+                                                        },
+                                                    },
+                                                    append: smart_mirror = &gtk::CheckButton {
+                                                        set_label: Some(&gettext("Mirror to a playlist on the server")),
+                                                    },
+                                                    gtk::Button {
+                                                        add_css_class: "suggested-action",
+                                                        set_label: &gettext("Save smart playlist"),
+                                                        connect_clicked => TracksViewIn::SaveSmartPlaylist,
+                                                    },
+                                                }
+                                            },
+                                        },
                                     }
                                 }
                             },
@@ -474,6 +556,10 @@ impl relm4::Component for TracksView {
                         }
                     });
             }
+            TracksViewIn::UpdateRatingSong(id, rating) => (0..self.tracks.len())
+                .filter_map(|i| self.tracks.get(i))
+                .filter(|t| t.borrow().item().id == id)
+                .for_each(|track| track.borrow_mut().set_rating(rating)),
             TracksViewIn::UpdatePlayCountSong(id, play_count) => (0..self.tracks.len())
                 .filter_map(|i| self.tracks.get(i))
                 .filter(|t| t.borrow().item().id == id)
@@ -622,6 +708,18 @@ impl relm4::Component for TracksView {
                         .emit(CoverIn::LoadSong(Box::new(track.borrow().item().clone())));
                 }
             }
+            TracksViewIn::ShareSelected => {
+                let children: Vec<submarine::data::Child> =
+                    (0..self.tracks.selection_model.n_items())
+                        .filter(|i| self.tracks.selection_model.is_selected(*i))
+                        .filter_map(|i| self.tracks.get_visible(i))
+                        .map(|row| row.borrow().item().clone())
+                        .collect();
+                if !children.is_empty() {
+                    let drop = Droppable::Queue(children);
+                    sender.output(TracksViewOut::Share(drop)).unwrap();
+                }
+            }
             TracksViewIn::RecalcDragSource => {
                 let len = self.tracks.selection_model.n_items();
                 let selected_rows: Vec<u32> = (0..len)
@@ -648,6 +746,44 @@ impl relm4::Component for TracksView {
                     .filter_map(|i| self.tracks.get(*i))
                     .for_each(|row| row.borrow_mut().set_drag_src(drop.clone()));
             }
+            TracksViewIn::SaveSmartPlaylist => {
+                let filters: Vec<_> = self
+                    .filters
+                    .borrow()
+                    .iter()
+                    .filter_map(|row| row.filter().clone())
+                    .collect();
+                if filters.is_empty() {
+                    sender
+                        .output(TracksViewOut::DisplayToast(gettext(
+                            "A smart playlist needs at least one active filter",
+                        )))
+                        .unwrap();
+                    return;
+                }
+
+                let name = match widgets.smart_name.text().trim() {
+                    "" => gettext("New smart playlist"),
+                    name => name.to_string(),
+                };
+                let limit = match widgets.smart_limit.value() as usize {
+                    0 => None,
+                    limit => Some(limit),
+                };
+                let list = SmartPlaylist {
+                    name,
+                    filters,
+                    sort: SmartSort::ALL[widgets.smart_sort.selected() as usize],
+                    limit,
+                    mirror: widgets.smart_mirror.is_active(),
+                    mirror_id: None,
+                };
+                widgets.smart_popover.popdown();
+                widgets.smart_name.set_text("");
+                sender
+                    .output(TracksViewOut::CreateSmartPlaylist(list))
+                    .unwrap();
+            }
             TracksViewIn::CreatePlaylist => {
                 if self.shown_tracks.is_empty() {
                     return;
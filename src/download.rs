@@ -153,6 +153,10 @@ impl Download {
                     )
                 })
                 .collect(),
+            // the server zips a directory the same way as an album
+            Droppable::Directory(dir, _songs) => {
+                vec![(format!("{}.zip", dir.name), Id::album(dir.id))]
+            }
         };
 
         //TODO sanitize file names, e.g "/"
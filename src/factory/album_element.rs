@@ -16,6 +16,7 @@ use crate::{
         types::{Droppable, Id},
     },
     components::descriptive_cover::{DescriptiveCover, DescriptiveCoverInit, DescriptiveCoverOut},
+    gtk_helper::context_menu::add_context_menu,
     subsonic::Subsonic,
 };
 
@@ -35,6 +36,7 @@ pub enum AlbumElementIn {
     Hover(bool),
     FavoriteClicked,
     Clicked,
+    ShareClicked,
 }
 
 #[derive(Debug)]
@@ -42,6 +44,7 @@ pub enum AlbumElementOut {
     Clicked(Id),
     FavoriteClicked(String, bool),
     DisplayToast(String),
+    Share(Droppable),
 }
 
 #[relm4::factory(pub)]
@@ -128,6 +131,16 @@ impl relm4::factory::FactoryComponent for AlbumElement {
         });
         model.cover.widget().add_controller(drag_src);
 
+        // context menu
+        let menu_sender = sender.clone();
+        add_context_menu(
+            model.cover.widget(),
+            vec![(
+                gettext("Share"),
+                Box::new(move || menu_sender.input(AlbumElementIn::ShareClicked)),
+            )],
+        );
+
         // set favorite icon
         model.favorite.set_visible(false);
         model.favorite_ribbon.set_visible(false);
@@ -247,6 +260,15 @@ impl relm4::factory::FactoryComponent for AlbumElement {
                     .output(AlbumElementOut::Clicked(self.id.clone()))
                     .unwrap();
             }
+            AlbumElementIn::ShareClicked => {
+                if let Some(album) = self.subsonic.borrow().find_album(self.id.as_ref()) {
+                    sender
+                        .output(AlbumElementOut::Share(Droppable::AlbumChild(Box::new(
+                            album,
+                        ))))
+                        .unwrap();
+                }
+            }
         }
     }
 }
@@ -20,6 +20,7 @@ use crate::{
         cover::Cover,
     },
     factory::SetupFinished,
+    gtk_helper::rating::Rating,
     subsonic::Subsonic,
 };
 
@@ -29,6 +30,7 @@ pub struct AlbumRow {
     item: submarine::data::Child,
     cover: relm4::Controller<Cover>,
     fav_btn: Option<gtk::Button>,
+    rating: Option<Rating>,
     sender: relm4::ComponentSender<AlbumsView>,
 }
 
@@ -54,6 +56,7 @@ impl AlbumRow {
             item,
             cover,
             fav_btn: None,
+            rating: None,
             sender,
         }
     }
@@ -69,6 +72,15 @@ impl AlbumRow {
     pub fn fav_btn(&self) -> &Option<gtk::Button> {
         &self.fav_btn
     }
+
+    pub fn set_rating(&mut self, rating: Option<i32>) {
+        self.item.user_rating = rating;
+
+        // update widget
+        if let Some(widget) = &self.rating {
+            widget.set_rating(rating);
+        }
+    }
 }
 
 pub struct Model {
@@ -390,6 +402,57 @@ impl relm4::typed_view::column::RelmColumn for PlayCountColumn {
     }
 }
 
+pub struct RatingColumn;
+
+impl relm4::typed_view::column::RelmColumn for RatingColumn {
+    type Root = gtk::Viewport;
+    type Item = AlbumRow;
+    type Widgets = (Rc<RefCell<String>>, Rating, Model, SetupFinished);
+
+    const COLUMN_NAME: &'static str = "Rating";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let (view, model) = Model::new();
+        let rating = Rating::new();
+        let cell = Rc::new(RefCell::new(String::new()));
+        view.set_child(Some(rating.widget()));
+        (view, (cell, rating, model, SetupFinished(false)))
+    }
+
+    fn bind(
+        item: &mut Self::Item,
+        (cell, rating, model, finished): &mut Self::Widgets,
+        _root: &mut Self::Root,
+    ) {
+        model.set_from_row(item);
+        rating.set_rating(item.item.user_rating);
+        cell.replace(item.item.id.clone());
+
+        if !finished.0 {
+            finished.0 = true;
+            let sender = item.sender.clone();
+            let cell = cell.clone();
+            rating.connect_changed(move |rating| {
+                sender
+                    .output(AlbumsViewOut::RatingClicked(cell.borrow().clone(), rating))
+                    .unwrap();
+            });
+        }
+
+        item.rating = Some(rating.clone());
+    }
+
+    fn unbind(item: &mut Self::Item, _widgets: &mut Self::Widgets, _root: &mut Self::Root) {
+        item.rating = None;
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| b.item.user_rating.cmp(&a.item.user_rating)))
+    }
+}
+
 pub struct FavColumn;
 
 impl relm4::typed_view::column::RelmColumn for FavColumn {
@@ -0,0 +1,150 @@
+use relm4::gtk::{self, prelude::WidgetExt};
+
+use crate::common::convert_for_label;
+
+#[derive(Debug, PartialEq)]
+pub struct BookmarkRow {
+    item: submarine::data::Bookmark,
+}
+
+impl BookmarkRow {
+    pub fn new(item: submarine::data::Bookmark) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::Bookmark {
+        &self.item
+    }
+}
+
+fn create_label() -> gtk::Label {
+    gtk::Label::builder()
+        .halign(gtk::Align::Start)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build()
+}
+
+pub struct TitleColumn;
+
+impl relm4::typed_view::column::RelmColumn for TitleColumn {
+    type Root = gtk::Label;
+    type Item = BookmarkRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Title";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.entry.title);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.entry.title.cmp(&b.item.entry.title)))
+    }
+}
+
+pub struct ArtistColumn;
+
+impl relm4::typed_view::column::RelmColumn for ArtistColumn {
+    type Root = gtk::Label;
+    type Item = BookmarkRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Artist";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(item.item.entry.artist.as_deref().unwrap_or_default());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| {
+            a.item.entry.artist.cmp(&b.item.entry.artist)
+        }))
+    }
+}
+
+pub struct PositionColumn;
+
+impl relm4::typed_view::column::RelmColumn for PositionColumn {
+    type Root = gtk::Label;
+    type Item = BookmarkRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Position";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let length = i64::from(item.item.entry.duration.unwrap_or(0)) * 1000;
+        label.set_label(&format!(
+            "{} / {}",
+            convert_for_label(item.item.position),
+            convert_for_label(length)
+        ));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.position.cmp(&b.item.position)))
+    }
+}
+
+pub struct CommentColumn;
+
+impl relm4::typed_view::column::RelmColumn for CommentColumn {
+    type Root = gtk::Label;
+    type Item = BookmarkRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Comment";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let label = create_label();
+        label.add_css_class("dim-label");
+        (label, ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(item.item.comment.as_deref().unwrap_or_default());
+    }
+}
+
+pub struct ChangedColumn;
+
+impl relm4::typed_view::column::RelmColumn for ChangedColumn {
+    type Root = gtk::Label;
+    type Item = BookmarkRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Changed";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.changed.format("%d.%m.%Y %H:%M").to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| b.item.changed.cmp(&a.item.changed)))
+    }
+}
@@ -522,6 +522,34 @@ impl relm4::factory::FactoryComponent for FilterRow {
                         connect_clicked => Self::Input::RemoveFilter,
                     }
                 },
+                add_enumed[Category::Rating] = &gtk::Box {
+                    set_spacing: 5,
+                    gtk::Box {
+                        set_hexpand: true,
+
+                        gtk::Label {
+                            set_text: &gettext("Rating"),
+                        },
+                    },
+
+                    #[name = "rating_dropdown"]
+                    gtk::DropDown {
+                        set_model: Some(&OrderRow::store()),
+                        set_factory: Some(&OrderRow::factory()),
+                        connect_selected_item_notify => Self::Input::ParameterChanged,
+                    },
+                    #[name = "rating_entry"]
+                    gtk::SpinButton {
+                        set_digits: 0,
+                        set_adjustment: &gtk::Adjustment::new(3f64, 0f64, 5f64, 1f64, 1f64, 1f64),
+                        connect_text_notify => Self::Input::ParameterChanged,
+                    },
+                    gtk::Button {
+                        set_icon_name: "user-trash-symbolic",
+                        set_tooltip: &gettext("remove this filter"),
+                        connect_clicked => Self::Input::RemoveFilter,
+                    }
+                },
                 add_enumed[Category::Title] = &gtk::Box {
                     set_spacing: 5,
                     gtk::Box {
@@ -771,6 +799,16 @@ impl relm4::factory::FactoryComponent for FilterRow {
                             self.filter = None;
                         }
                     }
+                    Category::Rating => {
+                        let order = casting_widget(&widgets.rating_dropdown);
+                        let order: std::cell::Ref<OrderRow> = order.borrow();
+                        if let Ok(number) = widgets.rating_entry.text().parse::<i32>() {
+                            self.filter = Some(Filter::Rating(order.order, number));
+                            widgets.rating_entry.set_tooltip_text(None);
+                        } else {
+                            self.filter = None;
+                        }
+                    }
                 }
                 sender.output(Self::Output::ParameterChanged).unwrap();
             }
@@ -0,0 +1,146 @@
+use gettextrs::gettext;
+use relm4::gtk::{self, prelude::BoxExt};
+
+use crate::common::convert_for_label;
+
+/// entry of a directory on the server
+#[derive(Debug, Clone, PartialEq)]
+pub enum FolderItem {
+    /// id and name of a directory, its content is fetched when it is opened
+    Directory(String, String),
+    Song(submarine::data::Child),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FolderRow {
+    item: FolderItem,
+}
+
+impl FolderRow {
+    pub fn directory(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            item: FolderItem::Directory(id.into(), name.into()),
+        }
+    }
+
+    pub fn from_child(child: submarine::data::Child) -> Self {
+        if child.is_dir {
+            Self::directory(child.id, child.title)
+        } else {
+            Self {
+                item: FolderItem::Song(child),
+            }
+        }
+    }
+
+    pub fn item(&self) -> &FolderItem {
+        &self.item
+    }
+
+    pub fn name(&self) -> &str {
+        match &self.item {
+            FolderItem::Directory(_id, name) => name,
+            FolderItem::Song(child) => &child.title,
+        }
+    }
+
+    /// directories are listed before songs
+    fn sort_key(&self) -> (bool, String) {
+        (
+            matches!(self.item, FolderItem::Song(_)),
+            self.name().to_lowercase(),
+        )
+    }
+}
+
+pub struct NameColumn;
+
+impl relm4::typed_view::column::RelmColumn for NameColumn {
+    type Root = gtk::Box;
+    type Item = FolderRow;
+    type Widgets = (gtk::Image, gtk::Label);
+
+    const COLUMN_NAME: &'static str = "Name";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let root = gtk::Box::new(gtk::Orientation::Horizontal, 7);
+        let icon = gtk::Image::default();
+        let label = gtk::Label::builder()
+            .halign(gtk::Align::Start)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        root.append(&icon);
+        root.append(&label);
+        (root, (icon, label))
+    }
+
+    fn bind(item: &mut Self::Item, (icon, label): &mut Self::Widgets, _root: &mut Self::Root) {
+        match &item.item {
+            FolderItem::Directory(..) => icon.set_icon_name(Some("folder-symbolic")),
+            FolderItem::Song(_) => icon.set_icon_name(Some("audio-x-generic-symbolic")),
+        }
+        label.set_label(item.name());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.sort_key().cmp(&b.sort_key())))
+    }
+}
+
+pub struct ArtistColumn;
+
+impl relm4::typed_view::column::RelmColumn for ArtistColumn {
+    type Root = gtk::Label;
+    type Item = FolderRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Artist";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let label = gtk::Label::builder()
+            .halign(gtk::Align::Start)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        (label, ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        match &item.item {
+            FolderItem::Directory(..) => label.set_label(""),
+            FolderItem::Song(child) => {
+                let stock = gettext("Unknown Artist");
+                label.set_label(child.artist.as_deref().unwrap_or(&stock));
+            }
+        }
+    }
+}
+
+pub struct LengthColumn;
+
+impl relm4::typed_view::column::RelmColumn for LengthColumn {
+    type Root = gtk::Label;
+    type Item = FolderRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Length";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        match &item.item {
+            FolderItem::Directory(..) => label.set_label(""),
+            FolderItem::Song(child) => {
+                let length = convert_for_label(i64::from(child.duration.unwrap_or(0)) * 1000);
+                label.set_label(&length);
+            }
+        }
+    }
+}
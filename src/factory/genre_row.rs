@@ -0,0 +1,304 @@
+use gettextrs::gettext;
+use relm4::gtk;
+
+use crate::common::convert_for_label;
+
+#[derive(Debug, PartialEq)]
+pub struct GenreRow {
+    item: submarine::data::Genre,
+}
+
+impl GenreRow {
+    pub fn new(item: submarine::data::Genre) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::Genre {
+        &self.item
+    }
+}
+
+/// album of the selected genre
+#[derive(Debug, PartialEq)]
+pub struct GenreAlbumRow {
+    item: submarine::data::Child,
+}
+
+impl GenreAlbumRow {
+    pub fn new(item: submarine::data::Child) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::Child {
+        &self.item
+    }
+}
+
+/// track of the selected genre
+#[derive(Debug, PartialEq)]
+pub struct GenreTrackRow {
+    item: submarine::data::Child,
+}
+
+impl GenreTrackRow {
+    pub fn new(item: submarine::data::Child) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::Child {
+        &self.item
+    }
+}
+
+fn create_label() -> gtk::Label {
+    gtk::Label::builder()
+        .halign(gtk::Align::Start)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build()
+}
+
+pub struct GenreColumn;
+
+impl relm4::typed_view::column::RelmColumn for GenreColumn {
+    type Root = gtk::Label;
+    type Item = GenreRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Genre";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.value);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.value.cmp(&b.item.value)))
+    }
+}
+
+pub struct AlbumCountColumn;
+
+impl relm4::typed_view::column::RelmColumn for AlbumCountColumn {
+    type Root = gtk::Label;
+    type Item = GenreRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Albums";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.album_count.to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| b.item.album_count.cmp(&a.item.album_count)))
+    }
+}
+
+pub struct SongCountColumn;
+
+impl relm4::typed_view::column::RelmColumn for SongCountColumn {
+    type Root = gtk::Label;
+    type Item = GenreRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Songs";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.song_count.to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| b.item.song_count.cmp(&a.item.song_count)))
+    }
+}
+
+pub struct AlbumTitleColumn;
+
+impl relm4::typed_view::column::RelmColumn for AlbumTitleColumn {
+    type Root = gtk::Label;
+    type Item = GenreAlbumRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Album";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let stock = gettext("Unknown Album");
+        label.set_label(item.item.album.as_deref().unwrap_or(&stock));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.album.cmp(&b.item.album)))
+    }
+}
+
+pub struct AlbumArtistColumn;
+
+impl relm4::typed_view::column::RelmColumn for AlbumArtistColumn {
+    type Root = gtk::Label;
+    type Item = GenreAlbumRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Artist";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let stock = gettext("Unknown Artist");
+        label.set_label(item.item.artist.as_deref().unwrap_or(&stock));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.artist.cmp(&b.item.artist)))
+    }
+}
+
+pub struct AlbumYearColumn;
+
+impl relm4::typed_view::column::RelmColumn for AlbumYearColumn {
+    type Root = gtk::Label;
+    type Item = GenreAlbumRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Year";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let year = item.item.year.map(|year| year.to_string());
+        label.set_label(&year.unwrap_or(String::from("-")));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.year.cmp(&b.item.year)))
+    }
+}
+
+pub struct TrackTitleColumn;
+
+impl relm4::typed_view::column::RelmColumn for TrackTitleColumn {
+    type Root = gtk::Label;
+    type Item = GenreTrackRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Title";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.title);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.title.cmp(&b.item.title)))
+    }
+}
+
+pub struct TrackArtistColumn;
+
+impl relm4::typed_view::column::RelmColumn for TrackArtistColumn {
+    type Root = gtk::Label;
+    type Item = GenreTrackRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Artist";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let stock = gettext("Unknown Artist");
+        label.set_label(item.item.artist.as_deref().unwrap_or(&stock));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.artist.cmp(&b.item.artist)))
+    }
+}
+
+pub struct TrackAlbumColumn;
+
+impl relm4::typed_view::column::RelmColumn for TrackAlbumColumn {
+    type Root = gtk::Label;
+    type Item = GenreTrackRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Album";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let stock = gettext("Unknown Album");
+        label.set_label(item.item.album.as_deref().unwrap_or(&stock));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.album.cmp(&b.item.album)))
+    }
+}
+
+pub struct TrackLengthColumn;
+
+impl relm4::typed_view::column::RelmColumn for TrackLengthColumn {
+    type Root = gtk::Label;
+    type Item = GenreTrackRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Length";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let length = convert_for_label(i64::from(item.item.duration.unwrap_or(0)) * 1000);
+        label.set_label(&length);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.duration.cmp(&b.item.duration)))
+    }
+}
@@ -0,0 +1,243 @@
+use gettextrs::gettext;
+use relm4::gtk;
+
+use crate::subsonic_history::PlayRecord;
+
+/// day with at least one play
+#[derive(Debug, PartialEq)]
+pub struct HistoryDayRow {
+    day: chrono::NaiveDate,
+    /// plays of the day, the latest first
+    plays: Vec<PlayRecord>,
+}
+
+impl HistoryDayRow {
+    pub fn new(day: chrono::NaiveDate, plays: Vec<PlayRecord>) -> Self {
+        Self { day, plays }
+    }
+
+    pub fn day(&self) -> &chrono::NaiveDate {
+        &self.day
+    }
+
+    pub fn plays(&self) -> &Vec<PlayRecord> {
+        &self.plays
+    }
+}
+
+/// play of the selected day
+#[derive(Debug, PartialEq)]
+pub struct HistoryPlayRow {
+    item: PlayRecord,
+}
+
+impl HistoryPlayRow {
+    pub fn new(item: PlayRecord) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &PlayRecord {
+        &self.item
+    }
+}
+
+fn create_label() -> gtk::Label {
+    gtk::Label::builder()
+        .halign(gtk::Align::Start)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build()
+}
+
+pub struct DayColumn;
+
+impl relm4::typed_view::column::RelmColumn for DayColumn {
+    type Root = gtk::Label;
+    type Item = HistoryDayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Day";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.day.format("%x").to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.day.cmp(&b.day)))
+    }
+}
+
+pub struct PlayCountColumn;
+
+impl relm4::typed_view::column::RelmColumn for PlayCountColumn {
+    type Root = gtk::Label;
+    type Item = HistoryDayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Plays";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.plays.len().to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.plays.len().cmp(&b.plays.len())))
+    }
+}
+
+pub struct TimeColumn;
+
+impl relm4::typed_view::column::RelmColumn for TimeColumn {
+    type Root = gtk::Label;
+    type Item = HistoryPlayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Time";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.started_local().format("%H:%M").to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.started.cmp(&b.item.started)))
+    }
+}
+
+pub struct TitleColumn;
+
+impl relm4::typed_view::column::RelmColumn for TitleColumn {
+    type Root = gtk::Label;
+    type Item = HistoryPlayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Title";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.song.title);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.song.title.cmp(&b.item.song.title)))
+    }
+}
+
+pub struct ArtistColumn;
+
+impl relm4::typed_view::column::RelmColumn for ArtistColumn {
+    type Root = gtk::Label;
+    type Item = HistoryPlayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Artist";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let stock = gettext("Unknown Artist");
+        label.set_label(item.item.song.artist.as_deref().unwrap_or(&stock));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.song.artist.cmp(&b.item.song.artist)))
+    }
+}
+
+pub struct AlbumColumn;
+
+impl relm4::typed_view::column::RelmColumn for AlbumColumn {
+    type Root = gtk::Label;
+    type Item = HistoryPlayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Album";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let stock = gettext("Unknown Album");
+        label.set_label(item.item.song.album.as_deref().unwrap_or(&stock));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.song.album.cmp(&b.item.song.album)))
+    }
+}
+
+pub struct ListenedColumn;
+
+impl relm4::typed_view::column::RelmColumn for ListenedColumn {
+    type Root = gtk::Label;
+    type Item = HistoryPlayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Listened";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&format!("{} %", item.item.listened));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.listened.cmp(&b.item.listened)))
+    }
+}
+
+pub struct StatusColumn;
+
+impl relm4::typed_view::column::RelmColumn for StatusColumn {
+    type Root = gtk::Label;
+    type Item = HistoryPlayRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Status";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let mut status = item.item.end.translate();
+        if item.item.scrobbled {
+            status = format!("{status}, {}", gettext("scrobbled"));
+        }
+        label.set_label(&status);
+    }
+}
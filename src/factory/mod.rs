@@ -11,10 +11,17 @@ pub mod album_row;
 pub mod album_track_row;
 pub mod artist_row;
 pub mod artist_song_row;
+pub mod bookmark_row;
 pub mod filter_row;
+pub mod folder_row;
+pub mod genre_row;
+pub mod history_row;
 pub mod playlist_element;
 pub mod playlist_row;
+pub mod podcast_row;
 pub mod queue_song_row;
+pub mod radio_row;
+pub mod share_row;
 pub mod track_row;
 
 fn get_list_item_widget(widget: &impl gtk::prelude::IsA<gtk::Widget>) -> Option<gtk::Widget> {
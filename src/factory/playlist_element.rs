@@ -13,7 +13,7 @@ use crate::{
     common::{self, types::Droppable},
     css,
     factory::{playlist_row::PlaylistUids, queue_song_row::QueueUids},
-    gtk_helper::stack::StackExt,
+    gtk_helper::{context_menu::add_context_menu, stack::StackExt},
     settings::Settings,
     subsonic::Subsonic,
 };
@@ -148,6 +148,7 @@ pub enum PlaylistElementIn {
     ShowIndicatorUpdate(f64),
     ShowIndicatorReset,
     MoveDropped(PlaylistElementDragged, f64),
+    ShareClicked,
 }
 
 #[derive(Debug)]
@@ -159,6 +160,7 @@ pub enum PlaylistElementOut {
     DropAppend(Droppable, submarine::data::PlaylistWithSongs),
     MoveDropAbove(PlaylistElementDragged, relm4::factory::DynamicIndex),
     MoveDropBelow(PlaylistElementDragged, relm4::factory::DynamicIndex),
+    Share(Droppable),
 }
 
 #[relm4::factory(pub)]
@@ -214,6 +216,16 @@ impl relm4::factory::FactoryComponent for PlaylistElement {
             ]);
         }
 
+        // context menu
+        let menu_sender = sender.clone();
+        add_context_menu(
+            &model.list_box_row,
+            vec![(
+                gettext("Share"),
+                Box::new(move || menu_sender.input(PlaylistElementIn::ShareClicked)),
+            )],
+        );
+
         sender.input(PlaylistElementIn::ShowIndicatorReset);
         model
     }
@@ -456,6 +468,10 @@ impl relm4::factory::FactoryComponent for PlaylistElement {
                     .output(PlaylistElementOut::Clicked(self.index.clone()))
                     .unwrap();
             }
+            PlaylistElementIn::ShareClicked => {
+                let drop = Droppable::Playlist(Box::new(self.playlist.clone()));
+                sender.output(PlaylistElementOut::Share(drop)).unwrap();
+            }
             PlaylistElementIn::DragEnter => {
                 self.drag_state.replace(DragState::Entered);
                 let state = self.drag_state.clone();
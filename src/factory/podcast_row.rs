@@ -0,0 +1,194 @@
+use gettextrs::gettext;
+use relm4::gtk::{self, prelude::WidgetExt};
+
+use crate::common::convert_for_label;
+
+#[derive(Debug, PartialEq)]
+pub struct ChannelRow {
+    item: submarine::data::PodcastChannel,
+}
+
+impl ChannelRow {
+    pub fn new(item: submarine::data::PodcastChannel) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::PodcastChannel {
+        &self.item
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EpisodeRow {
+    item: submarine::data::PodcastEpisode,
+    /// listened position in ms
+    position: Option<i64>,
+}
+
+impl EpisodeRow {
+    pub fn new(item: submarine::data::PodcastEpisode, position: Option<i64>) -> Self {
+        Self { item, position }
+    }
+
+    pub fn item(&self) -> &submarine::data::PodcastEpisode {
+        &self.item
+    }
+}
+
+fn create_label() -> gtk::Label {
+    gtk::Label::builder()
+        .halign(gtk::Align::Start)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build()
+}
+
+pub fn status_label(status: &submarine::data::PodcastStatus) -> String {
+    use submarine::data::PodcastStatus;
+
+    match status {
+        PodcastStatus::New => gettext("New"),
+        PodcastStatus::Downloading => gettext("Downloading"),
+        PodcastStatus::Completed => gettext("Completed"),
+        PodcastStatus::Error => gettext("Error"),
+        PodcastStatus::Deleted => gettext("Deleted"),
+        PodcastStatus::Skipped => gettext("Skipped"),
+    }
+}
+
+pub struct ChannelColumn;
+
+impl relm4::typed_view::column::RelmColumn for ChannelColumn {
+    type Root = gtk::Label;
+    type Item = ChannelRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Channel";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let title = item.item.title.clone().unwrap_or(item.item.url.clone());
+        label.set_label(&title);
+        label.set_tooltip_text(item.item.description.as_deref());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.title.cmp(&b.item.title)))
+    }
+}
+
+pub struct TitleColumn;
+
+impl relm4::typed_view::column::RelmColumn for TitleColumn {
+    type Root = gtk::Label;
+    type Item = EpisodeRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Title";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.child.title);
+        label.set_tooltip_text(item.item.description.as_deref());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.child.title.cmp(&b.item.child.title)))
+    }
+}
+
+pub struct PublishedColumn;
+
+impl relm4::typed_view::column::RelmColumn for PublishedColumn {
+    type Root = gtk::Label;
+    type Item = EpisodeRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Published";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let date = item
+            .item
+            .publish_date
+            .map(|date| date.format("%d.%m.%Y").to_string())
+            .unwrap_or_default();
+        label.set_label(&date);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| {
+            b.item.publish_date.cmp(&a.item.publish_date)
+        }))
+    }
+}
+
+pub struct LengthColumn;
+
+impl relm4::typed_view::column::RelmColumn for LengthColumn {
+    type Root = gtk::Label;
+    type Item = EpisodeRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Length";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let duration = i64::from(item.item.child.duration.unwrap_or(0)) * 1000;
+        match item.position {
+            None => label.set_label(&convert_for_label(duration)),
+            Some(position) => label.set_label(&format!(
+                "{} / {}",
+                convert_for_label(position),
+                convert_for_label(duration)
+            )),
+        }
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| {
+            a.item.child.duration.cmp(&b.item.child.duration)
+        }))
+    }
+}
+
+pub struct StatusColumn;
+
+impl relm4::typed_view::column::RelmColumn for StatusColumn {
+    type Root = gtk::Label;
+    type Item = EpisodeRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Status";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let label = gtk::Label::default();
+        label.add_css_class("dim-label");
+        (label, ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&status_label(&item.item.status));
+    }
+}
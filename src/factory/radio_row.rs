@@ -0,0 +1,97 @@
+use relm4::gtk::{self, prelude::WidgetExt};
+
+#[derive(Debug, PartialEq)]
+pub struct RadioRow {
+    item: submarine::data::InternetRadioStation,
+}
+
+impl RadioRow {
+    pub fn new(item: submarine::data::InternetRadioStation) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::InternetRadioStation {
+        &self.item
+    }
+}
+
+fn create_label() -> gtk::Label {
+    gtk::Label::builder()
+        .halign(gtk::Align::Start)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build()
+}
+
+pub struct NameColumn;
+
+impl relm4::typed_view::column::RelmColumn for NameColumn {
+    type Root = gtk::Label;
+    type Item = RadioRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Name";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.name);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.name.cmp(&b.item.name)))
+    }
+}
+
+pub struct StreamColumn;
+
+impl relm4::typed_view::column::RelmColumn for StreamColumn {
+    type Root = gtk::Label;
+    type Item = RadioRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Stream";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let label = create_label();
+        label.add_css_class("dim-label");
+        (label, ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.stream_url);
+        label.set_tooltip_text(Some(&item.item.stream_url));
+    }
+}
+
+pub struct HomepageColumn;
+
+impl relm4::typed_view::column::RelmColumn for HomepageColumn {
+    type Root = gtk::Label;
+    type Item = RadioRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Homepage";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        match &item.item.home_page_url {
+            None => label.set_text(""),
+            Some(url) => label.set_markup(&format!(
+                "<a href=\"{}\">{}</a>",
+                gtk::glib::markup_escape_text(url),
+                gtk::glib::markup_escape_text(url)
+            )),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use gettextrs::gettext;
+use relm4::gtk::{self, prelude::WidgetExt};
+
+#[derive(Debug, PartialEq)]
+pub struct ShareRow {
+    item: submarine::data::Share,
+}
+
+impl ShareRow {
+    pub fn new(item: submarine::data::Share) -> Self {
+        Self { item }
+    }
+
+    pub fn item(&self) -> &submarine::data::Share {
+        &self.item
+    }
+}
+
+fn create_label() -> gtk::Label {
+    gtk::Label::builder()
+        .halign(gtk::Align::Start)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .build()
+}
+
+pub struct DescriptionColumn;
+
+impl relm4::typed_view::column::RelmColumn for DescriptionColumn {
+    type Root = gtk::Label;
+    type Item = ShareRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Description";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(item.item.description.as_deref().unwrap_or_default());
+        let songs: Vec<&str> = item
+            .item
+            .entry
+            .iter()
+            .map(|child| child.title.as_str())
+            .collect();
+        label.set_tooltip_text(Some(&songs.join("\n")));
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.description.cmp(&b.item.description)))
+    }
+}
+
+pub struct UrlColumn;
+
+impl relm4::typed_view::column::RelmColumn for UrlColumn {
+    type Root = gtk::Label;
+    type Item = ShareRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Link";
+    const ENABLE_RESIZE: bool = true;
+    const ENABLE_EXPAND: bool = true;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let label = create_label();
+        label.add_css_class("dim-label");
+        (label, ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.url);
+    }
+}
+
+pub struct CreatedColumn;
+
+impl relm4::typed_view::column::RelmColumn for CreatedColumn {
+    type Root = gtk::Label;
+    type Item = ShareRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Created";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.created.format("%d.%m.%Y").to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| b.item.created.cmp(&a.item.created)))
+    }
+}
+
+pub struct ExpiresColumn;
+
+impl relm4::typed_view::column::RelmColumn for ExpiresColumn {
+    type Root = gtk::Label;
+    type Item = ShareRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Expires";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (create_label(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        let expires = match &item.item.expires {
+            None => gettext("Never"),
+            Some(date) => date.format("%d.%m.%Y").to_string(),
+        };
+        label.set_label(&expires);
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.expires.cmp(&b.item.expires)))
+    }
+}
+
+pub struct VisitsColumn;
+
+impl relm4::typed_view::column::RelmColumn for VisitsColumn {
+    type Root = gtk::Label;
+    type Item = ShareRow;
+    type Widgets = ();
+
+    const COLUMN_NAME: &'static str = "Visits";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        (gtk::Label::default(), ())
+    }
+
+    fn bind(item: &mut Self::Item, _widgets: &mut Self::Widgets, label: &mut Self::Root) {
+        label.set_label(&item.item.visit_count.to_string());
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| a.item.visit_count.cmp(&b.item.visit_count)))
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
     },
     components::tracks_view::{TracksView, TracksViewIn, TracksViewOut},
     factory::SetupFinished,
+    gtk_helper::rating::Rating,
     subsonic::Subsonic,
 };
 
@@ -28,6 +29,7 @@ pub struct TrackRow {
     item: submarine::data::Child,
     play_count: Option<gtk::Label>,
     fav_btn: Option<gtk::Button>,
+    rating: Option<Rating>,
     title_box: gtk::Viewport,
     sender: relm4::ComponentSender<TracksView>,
     multiple_drag_src: Option<gtk::DragSource>,
@@ -52,6 +54,7 @@ impl TrackRow {
             item,
             play_count: None,
             fav_btn: None,
+            rating: None,
             title_box: gtk::Viewport::default(),
             sender: sender.clone(),
             multiple_drag_src: None,
@@ -84,6 +87,15 @@ impl TrackRow {
         &self.fav_btn
     }
 
+    pub fn set_rating(&mut self, rating: Option<i32>) {
+        self.item.user_rating = rating;
+
+        // update widget
+        if let Some(widget) = &self.rating {
+            widget.set_rating(rating);
+        }
+    }
+
     // this will be set when clicking on a row
     pub fn set_drag_src(&mut self, drop: Droppable) {
         // remove old DragSource if there is one
@@ -582,3 +594,54 @@ impl relm4::typed_view::column::RelmColumn for FavColumn {
         Some(Box::new(|a, b| b.item.starred.cmp(&a.item.starred)))
     }
 }
+
+pub struct RatingColumn;
+
+impl relm4::typed_view::column::RelmColumn for RatingColumn {
+    type Root = gtk::Viewport;
+    type Item = TrackRow;
+    type Widgets = (Rc<RefCell<String>>, Rating, SetupFinished);
+
+    const COLUMN_NAME: &'static str = "Rating";
+    const ENABLE_RESIZE: bool = false;
+    const ENABLE_EXPAND: bool = false;
+
+    fn setup(_item: &gtk::ListItem) -> (Self::Root, Self::Widgets) {
+        let cell = Rc::new(RefCell::new(String::new()));
+        (
+            gtk::Viewport::default(),
+            (cell, Rating::new(), SetupFinished(false)),
+        )
+    }
+
+    fn bind(
+        item: &mut Self::Item,
+        (cell, rating, finished): &mut Self::Widgets,
+        view: &mut Self::Root,
+    ) {
+        rating.set_rating(item.item.user_rating);
+        cell.replace(item.item.id.clone());
+
+        if !finished.0 {
+            finished.0 = true;
+            let sender = item.sender.clone();
+            let cell = cell.clone();
+            rating.connect_changed(move |rating| {
+                sender
+                    .output(TracksViewOut::RatingClicked(cell.borrow().clone(), rating))
+                    .unwrap();
+            });
+        }
+
+        item.rating = Some(rating.clone());
+        view.set_child(Some(rating.widget()));
+    }
+
+    fn unbind(item: &mut Self::Item, _widgets: &mut Self::Widgets, _root: &mut Self::Root) {
+        item.rating = None;
+    }
+
+    fn sort_fn() -> relm4::typed_view::OrdFn<Self::Item> {
+        Some(Box::new(|a, b| b.item.user_rating.cmp(&a.item.user_rating)))
+    }
+}
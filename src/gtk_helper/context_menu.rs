@@ -0,0 +1,45 @@
+use relm4::gtk::{
+    self,
+    prelude::{BoxExt, ButtonExt, GestureSingleExt, OrientableExt, PopoverExt, WidgetExt},
+};
+
+/// entry of a context menu with its label and the action when clicked
+pub type ContextMenuEntry = (String, Box<dyn Fn()>);
+
+/// attaches a menu to widget that opens on a right click
+pub fn add_context_menu(
+    widget: &impl gtk::prelude::IsA<gtk::Widget>,
+    entries: Vec<ContextMenuEntry>,
+) {
+    let popover = gtk::Popover::builder().has_arrow(false).build();
+    popover.set_halign(gtk::Align::Start);
+    popover.set_parent(widget);
+
+    let list = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    for (label, action) in entries {
+        let button = gtk::Button::builder().label(label).build();
+        button.add_css_class("flat");
+        if let Some(label) = button.child() {
+            label.set_halign(gtk::Align::Start);
+        }
+        let menu = popover.clone();
+        button.connect_clicked(move |_btn| {
+            menu.popdown();
+            action();
+        });
+        list.append(&button);
+    }
+    popover.set_child(Some(&list));
+
+    let click = gtk::GestureClick::new();
+    click.set_button(gtk::gdk::BUTTON_SECONDARY);
+    let menu = popover.clone();
+    click.connect_pressed(move |_ctrl, _n, x, y| {
+        menu.set_pointing_to(Some(&gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+        menu.popup();
+    });
+    widget.add_controller(click);
+
+    // popover needs to be removed from parent before it is destroyed
+    widget.connect_destroy(move |_widget| popover.unparent());
+}
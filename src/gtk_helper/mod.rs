@@ -1,4 +1,6 @@
+pub mod context_menu;
 pub mod list_store;
 pub mod loading_widget;
+pub mod rating;
 pub mod scroll;
 pub mod stack;
@@ -0,0 +1,110 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, WidgetExt},
+    },
+    RelmWidgetExt,
+};
+
+/// highest rating a server accepts
+pub const MAX_RATING: i32 = 5;
+
+type ChangedCallback = Rc<RefCell<Option<Box<dyn Fn(i32)>>>>;
+
+/// row of stars to show and change a rating from 0 (unrated) to 5
+#[derive(Clone)]
+pub struct Rating {
+    root: gtk::Box,
+    stars: Vec<gtk::Button>,
+    rating: Rc<Cell<i32>>,
+    changed: ChangedCallback,
+}
+
+impl std::fmt::Debug for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rating")
+            .field("rating", &self.rating.get())
+            .finish()
+    }
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rating {
+    pub fn new() -> Self {
+        let root = gtk::Box::default();
+        root.add_css_class("rating");
+        root.set_valign(gtk::Align::Center);
+        let rating = Rc::new(Cell::new(0));
+        let changed: ChangedCallback = Rc::new(RefCell::new(None));
+
+        let stars: Vec<gtk::Button> = (1..=MAX_RATING)
+            .map(|value| {
+                let star = gtk::Button::from_icon_name("non-starred-symbolic");
+                star.add_css_class("flat");
+                star.set_focus_on_click(false);
+                star.set_tooltip(&format!("{} {value}", gettext("Rate with")));
+                root.append(&star);
+                star
+            })
+            .collect();
+
+        for (i, star) in stars.iter().enumerate() {
+            let value = i as i32 + 1;
+            let all = stars.clone();
+            let rating = rating.clone();
+            let changed = changed.clone();
+            star.connect_clicked(move |_btn| {
+                // clicking the current rating again removes it
+                let new = if rating.get() == value { 0 } else { value };
+                rating.set(new);
+                Self::show(&all, new);
+                if let Some(callback) = changed.borrow().as_ref() {
+                    callback(new);
+                }
+            });
+        }
+
+        Self {
+            root,
+            stars,
+            rating,
+            changed,
+        }
+    }
+
+    pub fn widget(&self) -> &gtk::Box {
+        &self.root
+    }
+
+    /// sets the shown rating without calling the changed callback
+    pub fn set_rating(&self, rating: Option<i32>) {
+        let rating = rating.unwrap_or(0).clamp(0, MAX_RATING);
+        self.rating.set(rating);
+        Self::show(&self.stars, rating);
+    }
+
+    /// callback is called with the new rating when the user clicks a star
+    pub fn connect_changed(&self, callback: impl Fn(i32) + 'static) {
+        self.changed.replace(Some(Box::new(callback)));
+    }
+
+    fn show(stars: &[gtk::Button], rating: i32) {
+        for (i, star) in stars.iter().enumerate() {
+            match (i as i32) < rating {
+                true => star.set_icon_name("starred-symbolic"),
+                false => star.set_icon_name("non-starred-symbolic"),
+            }
+        }
+    }
+}
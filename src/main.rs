@@ -16,9 +16,16 @@ mod factory;
 pub mod gtk_helper;
 mod mpris;
 mod playback;
+mod secret;
+mod share;
 pub mod settings;
 pub mod subsonic;
 pub mod subsonic_cover;
+pub mod subsonic_history;
+pub mod subsonic_offline;
+pub mod subsonic_podcasts;
+pub mod subsonic_scrobbles;
+pub mod subsonic_smart_playlists;
 
 const DEFAULT_LOG_ENV_PARA: &str = "info,bouy:trace,submarine:info";
 const LOG_PREFIX: &str = "Buoy";
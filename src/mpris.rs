@@ -29,6 +29,9 @@ struct Info {
     volume: f64,
     state: PlayState,
     song: Option<submarine::data::Child>,
+    /// internet radio station that is played instead of a song
+    station: Option<String>,
+    stream_title: Option<String>,
     loop_status: Repeat,
     shuffle: Shuffle,
     song_position: i64, // in microseconds
@@ -135,6 +138,14 @@ impl Mpris {
         self.sender.try_send(DataChanged::Metadata).unwrap();
     }
 
+    pub fn set_station(&mut self, station: Option<String>, title: Option<String>) {
+        let mut info = self.info.lock().unwrap();
+        info.station = station;
+        info.stream_title = title;
+        drop(info);
+        self.sender.try_send(DataChanged::Metadata).unwrap();
+    }
+
     pub fn set_loop_status(&mut self, repeat: Repeat) {
         self.info.lock().unwrap().loop_status = repeat;
         self.sender.try_send(DataChanged::Repeat).unwrap();
@@ -325,7 +336,15 @@ impl Player {
     #[zbus(property)]
     pub fn metadata(&self) -> zvariant::Value<'_> {
         let mut map = HashMap::new();
-        if let Some(song) = &self.info.lock().unwrap().song {
+        let info = self.info.lock().unwrap();
+        if let (None, Some(station)) = (&info.song, &info.station) {
+            use zvariant::Value;
+            map.insert("mpris:trackid", Value::new(String::from("radio")));
+            let title = info.stream_title.as_ref().unwrap_or(station);
+            map.insert("xesam:title", Value::new(String::from(title)));
+            map.insert("xesam:album", Value::new(String::from(station)));
+        }
+        if let Some(song) = &info.song {
             use zvariant::Value;
             map.insert("mpris:trackid", Value::new(String::from(&song.id)));
             map.insert("xesam:title", Value::new(String::from(&song.title)));
@@ -2,33 +2,45 @@ use std::{
     cell::RefCell,
     rc::Rc,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex,
     },
 };
 
 use gstreamer::{self as gst, prelude::*};
+use gstreamer_controller::{self as gst_controller, prelude::*};
 use relm4::gtk;
 
-use crate::{common::play_state::PlayState, settings::Settings};
+use crate::{
+    common::{play_state::PlayState, replay_gain::ReplayGain},
+    settings::Settings,
+};
 
 #[derive(Debug)]
 pub struct Playback {
     pipeline: gst::Pipeline,
-    source: gst::Element,
+    mixer: gst::Element,
+    limiter: gst::Element,
     volume: gst::Element,
     equalizer: gst::Element,
+    decks: Rc<RefCell<Decks>>,
     track_set: Arc<AtomicBool>,
     scrobbled: Arc<Mutex<Scrobbled>>, //needed for threshold
 }
 
 const TICK: u64 = 250; // update rate for Seekbar
+const FADE_STEPS: u64 = 32; // control points of the volume ramps while crossfading
+const PREPARE_NEXT_MS: u64 = 10_000; // time before end of track when the next track is requested
 
 #[derive(Debug)]
 pub enum PlaybackOut {
     TrackEnd,
     SongPosition(i64), // in ms
     ScrobbleThresholdReached,
+    AboutToFinish,
+    TrackChanged,
+    /// title of the currently played song of an internet radio
+    StreamTitle(String),
 }
 
 #[derive(Debug)]
@@ -37,39 +49,136 @@ pub enum Scrobbled {
     SetTo(Option<gstreamer::format::Percent>),
 }
 
-impl Playback {
-    pub fn new() -> anyhow::Result<(Self, async_channel::Receiver<PlaybackOut>)> {
-        let (sender, receiver) = async_channel::unbounded();
-        gst::init()?;
+/// Connection of a deck to the mixer
+///
+/// It is shared with the streaming thread of the preceding deck, which links
+/// the deck when its own audio ends.
+#[derive(Debug)]
+struct DeckLink {
+    src: gst::GhostPad,
+    mixer_pad: Option<gst::Pad>,
+    /// pad in front of the fade, so held back buffers get the volume ramp
+    block_pad: gst::Pad,
+    block: Option<gst::PadProbeId>,
+    /// running time of pipeline when this deck starts playing
+    offset: gst::ClockTime,
+}
 
-        // Create the empty pipeline
-        let pipeline = gst::Pipeline::with_name("playback");
-        // Create the elements
-        let source = gst::ElementFactory::make_with_name("uridecodebin", Some("source"))?;
-        source.set_property("download", true);
-        let convert = gst::ElementFactory::make_with_name("audioconvert", Some("convert"))?;
-        let volume = gst::ElementFactory::make_with_name("volume", Some("volume"))?;
-        let equalizer =
-            gst::ElementFactory::make_with_name("equalizer-10bands", Some("equalizer"))?;
-        let sink = gst::ElementFactory::make_with_name("autoaudiosink", Some("sink"))?;
-        let track_set = Arc::new(AtomicBool::new(false));
-        let scrobbled = Arc::new(Mutex::new(Scrobbled::SetTo(None)));
+impl DeckLink {
+    /// links deck to mixer, audio of deck starts at running time offset
+    fn link(&mut self, mixer: &gst::Element, offset: gst::ClockTime) -> anyhow::Result<()> {
+        let mixer_pad = mixer
+            .request_pad_simple("sink_%u")
+            .ok_or(anyhow::anyhow!("could not request pad from mixer"))?;
+        self.set_offset(offset);
+        mixer_pad.set_offset(offset.nseconds() as i64);
+        self.src.link(&mixer_pad)?;
+        self.mixer_pad = Some(mixer_pad);
+
+        if let Some(block) = self.block.take() {
+            self.block_pad.remove_probe(block);
+        }
+        Ok(())
+    }
 
-        // build the pipeline
-        pipeline.add_many([&source, &convert, &volume, &equalizer, &sink])?;
-        gst::Element::link_many([&convert, &volume, &equalizer, &sink])
-            .expect("Elements could not be linked.");
+    fn set_offset(&mut self, offset: gst::ClockTime) {
+        self.offset = offset;
+        if let Some(pad) = &self.mixer_pad {
+            pad.set_offset(offset.nseconds() as i64);
+        }
+    }
+}
+
+/// deck that is linked to the mixer when the audio of the current deck ends
+#[derive(Debug)]
+struct Follower {
+    link: Arc<Mutex<DeckLink>>,
+    mixer: gst::Element,
+}
+
+/// One decoding chain that feeds into the mixer
+///
+/// The next track gets its own deck which is prerolled while the current one is
+/// still playing. When the eos of the current deck passes its src pad, the end
+/// of its last buffer is the running time where its audio ends. The next deck is
+/// linked with that offset before the eos reaches the mixer, so the mixer
+/// continues without a gap.
+/// When crossfading the next deck is linked earlier by the crossfade duration and
+/// both decks are mixed with volume ramps. The ramps are control points of the
+/// fade volume, so they are applied sample accurate in the streaming thread.
+#[derive(Debug)]
+struct Deck {
+    bin: gst::Bin,
+    fade: gst::Element,
+    fade_control: gst_controller::InterpolationControlSource,
+    /// duration of fading in this deck
+    crossfade: gst::ClockTime,
+    link: Arc<Mutex<DeckLink>>,
+    prerolled: Arc<AtomicBool>,
+    /// running time of pipeline where the audio of this deck ended, known after its eos
+    end: Arc<Mutex<Option<gst::ClockTime>>>,
+    follower: Arc<Mutex<Option<Follower>>>,
+}
+
+impl Deck {
+    /// gain in dB is used when the server knows it, otherwise its read from the stream tags
+    fn new(pipeline: &gst::Pipeline, uri: &str, gain: Option<f64>) -> anyhow::Result<Self> {
+        let bin = gst::Bin::new();
+        let source = gst::ElementFactory::make("uridecodebin")
+            .property("uri", uri)
+            .property("download", true)
+            .build()?;
+        let convert = gst::ElementFactory::make("audioconvert").build()?;
+        let resample = gst::ElementFactory::make("audioresample").build()?;
+        let fade = gst::ElementFactory::make("volume").build()?;
+        let fade_control = gst_controller::InterpolationControlSource::new();
+        fade_control.set_property("mode", gst_controller::InterpolationMode::Linear);
+        fade.add_control_binding(&gst_controller::DirectControlBinding::new_absolute(
+            &fade,
+            "volume",
+            &fade_control,
+        ))?;
+        // the binding is only enabled while fading
+        fade.set_control_binding_disabled("volume", true);
+
+        // loudness normalization
+        let settings = Settings::get().lock().unwrap();
+        let normalize = match (&settings.replay_gain, gain) {
+            (ReplayGain::Off, _) => None,
+            (_, Some(gain)) => {
+                let gain = 10f64.powf((gain + settings.replay_gain_preamp) / 20.0);
+                Some(
+                    gst::ElementFactory::make("volume")
+                        .property("volume", gain.clamp(0.0, 10.0))
+                        .build()?,
+                )
+            }
+            (mode, None) => Some(
+                gst::ElementFactory::make("rgvolume")
+                    .property("album-mode", *mode == ReplayGain::Album)
+                    .property("pre-amp", settings.replay_gain_preamp)
+                    .build()?,
+            ),
+        };
+        drop(settings);
+
+        bin.add_many([&source, &convert, &resample, &fade])?;
+        match &normalize {
+            Some(normalize) => {
+                bin.add(normalize)?;
+                gst::Element::link_many([&convert, normalize, &resample, &fade])?;
+            }
+            None => gst::Element::link_many([&convert, &resample, &fade])?,
+        }
+
+        let src = gst::GhostPad::with_target(&fade.static_pad("src").unwrap())?;
+        bin.add_pad(&src)?;
 
         // Connect the pad-added signal
-        let conv = convert;
         source.connect_pad_added(move |_, src_pad| {
-            let sink_pad = conv
+            let sink_pad = convert
                 .static_pad("sink")
                 .expect("Failed to get static sink pad from convert");
-            // if sink_pad.is_linked() {
-            //     warn!("We are already linked. Ignoring.");
-            //     return;
-            // }
 
             let new_pad_caps = src_pad
                 .current_caps()
@@ -91,45 +200,476 @@ impl Playback {
             }
         });
 
+        // hold back the first buffer until the deck is linked to the mixer
+        let prerolled = Arc::new(AtomicBool::new(false));
+        let ready = prerolled.clone();
+        let block_pad = fade.static_pad("sink").unwrap();
+        let block = block_pad.add_probe(
+            gst::PadProbeType::BLOCK | gst::PadProbeType::BUFFER,
+            move |_pad, _info| {
+                ready.store(true, Ordering::Relaxed);
+                gst::PadProbeReturn::Ok
+            },
+        );
+
+        let link = Arc::new(Mutex::new(DeckLink {
+            src: src.clone(),
+            mixer_pad: None,
+            block_pad,
+            block,
+            offset: gst::ClockTime::ZERO,
+        }));
+        let end = Arc::new(Mutex::new(None));
+        let follower: Arc<Mutex<Option<Follower>>> = Arc::new(Mutex::new(None));
+
+        // track where the audio ends and hand over to the follower before the eos reaches the mixer
+        let last_stop = AtomicU64::new(0);
+        let own_link = Arc::downgrade(&link);
+        let own_end = end.clone();
+        let own_follower = follower.clone();
+        src.add_probe(
+            gst::PadProbeType::BUFFER | gst::PadProbeType::EVENT_DOWNSTREAM,
+            move |pad, info| {
+                match &info.data {
+                    Some(gst::PadProbeData::Buffer(buffer)) => {
+                        let stop = buffer
+                            .pts()
+                            .map(|pts| pts + buffer.duration().unwrap_or_default());
+                        let segment = pad.sticky_event::<gst::event::Segment>(0);
+                        let running_stop = segment.as_ref().and_then(|event| {
+                            event
+                                .segment()
+                                .downcast_ref::<gst::ClockTime>()?
+                                .to_running_time(stop?)
+                        });
+                        if let Some(running_stop) = running_stop {
+                            last_stop.store(running_stop.nseconds(), Ordering::Relaxed);
+                        }
+                    }
+                    Some(gst::PadProbeData::Event(event))
+                        if event.type_() == gst::EventType::Eos =>
+                    {
+                        let Some(link) = own_link.upgrade() else {
+                            return gst::PadProbeReturn::Ok;
+                        };
+                        let end = link.lock().unwrap().offset
+                            + gst::ClockTime::from_nseconds(last_stop.load(Ordering::Relaxed));
+                        *own_end.lock().unwrap() = Some(end);
+
+                        if let Some(follower) = own_follower.lock().unwrap().take() {
+                            if let Err(e) = follower.link.lock().unwrap().link(&follower.mixer, end)
+                            {
+                                tracing::error!("could not link next deck: {e}");
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                gst::PadProbeReturn::Ok
+            },
+        );
+
+        pipeline.add(&bin)?;
+
+        Ok(Self {
+            bin,
+            fade,
+            fade_control,
+            crossfade: gst::ClockTime::ZERO,
+            link,
+            prerolled,
+            end,
+            follower,
+        })
+    }
+
+    fn is_prerolled(&self) -> bool {
+        self.prerolled.load(Ordering::Relaxed)
+    }
+
+    fn is_linked(&self) -> bool {
+        self.link.lock().unwrap().mixer_pad.is_some()
+    }
+
+    /// links deck to mixer, audio of deck starts at running time offset
+    fn link(&self, mixer: &gst::Element, offset: gst::ClockTime) -> anyhow::Result<()> {
+        self.link.lock().unwrap().link(mixer, offset)
+    }
+
+    fn offset(&self) -> gst::ClockTime {
+        self.link.lock().unwrap().offset
+    }
+
+    fn set_offset(&self, offset: gst::ClockTime) {
+        self.link.lock().unwrap().set_offset(offset);
+    }
+
+    fn end(&self) -> Option<gst::ClockTime> {
+        *self.end.lock().unwrap()
+    }
+
+    /// links deck at the end of this deck; returns false when this deck already ended
+    fn set_follower(&self, deck: &Deck, mixer: &gst::Element) -> bool {
+        let mut follower = self.follower.lock().unwrap();
+        if self.end().is_some() {
+            return false;
+        }
+        *follower = Some(Follower {
+            link: deck.link.clone(),
+            mixer: mixer.clone(),
+        });
+        true
+    }
+
+    /// returns true when there was a follower which is not linked at the end anymore
+    fn take_follower(&self) -> bool {
+        self.follower.lock().unwrap().take().is_some()
+    }
+
+    fn set_fade(&self, volume: f64) {
+        self.fade.set_control_binding_disabled("volume", true);
+        self.fade_control.unset_all();
+        self.fade.set_property("volume", volume.clamp(0.0, 1.0));
+    }
+
+    /// ramps the volume with an equal power curve, which starts at running time of pipeline
+    fn schedule_fade(
+        &self,
+        start: gst::ClockTime,
+        duration: gst::ClockTime,
+        fade_in: bool,
+    ) -> anyhow::Result<()> {
+        let start = self
+            .stream_time(start)
+            .ok_or(anyhow::anyhow!("deck has no segment to schedule fade"))?;
+
+        self.fade_control.unset_all();
+        for step in 0..=FADE_STEPS {
+            let angle = step as f64 / FADE_STEPS as f64 * std::f64::consts::FRAC_PI_2;
+            let volume = match fade_in {
+                true => angle.sin(),
+                false => angle.cos(),
+            };
+            self.fade_control
+                .set(start + duration * step / FADE_STEPS, volume);
+        }
+        self.fade.set_control_binding_disabled("volume", false);
+        Ok(())
+    }
+
+    /// stream time of the deck at running time of pipeline, control points of the fade use it
+    fn stream_time(&self, running_time: gst::ClockTime) -> Option<gst::ClockTime> {
+        let event = self
+            .fade
+            .static_pad("sink")?
+            .sticky_event::<gst::event::Segment>(0)?;
+        let segment = event.segment().downcast_ref::<gst::ClockTime>()?;
+        let position =
+            segment.position_from_running_time(running_time.saturating_sub(self.offset()))?;
+        segment.to_stream_time(position)
+    }
+
+    fn duration(&self) -> Option<gst::ClockTime> {
+        self.bin.query_duration::<gst::ClockTime>()
+    }
+
+    /// position of running time in the track of this deck, it differs from the running time
+    /// since the deck was linked when the deck was seeked
+    fn position(&self, running_time: gst::ClockTime) -> gst::ClockTime {
+        self.stream_time(running_time)
+            .unwrap_or_else(|| running_time.saturating_sub(self.offset()))
+    }
+
+    /// if object is an element of this deck
+    fn contains(&self, object: &gst::Object) -> bool {
+        object.has_as_ancestor(&self.bin)
+    }
+
+    fn remove(self, pipeline: &gst::Pipeline, mixer: &gst::Element) {
+        self.take_follower();
+        if let Err(e) = self.bin.set_state(gst::State::Null) {
+            tracing::error!("could not stop deck: {e}");
+        }
+        let link = self.link.lock().unwrap();
+        if let Some(mixer_pad) = &link.mixer_pad {
+            if let Err(e) = link.src.unlink(mixer_pad) {
+                tracing::warn!("could not unlink deck: {e}");
+            }
+            mixer.release_request_pad(mixer_pad);
+        }
+        drop(link);
+        if let Err(e) = pipeline.remove(&self.bin) {
+            tracing::error!("could not remove deck from pipeline: {e}");
+        }
+    }
+}
+
+/// running time of pipeline
+///
+/// The position query returns the stream time, which starts at the seek position after a
+/// flushing seek, while the running time starts at zero again. Pad offsets are running time.
+fn running_time(pipeline: &gst::Pipeline, mixer: &gst::Element) -> Option<gst::ClockTime> {
+    let stream_time = pipeline.query_position::<gst::ClockTime>()?;
+    let event = mixer
+        .static_pad("src")?
+        .sticky_event::<gst::event::Segment>(0)?;
+    let segment = event.segment().downcast_ref::<gst::ClockTime>()?;
+    segment.to_running_time(segment.position_from_stream_time(stream_time)?)
+}
+
+#[derive(Debug, Default)]
+struct Decks {
+    outgoing: Option<Deck>,
+    current: Option<Deck>,
+    next: Option<Deck>,
+    next_requested: bool,
+}
+
+impl Decks {
+    fn clear_outgoing(&mut self, pipeline: &gst::Pipeline, mixer: &gst::Element) {
+        if let Some(outgoing) = self.outgoing.take() {
+            outgoing.remove(pipeline, mixer);
+        }
+    }
+
+    fn clear_next(&mut self, pipeline: &gst::Pipeline, mixer: &gst::Element) {
+        if let Some(current) = &self.current {
+            current.take_follower();
+        }
+        if let Some(next) = self.next.take() {
+            next.remove(pipeline, mixer);
+        }
+        self.next_requested = false;
+
+        // current may already be fading out
+        if let (None, Some(current)) = (&self.outgoing, &self.current) {
+            current.set_fade(1.0);
+        }
+    }
+
+    fn clear(&mut self, pipeline: &gst::Pipeline, mixer: &gst::Element) {
+        self.clear_outgoing(pipeline, mixer);
+        self.clear_next(pipeline, mixer);
+        if let Some(current) = self.current.take() {
+            current.remove(pipeline, mixer);
+        }
+    }
+}
+
+impl Playback {
+    pub fn new() -> anyhow::Result<(Self, async_channel::Receiver<PlaybackOut>)> {
+        let (sender, receiver) = async_channel::unbounded();
+        gst::init()?;
+
+        // Create the empty pipeline
+        let pipeline = gst::Pipeline::with_name("playback");
+        // Create the elements
+        let mixer = gst::ElementFactory::make_with_name("audiomixer", Some("mixer"))?;
+        let convert = gst::ElementFactory::make_with_name("audioconvert", Some("convert"))?;
+        // prevents clipping from positive ReplayGain, only enabled with ReplayGain
+        let limiter = gst::ElementFactory::make_with_name("rglimiter", Some("limiter"))?;
+        let volume = gst::ElementFactory::make_with_name("volume", Some("volume"))?;
+        let equalizer =
+            gst::ElementFactory::make_with_name("equalizer-10bands", Some("equalizer"))?;
+        let sink = gst::ElementFactory::make_with_name("autoaudiosink", Some("sink"))?;
+        let track_set = Arc::new(AtomicBool::new(false));
+        let scrobbled = Arc::new(Mutex::new(Scrobbled::SetTo(None)));
+        let decks = Rc::new(RefCell::new(Decks::default()));
+        // sources of errors, the decks they belong to are removed in the tick
+        let failed: Arc<Mutex<Vec<gst::Object>>> = Arc::new(Mutex::new(vec![]));
+
+        // build the pipeline
+        // decks are added when tracks are set
+        pipeline.add_many([&mixer, &convert, &limiter, &volume, &equalizer, &sink])?;
+        gst::Element::link_many([&mixer, &convert, &limiter, &volume, &equalizer, &sink])
+            .expect("Elements could not be linked.");
+
         //check for pipline messages
         let send = sender.clone();
         let bus = pipeline.bus().unwrap();
         let track = track_set.clone();
+        let failures = failed.clone();
         std::thread::spawn(move || {
             for msg in bus.iter_timed(gst::ClockTime::NONE) {
                 use gstreamer::MessageView;
-                if let MessageView::Eos(..) = msg.view() {
-                    track.store(false, Ordering::Relaxed);
-                    send.try_send(PlaybackOut::TrackEnd).unwrap();
+                match msg.view() {
+                    // the mixer only sends eos when no following deck is linked
+                    MessageView::Eos(..) => {
+                        track.store(false, Ordering::Relaxed);
+                        send.try_send(PlaybackOut::TrackEnd).unwrap();
+                    }
+                    MessageView::Error(err) => {
+                        tracing::error!(
+                            "error from {:?}: {} ({:?})",
+                            err.src().map(|src| src.path_string()),
+                            err.error(),
+                            err.debug()
+                        );
+                        if let Some(src) = err.src() {
+                            failures.lock().unwrap().push(src.clone());
+                        }
+                    }
+                    // radio streams send their ICY metadata as tags
+                    MessageView::Tag(tag) => {
+                        if let Some(title) = tag.tags().get::<gst::tags::Title>() {
+                            send.try_send(PlaybackOut::StreamTitle(title.get().to_string()))
+                                .unwrap();
+                        }
+                    }
+                    _ => {}
                 }
             }
         });
 
-        //callback for seekbar
-        let stamp = Rc::new(RefCell::new(pipeline.query_position::<gst::ClockTime>()));
+        //callback for seekbar and handing over to next deck
+        let stamp = Rc::new(RefCell::new(None));
         let pipeline_weak = pipeline.downgrade();
+        let mixer_weak = mixer.downgrade();
         let send = sender.clone();
         let scrobble = scrobbled.clone();
+        let tick_decks = decks.clone();
+        let track = track_set.clone();
         gtk::glib::source::timeout_add_local(std::time::Duration::from_millis(TICK), move || {
-            let Some(pipeline) = pipeline_weak.upgrade() else {
+            let (Some(pipeline), Some(mixer)) = (pipeline_weak.upgrade(), mixer_weak.upgrade())
+            else {
                 return gtk::glib::ControlFlow::Continue;
             };
 
+            // a failed deck would stall the mixer, which waits for its audio
+            let failures = std::mem::take(&mut *failed.lock().unwrap());
+            if !failures.is_empty() {
+                let mut decks = tick_decks.borrow_mut();
+                let has_failed = |deck: &Option<Deck>| {
+                    deck.as_ref()
+                        .is_some_and(|deck| failures.iter().any(|src| deck.contains(src)))
+                };
+                if has_failed(&decks.outgoing) {
+                    decks.clear_outgoing(&pipeline, &mixer);
+                }
+                let mut skip = has_failed(&decks.current);
+                if has_failed(&decks.next) {
+                    // the current deck already ended and waits for the failed one
+                    skip |= decks
+                        .current
+                        .as_ref()
+                        .is_some_and(|current| current.end().is_some());
+                    decks.clear_next(&pipeline, &mixer);
+                    // the failed track is not requested again, it is skipped when it is played
+                    decks.next_requested = true;
+                }
+                if skip {
+                    decks.clear(&pipeline, &mixer);
+                    drop(decks);
+                    if let Err(e) = pipeline.set_state(gst::State::Ready) {
+                        tracing::error!("could not stop failed playback: {e}");
+                    }
+                    track.store(false, Ordering::Relaxed);
+                    send.try_send(PlaybackOut::TrackEnd).unwrap();
+                    return gtk::glib::ControlFlow::Continue;
+                }
+            }
+
             //dont send messages when not playing a stream
             if pipeline.current_state() != gst::State::Playing {
                 return gtk::glib::ControlFlow::Continue;
             }
 
-            let current = pipeline.query_position::<gst::ClockTime>();
+            let Some(running_time) = running_time(&pipeline, &mixer) else {
+                return gtk::glib::ControlFlow::Continue;
+            };
+            let mut decks = tick_decks.borrow_mut();
+
+            // a crossfade has to start before the current deck ends, so it relies on the
+            // duration; without one the next deck follows gapless at the end instead
+            let Decks { current, next, .. } = &mut *decks;
+            if let (Some(current), Some(next)) = (current.as_ref(), next.as_mut()) {
+                // the duration is stream time, so the end is computed from the remaining
+                // audio; after a seek it differs from the offset of the deck plus the duration
+                // very short tracks should not be faded completely
+                let crossfade = current.duration().map(|duration| {
+                    let remaining = duration.saturating_sub(current.position(running_time));
+                    (running_time + remaining, next.crossfade.min(duration / 2))
+                });
+                if let Some((end, crossfade)) = crossfade {
+                    if !crossfade.is_zero()
+                        && !next.is_linked()
+                        && next.is_prerolled()
+                        && current.take_follower()
+                    {
+                        next.crossfade = crossfade;
+                        let start = end.saturating_sub(crossfade);
+                        // the ramp needs to be in place before the held back buffer is released
+                        next.set_offset(start);
+                        let linked = current
+                            .schedule_fade(start, crossfade, false)
+                            .and_then(|()| next.schedule_fade(start, crossfade, true))
+                            .and_then(|()| next.link(&mixer, start));
+                        if let Err(e) = linked {
+                            tracing::error!("could not crossfade into next deck: {e}");
+                            // fall back to following at the end
+                            current.set_fade(1.0);
+                            next.set_fade(1.0);
+                            current.set_follower(next, &mixer);
+                        }
+                    }
+                }
+            }
+
+            // outgoing deck ended or faded out completely
+            if decks.outgoing.as_ref().is_some_and(|outgoing| {
+                outgoing.end().is_some_and(|end| running_time >= end)
+                    || decks
+                        .current
+                        .as_ref()
+                        .is_some_and(|current| running_time >= current.offset() + current.crossfade)
+            }) {
+                decks.clear_outgoing(&pipeline, &mixer);
+            }
+
+            // next deck took over
+            if decks
+                .next
+                .as_ref()
+                .is_some_and(|next| next.is_linked() && running_time >= next.offset())
+            {
+                decks.clear_outgoing(&pipeline, &mixer);
+                let next = decks.next.take();
+                if let Some(old) = decks.current.take() {
+                    match next.as_ref().is_some_and(|next| !next.crossfade.is_zero()) {
+                        true => decks.outgoing = Some(old),
+                        false => old.remove(&pipeline, &mixer),
+                    }
+                }
+                decks.current = next;
+                decks.next_requested = false;
+                *scrobble.lock().unwrap() = Scrobbled::SetTo(Some(0.percent()));
+                send.try_send(PlaybackOut::TrackChanged).unwrap();
+            }
+
+            let Some(current) = decks.current.as_ref() else {
+                return gtk::glib::ControlFlow::Continue;
+            };
+            let position = current.position(running_time);
+            let duration = current.duration();
+
+            // ask for next track, without a duration right away as it is linked at the end anyway
+            let crossfade_ms = u64::from(Settings::get().lock().unwrap().crossfade_secs) * 1000;
+            if decks.next.is_none()
+                && !decks.next_requested
+                && duration.is_none_or(|duration| {
+                    duration.saturating_sub(position).mseconds() <= PREPARE_NEXT_MS + crossfade_ms
+                })
+            {
+                decks.next_requested = true;
+                send.try_send(PlaybackOut::AboutToFinish).unwrap();
+            }
+            drop(decks);
+
             // is not paused since last tick
-            if current != *stamp.borrow() {
-                let seconds = match current {
-                    Some(clock) => clock.seconds() as i64,
-                    None => 0,
-                };
-                send.try_send(PlaybackOut::SongPosition(seconds * 1000))
+            if Some(running_time) != *stamp.borrow() {
+                send.try_send(PlaybackOut::SongPosition(position.seconds() as i64 * 1000))
                     .unwrap();
-                stamp.replace(current);
+                stamp.replace(Some(running_time));
 
                 let mut lock = scrobble.lock().unwrap();
                 match *lock {
@@ -138,8 +678,10 @@ impl Playback {
                         tracing::error!("Scrobble is set to None while playing song");
                     }
                     Scrobbled::SetTo(Some(time)) => {
-                        if let Some(position) = pipeline.query_position::<gst::format::Percent>() {
-                            if position.percent() - time.percent()
+                        if let Some(duration) = duration.filter(|d| !d.is_zero()) {
+                            let percent = (position.mseconds() as f32 / duration.mseconds() as f32)
+                                .percent_ratio();
+                            if percent.percent() - time.percent()
                                 >= Settings::get().lock().unwrap().scrobble_threshold
                             {
                                 *lock = Scrobbled::ScrobbleTriggered;
@@ -157,22 +699,30 @@ impl Playback {
 
         let mut play = Self {
             pipeline,
-            source,
+            mixer,
+            limiter,
             volume,
             equalizer,
+            decks,
             track_set,
             scrobbled,
         };
 
         play.sync_equalizer();
         play.sync_volume();
+        play.sync_replay_gain();
         Ok((play, receiver))
     }
 
-    pub fn set_track(&mut self, uri: impl AsRef<str>) -> anyhow::Result<()> {
+    /// gain in dB for ReplayGain, when None it is read from the stream
+    pub fn set_track(&mut self, uri: impl AsRef<str>, gain: Option<f64>) -> anyhow::Result<()> {
         self.stop()?;
+
+        let deck = Deck::new(&self.pipeline, uri.as_ref(), gain)?;
+        deck.link(&self.mixer, gst::ClockTime::ZERO)?;
+        deck.bin.sync_state_with_parent()?;
+        self.decks.borrow_mut().current = Some(deck);
         self.track_set.store(true, Ordering::Relaxed);
-        self.source.set_property("uri", uri.as_ref());
 
         let mut lock = self.scrobbled.lock().unwrap();
         *lock = Scrobbled::SetTo(Some(0.percent()));
@@ -180,6 +730,41 @@ impl Playback {
         Ok(())
     }
 
+    /// prerolls the track that follows the current one, so it plays without a gap
+    /// or crossfades into it when crossfade_ms is not zero
+    pub fn set_next_track(
+        &mut self,
+        uri: impl AsRef<str>,
+        gain: Option<f64>,
+        crossfade_ms: u64,
+    ) -> anyhow::Result<()> {
+        let mut decks = self.decks.borrow_mut();
+        decks.clear_next(&self.pipeline, &self.mixer);
+        let Some(current) = decks.current.as_ref() else {
+            return Ok(());
+        };
+
+        let mut deck = Deck::new(&self.pipeline, uri.as_ref(), gain)?;
+        deck.crossfade = crossfade_ms * gst::ClockTime::MSECOND;
+        // the current track already ended, the next one is started with PlaybackOut::TrackEnd
+        if !current.set_follower(&deck, &self.mixer) {
+            deck.remove(&self.pipeline, &self.mixer);
+            return Ok(());
+        }
+        deck.bin.sync_state_with_parent()?;
+        decks.next = Some(deck);
+        decks.next_requested = true;
+
+        Ok(())
+    }
+
+    /// drops a prerolled next track, it will be requested again with PlaybackOut::AboutToFinish
+    pub fn clear_next_track(&mut self) {
+        self.decks
+            .borrow_mut()
+            .clear_next(&self.pipeline, &self.mixer);
+    }
+
     pub fn _is_track_set(&self) -> bool {
         self.track_set.load(Ordering::Relaxed)
     }
@@ -205,6 +790,7 @@ impl Playback {
     pub fn stop(&mut self) -> anyhow::Result<()> {
         self.track_set.store(false, Ordering::Relaxed);
         self.pipeline.set_state(gst::State::Ready)?;
+        self.decks.borrow_mut().clear(&self.pipeline, &self.mixer);
 
         let mut lock = self.scrobbled.lock().unwrap();
         *lock = Scrobbled::SetTo(None);
@@ -220,15 +806,25 @@ impl Playback {
     pub fn set_position(&mut self, position: i64) -> anyhow::Result<()> {
         let pos = position as u64 * gst::ClockTime::MSECOND;
 
+        // a flushing seek resets the running time, so only the current deck may stay
+        {
+            let mut decks = self.decks.borrow_mut();
+            decks.clear_outgoing(&self.pipeline, &self.mixer);
+            decks.clear_next(&self.pipeline, &self.mixer);
+            if let Some(current) = decks.current.as_ref() {
+                current.set_offset(gst::ClockTime::ZERO);
+                *current.end.lock().unwrap() = None;
+            }
+        }
+
+        // no segment seek, as the mixer needs an eos to finish the track
         // https://gstreamer.freedesktop.org/documentation/additional/design/seeking.html?gi-language=c
-        self.pipeline.seek_simple(
-            gst::SeekFlags::SEGMENT | gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
-            pos,
-        )?;
+        self.pipeline
+            .seek_simple(gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT, pos)?;
 
         // TODO find out why self.pipeline.query_position returns None
         // workaround calculate percent ourself
-        if let Some(total) = self.pipeline.query_duration::<gst::ClockTime>() {
+        if let Some(total) = self.duration_clock() {
             let percent = position as f32 / (total.mseconds() as f32);
             let mut lock = self.scrobbled.lock().unwrap();
             *lock = Scrobbled::SetTo(Some(percent.percent_ratio()));
@@ -239,15 +835,23 @@ impl Playback {
 
     /// returns position in seconds
     pub fn _position(&self) -> i32 {
-        match self.pipeline.query_position::<gst::ClockTime>() {
-            Some(clock) => clock.seconds() as i32,
-            None => 0,
+        let decks = self.decks.borrow();
+        match (
+            running_time(&self.pipeline, &self.mixer),
+            decks.current.as_ref(),
+        ) {
+            (Some(clock), Some(current)) => current.position(clock).seconds() as i32,
+            _ => 0,
         }
     }
 
+    fn duration_clock(&self) -> Option<gst::ClockTime> {
+        self.decks.borrow().current.as_ref()?.duration()
+    }
+
     /// returns duration of playback in seconds
     pub fn _duration(&self) -> i32 {
-        match self.source.query_duration::<gst::ClockTime>() {
+        match self.duration_clock() {
             Some(clock) => clock.seconds() as i32,
             None => 0,
         }
@@ -273,6 +877,12 @@ impl Playback {
         }
     }
 
+    /// the mode is applied to the following tracks, the limiter right away
+    pub fn sync_replay_gain(&mut self) {
+        let enabled = Settings::get().lock().unwrap().replay_gain != ReplayGain::Off;
+        self.limiter.set_property("enabled", enabled);
+    }
+
     fn sync_volume(&mut self) {
         let settings = Settings::get().lock().unwrap();
         let volume = if settings.mute { 0.0 } else { settings.volume };
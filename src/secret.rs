@@ -0,0 +1,255 @@
+use std::{collections::HashMap, io::Write};
+
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+const PREFIX: &str = "Buoy";
+const FILE_NAME: &str = "secrets.toml";
+
+const SERVICE: &str = "org.freedesktop.secrets";
+const SERVICE_PATH: &str = "/org/freedesktop/secrets";
+const SERVICE_INTERFACE: &str = "org.freedesktop.Secret.Service";
+const COLLECTION_PATH: &str = "/org/freedesktop/secrets/aliases/default";
+const COLLECTION_INTERFACE: &str = "org.freedesktop.Secret.Collection";
+const ITEM_INTERFACE: &str = "org.freedesktop.Secret.Item";
+/// attribute that identifies the secrets of this application
+const ATTRIBUTE: &str = "buoy-key";
+/// path that is returned when no prompt is needed
+const NO_PROMPT: &str = "/";
+
+/// stores secrets like password hashes outside of the config file
+pub trait SecretStorage {
+    fn store(&self, key: &str, secret: &str) -> anyhow::Result<()>;
+    fn lookup(&self, key: &str) -> anyhow::Result<Option<String>>;
+    fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+/// returns the default storage
+pub fn storage() -> Box<dyn SecretStorage> {
+    Box::new(Secrets::new())
+}
+
+/// uses the Secret Service and falls back to a file when it is not available
+pub struct Secrets {
+    service: Option<SecretService>,
+    file: FileStorage,
+}
+
+impl Secrets {
+    pub fn new() -> Self {
+        let service = match SecretService::new() {
+            Ok(service) => Some(service),
+            Err(e) => {
+                tracing::warn!("secret service not available, using file for secrets: {e}");
+                None
+            }
+        };
+        Self {
+            service,
+            file: FileStorage,
+        }
+    }
+}
+
+impl Default for Secrets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretStorage for Secrets {
+    fn store(&self, key: &str, secret: &str) -> anyhow::Result<()> {
+        if let Some(service) = &self.service {
+            match service.store(key, secret) {
+                Ok(()) => {
+                    // remove copies that were stored as fallback
+                    self.file.delete(key)?;
+                    return Ok(());
+                }
+                Err(e) => tracing::warn!("could not store secret in secret service: {e}"),
+            }
+        }
+        self.file.store(key, secret)
+    }
+
+    fn lookup(&self, key: &str) -> anyhow::Result<Option<String>> {
+        if let Some(service) = &self.service {
+            match service.lookup(key) {
+                Ok(Some(secret)) => return Ok(Some(secret)),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("could not lookup secret in secret service: {e}"),
+            }
+        }
+        self.file.lookup(key)
+    }
+
+    fn delete(&self, key: &str) -> anyhow::Result<()> {
+        // a locked keyring should not prevent logging out
+        if let Some(service) = &self.service {
+            if let Err(e) = service.delete(key) {
+                tracing::warn!("could not delete secret from secret service: {e}");
+            }
+        }
+        self.file.delete(key)
+    }
+}
+
+/// freedesktop Secret Service, e.g. GNOME Keyring or KWallet
+pub struct SecretService {
+    connection: zbus::blocking::Connection,
+    session: OwnedObjectPath,
+}
+
+/// secret as defined by the Secret Service specification
+type Secret = (OwnedObjectPath, Vec<u8>, Vec<u8>, String);
+
+impl SecretService {
+    pub fn new() -> anyhow::Result<Self> {
+        let connection = zbus::blocking::Connection::session()?;
+        let service = Self::proxy(&connection, SERVICE_PATH, SERVICE_INTERFACE)?;
+        let (_output, session): (OwnedValue, OwnedObjectPath) =
+            service.call("OpenSession", &("plain", Value::from("")))?;
+        Ok(Self {
+            connection,
+            session,
+        })
+    }
+
+    fn proxy<'a>(
+        connection: &zbus::blocking::Connection,
+        path: &'a str,
+        interface: &'a str,
+    ) -> anyhow::Result<zbus::blocking::Proxy<'a>> {
+        Ok(zbus::blocking::Proxy::new(
+            connection, SERVICE, path, interface,
+        )?)
+    }
+
+    fn attributes(key: &str) -> HashMap<&str, &str> {
+        HashMap::from([(ATTRIBUTE, key)])
+    }
+
+    /// returns the unlocked items that belong to key
+    fn search(&self, key: &str) -> anyhow::Result<Vec<OwnedObjectPath>> {
+        let service = Self::proxy(&self.connection, SERVICE_PATH, SERVICE_INTERFACE)?;
+        let (mut unlocked, locked): (Vec<OwnedObjectPath>, Vec<OwnedObjectPath>) =
+            service.call("SearchItems", &(Self::attributes(key),))?;
+
+        if !locked.is_empty() {
+            let (mut now_unlocked, prompt): (Vec<OwnedObjectPath>, OwnedObjectPath) =
+                service.call("Unlock", &(locked,))?;
+            if prompt.as_str() != NO_PROMPT {
+                anyhow::bail!("keyring is locked");
+            }
+            unlocked.append(&mut now_unlocked);
+        }
+
+        Ok(unlocked)
+    }
+
+    fn store(&self, key: &str, secret: &str) -> anyhow::Result<()> {
+        let collection = Self::proxy(&self.connection, COLLECTION_PATH, COLLECTION_INTERFACE)?;
+        let properties: HashMap<&str, Value> = HashMap::from([
+            (
+                "org.freedesktop.Secret.Item.Label",
+                Value::from(format!("{PREFIX}: {key}")),
+            ),
+            (
+                "org.freedesktop.Secret.Item.Attributes",
+                Value::from(Self::attributes(key)),
+            ),
+        ]);
+        let secret: Secret = (
+            self.session.clone(),
+            vec![],
+            secret.as_bytes().to_vec(),
+            String::from("text/plain"),
+        );
+
+        let (_item, prompt): (OwnedObjectPath, OwnedObjectPath) =
+            collection.call("CreateItem", &(properties, secret, true))?;
+        if prompt.as_str() != NO_PROMPT {
+            anyhow::bail!("keyring is locked");
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let items = self.search(key)?;
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let service = Self::proxy(&self.connection, SERVICE_PATH, SERVICE_INTERFACE)?;
+        let secrets: HashMap<OwnedObjectPath, Secret> =
+            service.call("GetSecrets", &(items, &self.session))?;
+        match secrets.into_values().next() {
+            None => Ok(None),
+            Some((_session, _parameters, value, _content_type)) => {
+                Ok(Some(String::from_utf8(value)?))
+            }
+        }
+    }
+
+    fn delete(&self, key: &str) -> anyhow::Result<()> {
+        for item in self.search(key)? {
+            let item = Self::proxy(&self.connection, item.as_str(), ITEM_INTERFACE)?;
+            let _prompt: OwnedObjectPath = item.call("Delete", &())?;
+        }
+        Ok(())
+    }
+}
+
+/// stores secrets in a file only readable by the user
+pub struct FileStorage;
+
+impl FileStorage {
+    fn path() -> anyhow::Result<std::path::PathBuf> {
+        Ok(dirs::config_dir()
+            .ok_or(std::io::Error::other("cant create config dir"))?
+            .join(PREFIX)
+            .join(FILE_NAME))
+    }
+
+    fn read() -> anyhow::Result<HashMap<String, String>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    fn write(secrets: &HashMap<String, String>) -> anyhow::Result<()> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let content = toml::to_string(secrets)?;
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(Self::path()?)?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl SecretStorage for FileStorage {
+    fn store(&self, key: &str, secret: &str) -> anyhow::Result<()> {
+        let mut secrets = Self::read()?;
+        secrets.insert(String::from(key), String::from(secret));
+        Self::write(&secrets)
+    }
+
+    fn lookup(&self, key: &str) -> anyhow::Result<Option<String>> {
+        Ok(Self::read()?.remove(key))
+    }
+
+    fn delete(&self, key: &str) -> anyhow::Result<()> {
+        let mut secrets = Self::read()?;
+        if secrets.remove(key).is_some() {
+            Self::write(&secrets)?;
+        }
+        Ok(())
+    }
+}
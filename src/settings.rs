@@ -7,7 +7,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::Client,
-    components::sequence_button_impl::{repeat::Repeat, shuffle::Shuffle},
+    common::{replay_gain::ReplayGain, transcoding::StreamProfile},
+    components::sequence_button_impl::{queue_end::QueueEnd, repeat::Repeat, shuffle::Shuffle},
+    secret::{self, SecretStorage},
 };
 
 const PREFIX: &str = "Buoy";
@@ -32,26 +34,50 @@ pub struct Settings {
     pub login_uri: Option<String>,
     #[serde(default)] //defaults to None
     pub login_username: Option<String>,
-    #[serde(default)] //defaults to None
+    // credentials are kept in the secret storage, they are only read from old configs
+    // and moved into a profile, which keeps them until they are stored
+    #[serde(default, skip_serializing)] //defaults to None
     pub login_hash: Option<String>,
-    #[serde(default)] //defaults to None
+    #[serde(default, skip_serializing)] //defaults to None
     pub login_salt: Option<String>,
+    #[serde(default)] //defaults to empty
+    pub profiles: Vec<ServerProfile>,
+    #[serde(default)] //defaults to None
+    pub active_profile: Option<String>,
 
     #[serde(default = "default_volume")]
     pub volume: f64,
     #[serde(default = "default_mute")]
     pub mute: bool,
+    #[serde(default)]
+    pub replay_gain: ReplayGain,
+    #[serde(default)] //defaults to 0.0 dB
+    pub replay_gain_preamp: f64,
+
+    #[serde(default)] //defaults to 0
+    pub crossfade_secs: u32,
+    #[serde(default)] //defaults to false
+    pub crossfade_skip_same_album: bool,
+
+    #[serde(default)] //defaults to original files
+    pub stream_unmetered: StreamProfile,
+    #[serde(default)] //defaults to original files
+    pub stream_metered: StreamProfile,
 
     #[serde(default)]
     pub repeat: Repeat,
     #[serde(default)]
     pub shuffle: Shuffle,
+    #[serde(default)] //defaults to stop
+    pub queue_end: QueueEnd,
 
     #[serde(default)]
     pub send_notifications: bool,
 
     #[serde(default)]
     pub queue_animations: bool,
+    #[serde(default = "default_sync_queue")]
+    pub sync_queue: bool,
 
     #[serde(default)]
     pub scrobble: bool,
@@ -88,6 +114,96 @@ pub struct Settings {
     pub dashboard_line_items: usize,
 }
 
+/// login information of a server, every server gets its own cache
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ServerProfile {
+    pub name: String,
+    pub uri: String,
+    pub username: String,
+    /// key of the credentials in the secret storage
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(skip)]
+    pub hash: String,
+    #[serde(skip)]
+    pub salt: String,
+    /// credentials that are not in the secret storage yet; they stay in the config file
+    /// until moving them succeeded
+    #[serde(default, rename = "hash", skip_serializing_if = "String::is_empty")]
+    file_hash: String,
+    #[serde(default, rename = "salt", skip_serializing_if = "String::is_empty")]
+    file_salt: String,
+    /// folder in the cache dir; empty for the login that existed before profiles
+    #[serde(default)]
+    pub cache_folder: String,
+    /// ids of the music folders the library is restricted to; empty for all folders
+    #[serde(default)]
+    pub music_folders: Vec<String>,
+}
+
+impl ServerProfile {
+    pub fn new(
+        uri: impl Into<String>,
+        username: impl Into<String>,
+        hash: impl Into<String>,
+        salt: impl Into<String>,
+    ) -> Self {
+        let uri = uri.into();
+        let username = username.into();
+        // servers on the same host can differ in port or path
+        let name = format!("{username}@{}", normalize_uri(&uri));
+        let cache_folder = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+
+        Self {
+            name,
+            uri,
+            username,
+            secret: None,
+            hash: hash.into(),
+            salt: salt.into(),
+            file_hash: String::new(),
+            file_salt: String::new(),
+            cache_folder,
+            music_folders: vec![],
+        }
+    }
+
+    /// returns true when both profiles log into the same server with the same user
+    fn same_login(&self, other: &Self) -> bool {
+        self.username == other.username && normalize_uri(&self.uri) == normalize_uri(&other.uri)
+    }
+
+    fn store_secret(&mut self, storage: &dyn SecretStorage) -> anyhow::Result<()> {
+        let key = self.name.clone();
+        storage.store(&key, &format!("{}:{}", self.hash, self.salt))?;
+        self.secret = Some(key);
+        Ok(())
+    }
+
+    /// keeps the credentials in the config file, when they could not be stored as secret
+    fn keep_in_file(&mut self) {
+        self.file_hash = self.hash.clone();
+        self.file_salt = self.salt.clone();
+    }
+}
+
+/// scheme, host, port and path of uri, so the same server always gets the same profile
+fn normalize_uri(uri: &str) -> String {
+    let Ok(url) = url::Url::parse(uri) else {
+        return uri.trim_end_matches('/').to_string();
+    };
+    let mut normalized = format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default());
+    // default ports are not part of the url
+    if let Some(port) = url.port() {
+        normalized.push_str(&format!(":{port}"));
+    }
+    normalized.push_str(url.path().trim_end_matches('/'));
+    normalized
+}
+
 fn default_window_width() -> i32 {
     1200
 }
@@ -108,6 +224,10 @@ fn default_mute() -> bool {
     false
 }
 
+fn default_sync_queue() -> bool {
+    true
+}
+
 fn default_scrobble_threshold() -> u32 {
     80
 }
@@ -150,8 +270,18 @@ impl Settings {
         let mut content = String::new();
         config_file.read_to_string(&mut content).unwrap_or_default();
         tracing::info!("loaded settings from file or created default settings");
-        let setting = toml::from_str::<Settings>(&content)
+        let mut setting = toml::from_str::<Settings>(&content)
             .expect("not all members of Settings are defaulted");
+        setting.migrate_login();
+        if setting.load_secrets() {
+            // remove credentials from config file
+            if let Err(e) = setting.save() {
+                tracing::error!("could not save migrated settings: {e}");
+            }
+        }
+        if let Some(name) = setting.active_profile.clone() {
+            setting.activate_profile(&name);
+        }
         SETTING.get_or_init(|| Mutex::new(setting))
     }
 
@@ -166,7 +296,170 @@ impl Settings {
         Ok(())
     }
 
+    /// creates a profile for a login from before profiles existed, so its cache is kept
+    fn migrate_login(&mut self) {
+        if !self.profiles.is_empty() {
+            return;
+        }
+        if let (Some(uri), Some(user), Some(hash), Some(salt)) = (
+            &self.login_uri,
+            &self.login_username,
+            &self.login_hash,
+            &self.login_salt,
+        ) {
+            let mut profile = ServerProfile::new(uri, user, hash, salt);
+            profile.cache_folder = String::new();
+            tracing::info!("migrated login to profile {}", profile.name);
+            self.active_profile = Some(profile.name.clone());
+            self.profiles.push(profile);
+        }
+    }
+
+    /// loads the credentials of all profiles from the secret storage
+    ///
+    /// credentials that are still in the config file are moved into the secret storage,
+    /// returns true when that happened
+    fn load_secrets(&mut self) -> bool {
+        let storage = secret::storage();
+        let mut migrated = false;
+        for profile in &mut self.profiles {
+            if profile.secret.is_none() {
+                if profile.hash.is_empty() {
+                    profile.hash = profile.file_hash.clone();
+                    profile.salt = profile.file_salt.clone();
+                }
+                if profile.hash.is_empty() {
+                    continue;
+                }
+                match profile.store_secret(storage.as_ref()) {
+                    Ok(()) => {
+                        profile.file_hash.clear();
+                        profile.file_salt.clear();
+                        migrated = true;
+                    }
+                    Err(e) => {
+                        tracing::error!("could not migrate credentials: {e}");
+                        // keep them in the config file, so they are not lost with the next save
+                        profile.keep_in_file();
+                    }
+                }
+                continue;
+            }
+
+            let Some(key) = &profile.secret else {
+                continue;
+            };
+            match storage.lookup(key) {
+                Ok(Some(secret)) => match secret.split_once(':') {
+                    Some((hash, salt)) => {
+                        profile.hash = String::from(hash);
+                        profile.salt = String::from(salt);
+                    }
+                    None => tracing::error!("malformed credentials of {}", profile.name),
+                },
+                Ok(None) => tracing::warn!("no credentials found for {}", profile.name),
+                Err(e) => tracing::error!("could not load credentials of {}: {e}", profile.name),
+            }
+        }
+        migrated
+    }
+
+    pub fn active_profile(&self) -> Option<&ServerProfile> {
+        let name = self.active_profile.as_ref()?;
+        self.profiles.iter().find(|profile| &profile.name == name)
+    }
+
+    /// adds or replaces a profile with the same name and uses it for login
+    pub fn add_profile(&mut self, mut profile: ServerProfile) {
+        if let Err(e) = profile.store_secret(secret::storage().as_ref()) {
+            tracing::error!("could not store credentials as secret, keep them in config: {e}");
+            profile.keep_in_file();
+        }
+        let name = profile.name.clone();
+        match self
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == profile.name || p.same_login(&profile))
+        {
+            Some(old) => {
+                // profiles from before port and path were part of the name used another key
+                if let Some(key) = old.secret.take().filter(|key| *key != name) {
+                    _ = secret::storage().delete(&key);
+                }
+                // keep cache and library selection of a known server
+                let cache_folder = std::mem::take(&mut old.cache_folder);
+                let music_folders = std::mem::take(&mut old.music_folders);
+                *old = ServerProfile {
+                    cache_folder,
+                    music_folders,
+                    ..profile
+                };
+            }
+            None => self.profiles.push(profile),
+        }
+        self.activate_profile(&name);
+    }
+
+    /// uses the login of the profile; returns false if there is no such profile
+    pub fn activate_profile(&mut self, name: &str) -> bool {
+        let Some(profile) = self.profiles.iter().find(|profile| profile.name == name) else {
+            return false;
+        };
+        self.login_uri = Some(profile.uri.clone());
+        self.login_username = Some(profile.username.clone());
+        self.login_hash = Some(profile.hash.clone());
+        self.login_salt = Some(profile.salt.clone());
+        self.active_profile = Some(profile.name.clone());
+        true
+    }
+
+    /// ids of the music folders of the active profile; empty for all folders
+    pub fn music_folders(&self) -> Vec<String> {
+        self.active_profile()
+            .map(|profile| profile.music_folders.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_music_folders(&mut self, folders: Vec<String>) {
+        let Some(name) = &self.active_profile else {
+            return;
+        };
+        if let Some(profile) = self.profiles.iter_mut().find(|p| &p.name == name) {
+            profile.music_folders = folders;
+        }
+    }
+
+    /// cache folder of the active profile
+    ///
+    /// locks Settings, so it must not be called while holding the lock
+    pub fn cache_dir() -> anyhow::Result<std::path::PathBuf> {
+        let folder = Self::get()
+            .lock()
+            .unwrap()
+            .active_profile()
+            .map(|profile| profile.cache_folder.clone())
+            .unwrap_or_default();
+        let dir = dirs::cache_dir()
+            .ok_or(std::io::Error::other("cant create cache dir"))?
+            .join(PREFIX)
+            .join(folder);
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// removes the login and the active profile
     pub fn reset_login(&mut self) -> anyhow::Result<()> {
+        if let Some(key) = self
+            .active_profile()
+            .and_then(|profile| profile.secret.clone())
+        {
+            if let Err(e) = secret::storage().delete(&key) {
+                tracing::error!("could not delete credentials: {e}");
+            }
+        }
+        if let Some(name) = self.active_profile.take() {
+            self.profiles.retain(|profile| profile.name != name);
+        }
         self.login_uri = None;
         self.login_username = None;
         self.login_hash = None;
@@ -192,3 +485,72 @@ impl Settings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_is_normalized() {
+        assert_eq!(
+            normalize_uri("https://music.example.com/"),
+            "https://music.example.com"
+        );
+        assert_eq!(
+            normalize_uri("https://music.example.com:443/navidrome/"),
+            "https://music.example.com/navidrome"
+        );
+        assert_eq!(
+            normalize_uri("http://192.168.0.2:4533"),
+            "http://192.168.0.2:4533"
+        );
+    }
+
+    #[test]
+    fn profiles_differ_in_port_and_path() {
+        let profile = ServerProfile::new("https://example.com:4533/", "user", "hash", "salt");
+        assert_eq!(profile.name, "user@https://example.com:4533");
+
+        let other_port = ServerProfile::new("https://example.com:4534", "user", "hash", "salt");
+        let other_path = ServerProfile::new("https://example.com:4533/a", "user", "hash", "salt");
+        let same = ServerProfile::new("https://example.com:4533", "user", "other", "salt");
+        assert!(!profile.same_login(&other_port));
+        assert!(!profile.same_login(&other_path));
+        assert!(profile.same_login(&same));
+        assert_ne!(profile.cache_folder, other_port.cache_folder);
+    }
+
+    /// storage that fails like a locked keyring without a writable fallback file
+    struct FailingStorage;
+
+    impl SecretStorage for FailingStorage {
+        fn store(&self, _key: &str, _secret: &str) -> anyhow::Result<()> {
+            anyhow::bail!("keyring is locked")
+        }
+        fn lookup(&self, _key: &str) -> anyhow::Result<Option<String>> {
+            Ok(None)
+        }
+        fn delete(&self, _key: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn credentials_stay_in_config_until_stored() {
+        let mut profile = ServerProfile::new("https://example.com", "user", "hash", "salt");
+        assert!(profile.store_secret(&FailingStorage).is_err());
+        profile.keep_in_file();
+        assert!(profile.secret.is_none());
+
+        let config = toml::to_string(&profile).unwrap();
+        let loaded: ServerProfile = toml::from_str(&config).unwrap();
+        assert_eq!(loaded.file_hash, "hash");
+        assert_eq!(loaded.file_salt, "salt");
+
+        // stored secrets are not written to the config
+        let mut stored = ServerProfile::new("https://example.com", "user", "hash", "salt");
+        stored.secret = Some(stored.name.clone());
+        let config = toml::to_string(&stored).unwrap();
+        assert!(!config.contains("hash"));
+    }
+}
@@ -0,0 +1,166 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gettextrs::gettext;
+use relm4::{
+    gtk::{
+        self,
+        prelude::{
+            BoxExt, ButtonExt, EditableExt, GridExt, GtkApplicationExt, GtkWindowExt,
+            OrientableExt, WidgetExt,
+        },
+    },
+    RelmWidgetExt,
+};
+
+use crate::{
+    common::types::Droppable,
+    components::app::{App, AppIn},
+    subsonic::Subsonic,
+};
+
+pub struct Share {}
+
+impl Share {
+    /// asks for description and expiry of a new share for drop
+    pub fn show_dialog(
+        subsonic: &Rc<RefCell<Subsonic>>,
+        sender: relm4::component::AsyncComponentSender<App>,
+        drop: Droppable,
+    ) {
+        let (ids, name) = Self::share_info(subsonic, &drop);
+        if ids.is_empty() {
+            return;
+        }
+
+        relm4::view! {
+            window = gtk::Window {
+                set_modal: true,
+                set_resizable: false,
+                set_title: Some(&gettext("Share")),
+                set_transient_for: Some(&relm4::main_application().windows()[0]),
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_margin_all: 15,
+                    set_spacing: 10,
+
+                    gtk::Label {
+                        add_css_class: granite::STYLE_CLASS_H2_LABEL,
+                        set_halign: gtk::Align::Start,
+                        set_label: &gettext("Create share link"),
+                    },
+                    gtk::Grid {
+                        set_row_spacing: 7,
+                        set_column_spacing: 7,
+
+                        attach[0, 0, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Description"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 0, 1, 1]: description = &gtk::Entry {
+                            set_hexpand: true,
+                            set_text: &name,
+                        },
+                        attach[0, 1, 1, 1] = &gtk::Label {
+                            set_label: &gettext("Expires"),
+                            set_halign: gtk::Align::End,
+                        },
+                        attach[1, 1, 1, 1]: expiry = &expiry_options() -> gtk::DropDown {},
+                    },
+                    gtk::Box {
+                        set_halign: gtk::Align::End,
+                        set_spacing: 10,
+
+                        append: cancel_btn = &gtk::Button {
+                            set_label: &gettext("Cancel"),
+                        },
+                        append: share_btn = &gtk::Button {
+                            add_css_class: "suggested-action",
+                            set_label: &gettext("Copy link"),
+                        },
+                    }
+                }
+            }
+        }
+
+        let win = window.clone();
+        cancel_btn.connect_clicked(move |_btn| {
+            win.close();
+        });
+
+        let win = window.clone();
+        share_btn.connect_clicked(move |_btn| {
+            win.close();
+            let description = description.text().trim().to_string();
+            let description = (!description.is_empty()).then_some(description);
+            sender.input(AppIn::CreateShare(
+                ids.clone(),
+                description,
+                expires_at(expiry.selected()),
+            ));
+        });
+
+        window.show();
+    }
+
+    /// returns the ids that are shared and a name describing them
+    fn share_info(subsonic: &Rc<RefCell<Subsonic>>, drop: &Droppable) -> (Vec<String>, String) {
+        match drop {
+            Droppable::Album(album) => (vec![album.id.clone()], album.name.clone()),
+            Droppable::AlbumChild(child) => (vec![child.id.clone()], child.title.clone()),
+            Droppable::AlbumWithSongs(album) => {
+                (vec![album.base.id.clone()], album.base.name.clone())
+            }
+            Droppable::Playlist(list) => (vec![list.base.id.clone()], list.base.name.clone()),
+            Droppable::Child(child) => (vec![child.id.clone()], child.title.clone()),
+            Droppable::Directory(dir, _songs) => (vec![dir.id.clone()], dir.name.clone()),
+            drop => {
+                let songs = drop.get_songs(subsonic);
+                let name = match songs.as_slice() {
+                    [song] => song.title.clone(),
+                    songs => format!("{} {}", songs.len(), gettext("songs")),
+                };
+                (songs.into_iter().map(|song| song.id).collect(), name)
+            }
+        }
+    }
+}
+
+/// choices for when a share expires
+pub fn expiry_options() -> gtk::DropDown {
+    let never = gettext("Never");
+    let day = gettext("In one day");
+    let week = gettext("In one week");
+    let month = gettext("In one month");
+    let year = gettext("In one year");
+    gtk::DropDown::from_strings(&[&never, &day, &week, &month, &year])
+}
+
+/// converts the selection of expiry_options into ms since epoch; None never expires
+pub fn expires_at(selected: u32) -> Option<i64> {
+    let duration = match selected {
+        1 => chrono::TimeDelta::days(1),
+        2 => chrono::TimeDelta::weeks(1),
+        3 => chrono::TimeDelta::days(30),
+        4 => chrono::TimeDelta::days(365),
+        _ => return None,
+    };
+    Some((chrono::Utc::now() + duration).timestamp_millis())
+}
+
+/// returns the shortest option of expiry_options that does not expire before expires
+pub fn expiry_option(expires: Option<i64>) -> u32 {
+    let Some(expires) = expires else {
+        return 0;
+    };
+    (1..4)
+        .find(|&selected| expires_at(selected).is_some_and(|at| at >= expires))
+        .unwrap_or(4)
+}
+
+/// copies text into the clipboard of the app
+pub fn copy_to_clipboard(text: &str) {
+    if let Some(display) = gtk::gdk::Display::default() {
+        display.clipboard().set_text(text);
+    }
+}
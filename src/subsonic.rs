@@ -1,22 +1,50 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
 
 use futures::StreamExt;
+use gettextrs::gettext;
 use serde::{Deserialize, Serialize};
 
-use crate::{client::Client, subsonic_cover, subsonic_cover::SubsonicCovers};
+use crate::{
+    client::Client, common::types::Id, settings::Settings, subsonic_cover,
+    subsonic_cover::SubsonicCovers, subsonic_history::SubsonicHistory,
+    subsonic_offline::SubsonicOffline, subsonic_podcasts::SubsonicPodcasts,
+    subsonic_scrobbles::SubsonicScrobbles, subsonic_smart_playlists::SubsonicSmartPlaylists,
+};
 
-const PREFIX: &str = "Buoy";
 const MUSIC_INFOS: &str = "Music-Infos";
+/// sender of the channel of the current load, see Subsonic::progress
+static PROGRESS: Mutex<Option<async_channel::Sender<String>>> = Mutex::new(None);
+/// number of albums fetched at once while looking for changed albums
+const SYNC_PAGE_SIZE: usize = 50;
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Subsonic {
     scan_status: Option<i64>,
+    /// music folders the cache was fetched for; empty for all folders
+    #[serde(default)]
+    music_folders: Vec<String>,
     artists: Vec<submarine::data::ArtistId3>,
     album_list: Vec<submarine::data::Child>,
     tracks: Vec<submarine::data::Child>,
     playlists: Vec<submarine::data::PlaylistWithSongs>,
     #[serde(skip)]
     covers: SubsonicCovers,
+    #[serde(skip)]
+    offline: SubsonicOffline,
+    #[serde(skip)]
+    podcasts: SubsonicPodcasts,
+    #[serde(skip)]
+    smart_playlists: SubsonicSmartPlaylists,
+    #[serde(skip)]
+    history: SubsonicHistory,
+    #[serde(skip)]
+    scrobbles: SubsonicScrobbles,
+    /// songs of pins that changed with the last sync and are not stored offline yet
+    #[serde(skip)]
+    outdated_pins: Vec<submarine::data::Child>,
 }
 
 impl Subsonic {
@@ -26,151 +54,484 @@ impl Subsonic {
 
         //fetch scan status
         let scan_status = client.get_scan_status().await?;
+        let music_folders = Settings::get().lock().unwrap().music_folders();
 
         //fetch artists
         tracing::info!("fetching artists");
-        let indexes = client.get_artists(None).await?;
-        let artists: Vec<_> = indexes.into_iter().flat_map(|i| i.artist).collect();
+        Self::report(gettext("fetching artists"));
+        let artists = Self::fetch_artists(&music_folders).await?;
         tracing::info!("fetched {} artists", artists.len());
 
         //fetch album_list
         tracing::info!("fetching albums");
-        let album_list: Vec<submarine::data::Child> = {
-            let mut albums = vec![];
+        Self::report(gettext("fetching albums"));
+        let album_list = Self::fetch_album_list(&music_folders).await;
+        tracing::info!("fetched {} albums", album_list.len());
+
+        //fetch tracks
+        tracing::info!("fetching tracks");
+        let tracks = Self::fetch_tracks(&album_list).await;
+        let tracks: Vec<submarine::data::Child> = tracks.into_values().flatten().collect();
+        tracing::info!("fetched {} tracks", tracks.len());
+
+        //fetch playlists
+        tracing::info!("fetching playlists");
+        Self::report(gettext("fetching playlists"));
+        let playlists = Self::fetch_playlists().await?;
+        tracing::info!("fetched {} playlists", playlists.len());
+
+        let result = Self {
+            scan_status: scan_status.count,
+            music_folders,
+            artists,
+            album_list,
+            tracks,
+            playlists,
+            covers: SubsonicCovers::default(),
+            offline: SubsonicOffline::default(),
+            podcasts: SubsonicPodcasts::default(),
+            smart_playlists: SubsonicSmartPlaylists::default(),
+            history: SubsonicHistory::default(),
+            scrobbles: SubsonicScrobbles::default(),
+            outdated_pins: vec![],
+        };
+
+        result.save()?;
+
+        tracing::info!("finished loading subsonic info");
+        Ok(result)
+    }
+
+    /// updates only the albums that changed on the server since the last sync
+    ///
+    /// the albums are diffed newest first until the first unchanged one; removed albums are
+    /// found with the album counts of the artists
+    /// everything is fetched before the cache is changed, so it stays intact when the connection fails
+    pub async fn sync(&mut self) -> anyhow::Result<()> {
+        tracing::info!("sync subsonic cache");
+        let client = Client::get().unwrap();
+        let scan_status = client.get_scan_status().await?;
+        let music_folders = Settings::get().lock().unwrap().music_folders();
+
+        //fetch artists
+        Self::report(gettext("fetching artists"));
+        let artists = Self::fetch_artists(&music_folders).await?;
+
+        //find new, changed and removed albums
+        Self::report(gettext("fetching albums"));
+        let cached: HashMap<&str, &submarine::data::Child> = self
+            .album_list
+            .iter()
+            .map(|album| (album.id.as_str(), album))
+            .collect();
+        let (outdated, removed) = if music_folders != self.music_folders {
+            // albums of other folders are in the cache, so the full list is needed
+            let album_list = Self::fetch_album_list(&music_folders).await;
+            let current: HashSet<&str> = album_list.iter().map(|album| album.id.as_str()).collect();
+            let removed: HashSet<String> = self
+                .album_list
+                .iter()
+                .filter(|album| !current.contains(album.id.as_str()))
+                .map(|album| album.id.clone())
+                .collect();
+            let outdated: Vec<submarine::data::Child> = album_list
+                .into_iter()
+                .filter(|album| match cached.get(album.id.as_str()) {
+                    None => true,
+                    Some(old) => album_changed(old, album),
+                })
+                .collect();
+            (outdated, removed)
+        } else {
+            let outdated = Self::fetch_changed_albums(&music_folders, &cached).await?;
+            let removed = self.fetch_removed_albums(&artists, &outdated).await?;
+            (outdated, removed)
+        };
+        tracing::info!(
+            "{} albums are new or changed, {} albums were removed",
+            outdated.len(),
+            removed.len()
+        );
+
+        //fetch tracks of outdated albums
+        let fetched = Self::fetch_tracks(&outdated).await;
+        let failed = outdated.len() - fetched.len();
+
+        //refresh playlists
+        Self::report(gettext("fetching playlists"));
+        let playlists = Self::fetch_playlists().await?;
+
+        self.artists = artists;
+        self.tracks.retain(|track| match &track.album_id {
+            None => true,
+            Some(id) => !removed.contains(id) && !fetched.contains_key(id),
+        });
+        self.album_list = apply_album_changes(&self.album_list, outdated, &removed, &fetched);
+        self.tracks.extend(fetched.into_values().flatten());
+        tracing::info!("synced {} tracks", self.tracks.len());
+        self.playlists = playlists;
+        // albums that could not be fetched are tried again with the next sync
+        if failed == 0 {
+            self.scan_status = scan_status.count;
+        } else {
+            tracing::warn!("could not fetch {failed} albums; sync again on next start");
+        }
+        self.music_folders = music_folders;
+        self.save()?;
+
+        tracing::info!("finished syncing subsonic info");
+        Ok(())
+    }
+
+    /// fetches the newest albums of the music folders until the first album that is cached
+    /// and did not change
+    async fn fetch_changed_albums(
+        folders: &[String],
+        cached: &HashMap<&str, &submarine::data::Child>,
+    ) -> anyhow::Result<Vec<submarine::data::Child>> {
+        let client = Client::get().unwrap();
+        let folders: Vec<Option<&str>> = match folders.is_empty() {
+            true => vec![None],
+            false => folders.iter().map(|folder| Some(folder.as_str())).collect(),
+        };
+
+        let mut changed = vec![];
+        for folder in folders {
             let mut offset = 0;
-            loop {
-                match client
+            'pages: loop {
+                let page = client
                     .get_album_list(
-                        submarine::api::get_album_list::Order::AlphabeticalByName,
-                        Some(500),
+                        submarine::api::get_album_list::Order::Newest,
+                        Some(SYNC_PAGE_SIZE),
                         Some(offset),
-                        None::<&str>,
+                        folder,
                     )
-                    .await
-                {
-                    Err(e) => {
-                        tracing::error!("error while fetching albums: {e}");
+                    .await?;
+                for album in &page {
+                    match cached.get(album.id.as_str()) {
+                        Some(old) if !album_changed(old, album) => break 'pages,
+                        _ => changed.push(album.clone()),
                     }
-                    Ok(mut part) => {
-                        if part.len() < 500 || part.is_empty() {
-                            albums.append(&mut part);
-                            break;
-                        } else {
-                            albums.append(&mut part);
-                            offset += 500;
-                        }
+                }
+                if page.len() < SYNC_PAGE_SIZE {
+                    break;
+                }
+                offset += SYNC_PAGE_SIZE;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// returns the ids of cached albums that were removed on the server
+    ///
+    /// only the albums of artists whose album count changed are fetched
+    async fn fetch_removed_albums(
+        &self,
+        artists: &[submarine::data::ArtistId3],
+        changed: &[submarine::data::Child],
+    ) -> anyhow::Result<HashSet<String>> {
+        let client = Client::get().unwrap();
+        let server_counts: HashMap<&str, usize> = artists
+            .iter()
+            .map(|artist| (artist.id.as_str(), artist.album_count.max(0) as usize))
+            .collect();
+
+        // albums the cache will contain when nothing was removed
+        let mut known: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for album in self.album_list.iter().chain(changed) {
+            if let Some(artist) = &album.artist_id {
+                known
+                    .entry(artist.as_str())
+                    .or_default()
+                    .insert(album.id.as_str());
+            }
+        }
+
+        let mut removed = HashSet::new();
+        for (artist, albums) in known {
+            let current: HashSet<String> = match server_counts.get(artist) {
+                // artist has no albums in the music folders anymore
+                None => HashSet::new(),
+                Some(count) if *count == albums.len() => continue,
+                Some(_) => client
+                    .get_artist(artist)
+                    .await?
+                    .album
+                    .into_iter()
+                    .map(|album| album.id)
+                    .collect(),
+            };
+            removed.extend(
+                albums
+                    .into_iter()
+                    .filter(|album| !current.contains(*album))
+                    .map(String::from),
+            );
+        }
+        Ok(removed)
+    }
+
+    /// creates a channel for progress messages while loading or syncing the library,
+    /// messages of earlier loads are not received
+    pub fn progress() -> async_channel::Receiver<String> {
+        let (sender, receiver) = async_channel::unbounded();
+        *PROGRESS.lock().unwrap() = Some(sender);
+        receiver
+    }
+
+    fn report(msg: impl Into<String>) {
+        if let Some(sender) = PROGRESS.lock().unwrap().as_ref() {
+            _ = sender.try_send(msg.into());
+        }
+    }
+
+    /// fetches the artists of the music folders, all artists when folders is empty
+    async fn fetch_artists(folders: &[String]) -> anyhow::Result<Vec<submarine::data::ArtistId3>> {
+        let client = Client::get().unwrap();
+        if folders.is_empty() {
+            let indexes = client.get_artists(None).await?;
+            return Ok(indexes.into_iter().flat_map(|i| i.artist).collect());
+        }
+
+        // an artist can have albums in several folders
+        let mut ids = HashSet::new();
+        let mut artists = vec![];
+        for folder in folders {
+            let indexes = client.get_artists(Some(folder.clone())).await?;
+            artists.extend(
+                indexes
+                    .into_iter()
+                    .flat_map(|i| i.artist)
+                    .filter(|artist| ids.insert(artist.id.clone())),
+            );
+        }
+        Ok(artists)
+    }
+
+    /// fetches the albums of the music folders, all albums when folders is empty
+    async fn fetch_album_list(folders: &[String]) -> Vec<submarine::data::Child> {
+        if folders.is_empty() {
+            return Self::fetch_album_list_of_folder(None).await;
+        }
+
+        let mut albums = vec![];
+        for folder in folders {
+            albums.append(&mut Self::fetch_album_list_of_folder(Some(folder)).await);
+        }
+        albums
+    }
+
+    async fn fetch_album_list_of_folder(folder: Option<&str>) -> Vec<submarine::data::Child> {
+        let client = Client::get().unwrap();
+        let mut albums = vec![];
+        let mut offset = 0;
+        loop {
+            match client
+                .get_album_list(
+                    submarine::api::get_album_list::Order::AlphabeticalByName,
+                    Some(500),
+                    Some(offset),
+                    folder,
+                )
+                .await
+            {
+                Err(e) => {
+                    tracing::error!("error while fetching albums: {e}");
+                }
+                Ok(mut part) => {
+                    if part.len() < 500 || part.is_empty() {
+                        albums.append(&mut part);
+                        break;
+                    } else {
+                        albums.append(&mut part);
+                        offset += 500;
                     }
                 }
             }
-            albums
-        };
-        tracing::info!("fetched {} albums", album_list.len());
+        }
+        albums
+    }
 
-        //fetch tracks
-        tracing::info!("fetching tracks");
-        let tasks: Vec<_> = album_list
+    /// returns the songs of every album that could be fetched, mapped by album id
+    async fn fetch_tracks(
+        albums: &[submarine::data::Child],
+    ) -> HashMap<String, Vec<submarine::data::Child>> {
+        let tasks: Vec<_> = albums
             .iter()
             .map(|album| async move {
                 let client = Client::get().unwrap();
                 tracing::info!("start loading album {}", album.title);
                 match client.get_album(&album.id).await {
-                    Ok(album) => album.song,
+                    Ok(result) => Some((album.id.clone(), result.song)),
                     Err(e) => {
                         tracing::error!("error fetching album {}: {e}", album.title);
-                        vec![]
+                        None
                     }
                 }
             })
             .collect();
         //buffer futures to not overwhelm server and client
         // based on: https://stackoverflow.com/questions/70871368/limiting-the-number-of-concurrent-futures-in-join-all
-        let stream = futures::stream::iter(tasks)
-            .buffer_unordered(50)
-            .collect::<Vec<_>>();
-        let tracks = stream.await;
-        let tracks: Vec<submarine::data::Child> = tracks.into_iter().flatten().collect();
-        tracing::info!("fetched {} tracks", tracks.len());
-
-        //fetch playlists
-        tracing::info!("fetching playlists");
-        let playlists = {
-            let mut playlist_list = vec![];
-            let playlists = client.get_playlists(None::<&str>).await?;
-            for playlist in playlists {
-                let list = client.get_playlist(playlist.id).await?;
-                playlist_list.push(list);
+        let mut stream = futures::stream::iter(tasks).buffer_unordered(50);
+
+        let mut tracks = HashMap::new();
+        let mut done = 0;
+        while let Some(result) = stream.next().await {
+            done += 1;
+            Self::report(format!(
+                "{} {done}/{}",
+                gettext("fetching albums"),
+                albums.len()
+            ));
+            if let Some((id, songs)) = result {
+                tracks.insert(id, songs);
             }
-            playlist_list
-        };
-        tracing::info!("fetched {} playlists", playlists.len());
-
-        let result = Self {
-            scan_status: scan_status.count,
-            artists,
-            album_list,
-            tracks,
-            playlists,
-            covers: SubsonicCovers::default(),
-        };
-
-        result.save()?;
+        }
+        tracks
+    }
 
-        tracing::info!("finished loading subsonic info");
-        Ok(result)
+    async fn fetch_playlists() -> anyhow::Result<Vec<submarine::data::PlaylistWithSongs>> {
+        let client = Client::get().unwrap();
+        let mut playlist_list = vec![];
+        let playlists = client.get_playlists(None::<&str>).await?;
+        for playlist in playlists {
+            let list = client.get_playlist(playlist.id).await?;
+            playlist_list.push(list);
+        }
+        Ok(playlist_list)
     }
 
     // this is the main way to create a Subsonic object
     pub async fn load_or_create() -> anyhow::Result<Self> {
-        let current_scan_status = {
-            let client = match Client::get() {
-                None => {
-                    tracing::warn!("no client found");
-                    return Ok(Self::default());
-                }
-                Some(client) => client,
-            };
-            client.get_scan_status().await?
+        let client = match Client::get() {
+            None => {
+                tracing::warn!("no client found");
+                return Ok(Self::default());
+            }
+            Some(client) => client,
         };
 
+        // the cache is loaded first, so library and offline songs are usable without a connection
+        let mut synced = false;
         let mut subsonic = match Self::load().await {
-            Ok(subsonic) => {
-                if subsonic.scan_status == current_scan_status.count {
-                    tracing::info!("scan status is current; load cached info");
-                    subsonic
-                } else {
-                    tracing::info!("scan_status changed; reload info");
-                    Self::new().await?
+            Ok(mut subsonic) => {
+                // covers need to be loaded before syncing, because syncing saves them
+                let _ = subsonic.covers.load();
+                match client.get_scan_status().await {
+                    Err(e) => tracing::warn!("server is not reachable; skip sync: {e}"),
+                    Ok(current_scan_status) => {
+                        let music_folders = Settings::get().lock().unwrap().music_folders();
+                        let outdated = if subsonic.music_folders != music_folders {
+                            tracing::info!("selected music folders changed; sync info");
+                            true
+                        } else if subsonic.scan_status == current_scan_status.count {
+                            tracing::info!("scan status is current; load cached info");
+                            false
+                        } else {
+                            tracing::info!("scan_status changed; sync info");
+                            true
+                        };
+                        if outdated {
+                            match subsonic.sync().await {
+                                Ok(()) => synced = true,
+                                Err(e) => tracing::warn!("could not sync; keep cached info: {e}"),
+                            }
+                        }
+                    }
                 }
+                subsonic
             }
             Err(_e) => {
                 tracing::warn!("no cache found or cache is malformed");
                 //load new from server
-                Self::new().await?
+                match Self::new().await {
+                    Ok(mut subsonic) => {
+                        let _ = subsonic.covers.load();
+                        synced = true;
+                        subsonic
+                    }
+                    Err(e) => return Err(e.context("could not load library from server")),
+                }
             }
         };
 
-        let _ = subsonic.covers.load();
+        let _ = subsonic.offline.load();
+        let _ = subsonic.podcasts.load();
+        let _ = subsonic.smart_playlists.load();
+        let _ = subsonic.history.load();
+        let _ = subsonic.scrobbles.load();
+        if synced {
+            // the library changed, so the mirrored smart playlists might be outdated
+            if let Err(e) = subsonic.mirror_smart_playlists().await {
+                tracing::error!("could not mirror smart playlists: {e}");
+            }
+            if let Err(e) = subsonic.refresh_pins() {
+                tracing::error!("could not update offline songs: {e}");
+            }
+        }
         Ok(subsonic)
     }
 
+    /// resolves the pins with the current library, so songs that were added to pinned albums,
+    /// artists or playlists are stored offline and removed songs are deleted
+    fn refresh_pins(&mut self) -> anyhow::Result<()> {
+        let mut missing = vec![];
+        for pin in self.offline.pins().to_vec() {
+            let Ok(id) = Id::try_from(pin.id.as_str()) else {
+                continue;
+            };
+            let songs = match &id {
+                Id::Album(album) => self.songs_of_album(album),
+                Id::Artist(artist) => self.songs_of_artist(artist),
+                Id::Playlist(list) => match self.playlists.iter().find(|p| p.base.id == *list) {
+                    Some(list) => list.entry.clone(),
+                    None => vec![],
+                },
+                Id::Song(_) => vec![],
+            };
+            // keep the stored songs of items that are not on the server anymore
+            if songs.is_empty() {
+                continue;
+            }
+            missing.extend(self.offline.pin(&id, pin.name, songs));
+        }
+        self.offline.remove_unused_files()?;
+        self.offline.save()?;
+
+        let mut ids = HashSet::new();
+        missing.retain(|song| ids.insert(song.id.clone()));
+        self.outdated_pins = missing;
+        Ok(())
+    }
+
+    /// songs that need to be downloaded after pins changed with the sync
+    pub fn take_outdated_pins(&mut self) -> Vec<submarine::data::Child> {
+        std::mem::take(&mut self.outdated_pins)
+    }
+
     pub async fn load() -> anyhow::Result<Self> {
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(MUSIC_INFOS);
+        let cache_path = Settings::cache_dir()?.join(MUSIC_INFOS);
         let content = tokio::fs::read(cache_path).await?;
         tracing::info!("loaded subsonic cache");
         let mut reader = content.as_slice();
         let mut deserializer = rmp_serde::Deserializer::new(&mut reader);
         let mut result = Self::deserialize(&mut deserializer)?;
 
-        // update smart playlists
+        // update smart playlists, the cached ones are kept without a connection
         let client = Client::get().unwrap();
         let mut modified_list = false;
         for playlist in result.playlists.iter_mut() {
             if Self::is_smart_playlist(&playlist.base) {
-                *playlist = client.get_playlist(&playlist.base.id).await?;
-                modified_list = true;
+                match client.get_playlist(&playlist.base.id).await {
+                    Ok(list) => {
+                        *playlist = list;
+                        modified_list = true;
+                    }
+                    Err(e) => {
+                        tracing::warn!("could not update smart playlist: {e}");
+                        break;
+                    }
+                }
             }
         }
         if modified_list {
@@ -181,6 +542,11 @@ impl Subsonic {
         Ok(result)
     }
 
+    /// if a cache of the active login exists, so the library can be used without a connection
+    pub fn has_cache() -> bool {
+        Settings::cache_dir().is_ok_and(|dir| dir.join(MUSIC_INFOS).exists())
+    }
+
     pub fn save(&self) -> anyhow::Result<()> {
         tracing::info!("saving cover cache");
         self.covers.save()?;
@@ -189,10 +555,7 @@ impl Subsonic {
         let mut cache = vec![];
         let mut serializer = rmp_serde::Serializer::new(&mut cache);
         self.serialize(&mut serializer)?;
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(MUSIC_INFOS);
+        let cache_path = Settings::cache_dir()?.join(MUSIC_INFOS);
         std::fs::write(cache_path, cache)?;
 
         Ok(())
@@ -285,6 +648,22 @@ impl Subsonic {
             .collect()
     }
 
+    pub fn albums_of_genre(&self, genre: impl AsRef<str>) -> Vec<submarine::data::Child> {
+        self.album_list
+            .iter()
+            .filter(|album| album.genre.as_deref() == Some(genre.as_ref()))
+            .cloned()
+            .collect()
+    }
+
+    pub fn tracks_of_genre(&self, genre: impl AsRef<str>) -> Vec<submarine::data::Child> {
+        self.tracks
+            .iter()
+            .filter(|track| track.genre.as_deref() == Some(genre.as_ref()))
+            .cloned()
+            .collect()
+    }
+
     pub fn favorite_song(&mut self, id: impl AsRef<str>, state: bool) {
         for playlist in &mut self.playlists {
             for song in &mut playlist.entry {
@@ -319,6 +698,35 @@ impl Subsonic {
         self.save().expect("saving failed");
     }
 
+    /// sets the rating of a song; 0 removes the rating
+    pub fn rate_song(&mut self, id: impl AsRef<str>, rating: i32) {
+        let rating = (rating > 0).then_some(rating);
+        for playlist in &mut self.playlists {
+            for song in &mut playlist.entry {
+                if song.id == id.as_ref() {
+                    song.user_rating = rating;
+                }
+            }
+        }
+        for track in &mut self.tracks {
+            if track.id == id.as_ref() {
+                track.user_rating = rating;
+            }
+        }
+        self.save().expect("saving failed");
+    }
+
+    /// sets the rating of an album; 0 removes the rating
+    pub fn rate_album(&mut self, id: impl AsRef<str>, rating: i32) {
+        let rating = (rating > 0).then_some(rating);
+        for album in &mut self.album_list {
+            if album.id == id.as_ref() {
+                album.user_rating = rating;
+            }
+        }
+        self.save().expect("saving failed");
+    }
+
     pub fn favorite_artist(&mut self, id: impl AsRef<str>, state: bool) {
         for artist in &mut self.artists {
             if artist.id == id.as_ref() {
@@ -415,15 +823,80 @@ impl Subsonic {
         self.covers.cover_icon(id)
     }
 
+    pub fn offline(&self) -> &SubsonicOffline {
+        &self.offline
+    }
+
+    pub fn offline_mut(&mut self) -> &mut SubsonicOffline {
+        &mut self.offline
+    }
+
+    pub fn podcasts(&self) -> &SubsonicPodcasts {
+        &self.podcasts
+    }
+
+    pub fn podcasts_mut(&mut self) -> &mut SubsonicPodcasts {
+        &mut self.podcasts
+    }
+
+    pub fn smart_playlists(&self) -> &SubsonicSmartPlaylists {
+        &self.smart_playlists
+    }
+
+    pub fn smart_playlists_mut(&mut self) -> &mut SubsonicSmartPlaylists {
+        &mut self.smart_playlists
+    }
+
+    pub fn history(&self) -> &SubsonicHistory {
+        &self.history
+    }
+
+    pub fn history_mut(&mut self) -> &mut SubsonicHistory {
+        &mut self.history
+    }
+
+    pub fn scrobbles(&self) -> &SubsonicScrobbles {
+        &self.scrobbles
+    }
+
+    pub fn scrobbles_mut(&mut self) -> &mut SubsonicScrobbles {
+        &mut self.scrobbles
+    }
+
+    /// evaluates the smart playlists and updates their playlists on the server
+    pub async fn mirror_smart_playlists(&mut self) -> anyhow::Result<()> {
+        let mut modified = false;
+        for list in self.smart_playlists.lists_mut().iter_mut() {
+            if !list.mirror {
+                continue;
+            }
+            let songs = list.evaluate(&self.tracks);
+            let mirrored = list.mirror(&songs).await?;
+            match self
+                .playlists
+                .iter_mut()
+                .find(|playlist| playlist.base.id == mirrored.base.id)
+            {
+                Some(playlist) => *playlist = mirrored,
+                None => self.playlists.push(mirrored),
+            }
+            modified = true;
+        }
+
+        if modified {
+            tracing::info!("mirrored smart playlist(s) to server");
+            self.smart_playlists.save()?;
+            self.save()?;
+        }
+        Ok(())
+    }
+
     pub fn delete_cache(&mut self) -> anyhow::Result<()> {
         // delete stored covers
         self.covers.delete_cache()?;
 
         // delete music info
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant find cache info"))?
-            .join(PREFIX)
-            .join(MUSIC_INFOS);
+        let cache_path = Settings::cache_dir()?.join(MUSIC_INFOS);
 
         // if there is no cache then there is no cache to delete
         if !cache_path.exists() {
@@ -525,3 +998,84 @@ impl Subsonic {
         false
     }
 }
+
+/// checks if the server reports different metadata for an album, so its songs need to be fetched again
+fn album_changed(old: &submarine::data::Child, new: &submarine::data::Child) -> bool {
+    old.title != new.title
+        || old.artist != new.artist
+        || old.cover_art != new.cover_art
+        || old.year != new.year
+        || old.genre != new.genre
+        || old.duration != new.duration
+        || old.song_count != new.song_count
+        || old.created != new.created
+}
+
+/// returns the album list with changes of a sync
+///
+/// only changed albums whose songs were fetched are taken over, the others keep their cached
+/// entry or are left out when they are new, so they are detected as changed again
+fn apply_album_changes(
+    cached: &[submarine::data::Child],
+    changed: Vec<submarine::data::Child>,
+    removed: &HashSet<String>,
+    fetched: &HashMap<String, Vec<submarine::data::Child>>,
+) -> Vec<submarine::data::Child> {
+    let mut changed: HashMap<String, submarine::data::Child> = changed
+        .into_iter()
+        .filter(|album| fetched.contains_key(&album.id))
+        .map(|album| (album.id.clone(), album))
+        .collect();
+
+    let mut albums: Vec<submarine::data::Child> = cached
+        .iter()
+        .filter(|album| !removed.contains(&album.id))
+        .map(|album| changed.remove(&album.id).unwrap_or_else(|| album.clone()))
+        .collect();
+    albums.extend(changed.into_values());
+    albums.sort_by_key(|album| album.title.to_lowercase());
+    albums
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn album(id: &str, title: &str) -> submarine::data::Child {
+        submarine::data::Child {
+            id: String::from(id),
+            title: String::from(title),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn album_changes_are_detected() {
+        let old = album("1", "Album");
+        assert!(!album_changed(&old, &old.clone()));
+
+        let mut retitled = old.clone();
+        retitled.title = String::from("Other");
+        assert!(album_changed(&old, &retitled));
+
+        let mut more_songs = old.clone();
+        more_songs.song_count = Some(12);
+        assert!(album_changed(&old, &more_songs));
+    }
+
+    #[test]
+    fn only_fetched_albums_are_applied() {
+        let cached = vec![album("1", "A"), album("2", "B"), album("3", "C")];
+        let changed = vec![album("2", "B2"), album("4", "D"), album("5", "E")];
+        let removed = HashSet::from([String::from("3")]);
+        // songs of album 5 could not be fetched
+        let fetched = HashMap::from([(String::from("2"), vec![]), (String::from("4"), vec![])]);
+
+        let albums = apply_album_changes(&cached, changed, &removed, &fetched);
+        let ids: Vec<(&str, &str)> = albums
+            .iter()
+            .map(|album| (album.id.as_str(), album.title.as_str()))
+            .collect();
+        assert_eq!(ids, [("1", "A"), ("2", "B2"), ("4", "D")]);
+    }
+}
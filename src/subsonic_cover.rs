@@ -3,10 +3,9 @@ use std::{collections::HashMap, io::Cursor};
 use relm4::gtk::{self, gdk};
 use serde::{Deserialize, Serialize};
 
-use crate::client::Client;
+use crate::{client::Client, settings::Settings};
 
 const COVER_SIZE: Option<i32> = Some(200);
-const PREFIX: &str = "Buoy";
 const COVER_CACHE: &str = "cover-cache";
 static CONCURRENT_COVER_RELOAD: tokio::sync::Semaphore = tokio::sync::Semaphore::const_new(50);
 
@@ -129,20 +128,14 @@ impl SubsonicCovers {
     pub fn save(&self) -> anyhow::Result<()> {
         let cache: Vec<u8> = postcard::to_allocvec(self)?;
 
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(COVER_CACHE);
+        let cache_path = Settings::cache_dir()?.join(COVER_CACHE);
         std::fs::write(cache_path, cache)?;
 
         Ok(())
     }
 
     pub fn load(&mut self) -> anyhow::Result<()> {
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(COVER_CACHE);
+        let cache_path = Settings::cache_dir()?.join(COVER_CACHE);
         let content = std::fs::read(cache_path)?;
         tracing::info!("loaded subsonic cover cache");
         let result = postcard::from_bytes::<Self>(&content)?;
@@ -153,10 +146,7 @@ impl SubsonicCovers {
     }
 
     pub fn delete_cache(&self) -> anyhow::Result<()> {
-        let cache_path = dirs::cache_dir()
-            .ok_or(std::io::Error::other("cant create cache dir"))?
-            .join(PREFIX)
-            .join(COVER_CACHE);
+        let cache_path = Settings::cache_dir()?.join(COVER_CACHE);
         std::fs::remove_file(cache_path)?;
         Ok(())
     }
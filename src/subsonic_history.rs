@@ -0,0 +1,283 @@
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+const PLAY_HISTORY: &str = "Play-History";
+/// plays that end this close to the end of the song count as finished
+const FINISHED_MARGIN_MS: i64 = 10_000;
+/// position jumps larger than this are seeks and do not count as listened
+const MAX_TICK_MS: i64 = 5_000;
+
+/// how a play of a song ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayEnd {
+    Skipped,
+    Finished,
+}
+
+impl PlayEnd {
+    pub fn translate(&self) -> String {
+        use gettextrs::gettext;
+
+        match self {
+            Self::Skipped => gettext("Skipped"),
+            Self::Finished => gettext("Finished"),
+        }
+    }
+}
+
+/// a single play of a song
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayRecord {
+    pub song: submarine::data::Child,
+    /// start of the play in ms since epoch
+    pub started: i64,
+    /// how much of the song was listened to in percent
+    pub listened: u8,
+    pub end: PlayEnd,
+    pub scrobbled: bool,
+}
+
+impl PlayRecord {
+    /// local time the play started at
+    pub fn started_local(&self) -> chrono::DateTime<chrono::Local> {
+        chrono::DateTime::from_timestamp_millis(self.started)
+            .unwrap_or_default()
+            .with_timezone(&chrono::Local)
+    }
+
+    /// local day the play started on
+    pub fn day(&self) -> chrono::NaiveDate {
+        self.started_local().date_naive()
+    }
+}
+
+/// play of the current song, it is stored in the history when the song changes
+#[derive(Debug)]
+pub struct ActivePlay {
+    song: submarine::data::Child,
+    started: i64,
+    /// last reported position in ms
+    position: i64,
+    /// time in ms the song was actually played, without seeks
+    listened: i64,
+    scrobbled: bool,
+}
+
+impl ActivePlay {
+    pub fn new(song: submarine::data::Child) -> Self {
+        Self {
+            song,
+            started: chrono::Local::now().timestamp_millis(),
+            position: 0,
+            listened: 0,
+            scrobbled: false,
+        }
+    }
+
+    pub fn song(&self) -> &submarine::data::Child {
+        &self.song
+    }
+
+    /// start of the play in ms since epoch
+    pub fn started(&self) -> i64 {
+        self.started
+    }
+
+    pub fn set_position(&mut self, position: i64) {
+        let delta = position - self.position;
+        if delta > 0 && delta <= MAX_TICK_MS {
+            self.listened += delta;
+        }
+        self.position = position;
+    }
+
+    pub fn set_scrobbled(&mut self) {
+        self.scrobbled = true;
+    }
+
+    pub fn finish(self) -> PlayRecord {
+        let length = i64::from(self.song.duration.unwrap_or(0)) * 1000;
+        let listened = match length {
+            0 => 0,
+            length => (self.listened * 100 / length).clamp(0, 100) as u8,
+        };
+        // without a known length a play can not be told apart from a skip
+        let end = match length > 0 && self.position + FINISHED_MARGIN_MS >= length {
+            true => PlayEnd::Finished,
+            false => PlayEnd::Skipped,
+        };
+
+        PlayRecord {
+            song: self.song,
+            started: self.started,
+            listened,
+            end,
+            scrobbled: self.scrobbled,
+        }
+    }
+}
+
+/// entry of the history file
+#[derive(Debug, Serialize, Deserialize)]
+enum HistoryEntry {
+    Play(PlayRecord),
+    /// play of song that started at started was scrobbled later
+    Scrobbled {
+        song: String,
+        started: i64,
+    },
+}
+
+impl HistoryEntry {
+    /// entries are prefixed with their length, so a malformed one can be skipped
+    fn encode(&self) -> anyhow::Result<Vec<u8>> {
+        let body = rmp_serde::to_vec(self)?;
+        let mut bytes = u32::try_from(body.len())?.to_le_bytes().to_vec();
+        bytes.extend(body);
+        Ok(bytes)
+    }
+}
+
+/// Stores every play of the server profile
+///
+/// The history file is append only, every play and every later scrobble of a play is
+/// added to its end
+#[derive(Default, Debug)]
+pub struct SubsonicHistory {
+    records: Vec<PlayRecord>,
+}
+
+impl SubsonicHistory {
+    /// all plays, the oldest first
+    pub fn records(&self) -> &Vec<PlayRecord> {
+        &self.records
+    }
+
+    pub fn push(&mut self, record: PlayRecord) -> anyhow::Result<()> {
+        let entry = HistoryEntry::Play(record);
+        Self::append(&entry.encode()?)?;
+
+        let HistoryEntry::Play(record) = entry else {
+            unreachable!()
+        };
+        self.records.push(record);
+        Ok(())
+    }
+
+    /// marks the plays of song ids that started at the given times as scrobbled
+    pub fn set_scrobbled(&mut self, plays: &[(String, i64)]) -> anyhow::Result<()> {
+        let mut content = vec![];
+        for record in self.records.iter_mut().filter(|record| !record.scrobbled) {
+            if plays.contains(&(record.song.id.clone(), record.started)) {
+                record.scrobbled = true;
+                let entry = HistoryEntry::Scrobbled {
+                    song: record.song.id.clone(),
+                    started: record.started,
+                };
+                content.append(&mut entry.encode()?);
+            }
+        }
+        if content.is_empty() {
+            return Ok(());
+        }
+        Self::append(&content)
+    }
+
+    fn append(content: &[u8]) -> anyhow::Result<()> {
+        let cache_path = Settings::cache_dir()?.join(PLAY_HISTORY);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(cache_path)?;
+        file.write_all(content)?;
+        Ok(())
+    }
+
+    /// returns the plays and the length of content that consists of complete entries
+    fn parse(content: &[u8]) -> (Vec<PlayRecord>, usize) {
+        let mut records: Vec<PlayRecord> = vec![];
+        let mut offset = 0;
+        while let Some(length) = content.get(offset..offset + 4) {
+            let length = u32::from_le_bytes([length[0], length[1], length[2], length[3]]);
+            let Some(body) = content.get(offset + 4..offset + 4 + length as usize) else {
+                break;
+            };
+            offset += 4 + length as usize;
+
+            match rmp_serde::from_slice::<HistoryEntry>(body) {
+                Ok(HistoryEntry::Play(record)) => records.push(record),
+                Ok(HistoryEntry::Scrobbled { song, started }) => records
+                    .iter_mut()
+                    .filter(|record| record.song.id == song && record.started == started)
+                    .for_each(|record| record.scrobbled = true),
+                // the following entries are still readable
+                Err(e) => tracing::warn!("skip malformed entry of play history: {e}"),
+            }
+        }
+        (records, offset)
+    }
+
+    pub fn load(&mut self) -> anyhow::Result<()> {
+        let cache_path = Settings::cache_dir()?.join(PLAY_HISTORY);
+        let content = std::fs::read(&cache_path)?;
+        let (records, complete) = Self::parse(&content);
+
+        // an interrupted write leaves an incomplete entry, which would make the following
+        // entries unreadable; the complete ones are written to a new file that replaces it
+        if complete < content.len() {
+            tracing::warn!("play history ends with an incomplete entry, remove it");
+            let tmp_path = cache_path.with_extension("tmp");
+            std::fs::write(&tmp_path, &content[..complete])?;
+            std::fs::rename(tmp_path, cache_path)?;
+        }
+
+        self.records = records;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(id: &str, started: i64) -> PlayRecord {
+        PlayRecord {
+            song: submarine::data::Child {
+                id: String::from(id),
+                ..Default::default()
+            },
+            started,
+            listened: 100,
+            end: PlayEnd::Finished,
+            scrobbled: false,
+        }
+    }
+
+    #[test]
+    fn malformed_entries_are_skipped() {
+        let mut content = vec![];
+        content.append(&mut HistoryEntry::Play(play("a", 1)).encode().unwrap());
+        content.append(&mut HistoryEntry::Play(play("b", 2)).encode().unwrap());
+        // entry with a valid length, but malformed content
+        content.extend([3, 0, 0, 0, 0xc1, 0xc1, 0xc1]);
+        content.append(&mut HistoryEntry::Play(play("c", 3)).encode().unwrap());
+        let marker = HistoryEntry::Scrobbled {
+            song: String::from("b"),
+            started: 2,
+        };
+        content.append(&mut marker.encode().unwrap());
+        let complete = content.len();
+        // interrupted write
+        content.extend(&HistoryEntry::Play(play("d", 4)).encode().unwrap()[..6]);
+
+        let (records, readable) = SubsonicHistory::parse(&content);
+        let ids: Vec<&str> = records.iter().map(|play| play.song.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert!(!records[0].scrobbled);
+        assert!(records[1].scrobbled);
+        assert_eq!(readable, complete);
+    }
+}
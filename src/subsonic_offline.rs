@@ -0,0 +1,283 @@
+use std::{collections::HashMap, io::Write};
+
+use gettextrs::gettext;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    common::types::{Droppable, Id},
+    components::app::{App, AppIn},
+    settings::Settings,
+};
+
+const OFFLINE_INFOS: &str = "Offline-Infos";
+const OFFLINE_FOLDER: &str = "offline";
+
+/// Songs that are stored locally, so they can be played without a connection to the server
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct SubsonicOffline {
+    /// pinned albums, artists and playlists
+    pins: Vec<Pin>,
+    /// id of song to its stored file
+    files: HashMap<String, OfflineFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pin {
+    /// serialized Id of album, artist or playlist
+    pub id: String,
+    pub name: String,
+    /// ids of songs that are kept because of this pin
+    pub songs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineFile {
+    pub file_name: String,
+    /// in bytes
+    pub size: u64,
+}
+
+impl SubsonicOffline {
+    /// returns the id and name that a Droppable is pinned with
+    pub fn pin_info(drop: &Droppable) -> Option<(Id, String)> {
+        match drop {
+            Droppable::AlbumChild(child) => Some((Id::album(&child.id), child.title.clone())),
+            Droppable::Album(album) => Some((Id::album(&album.id), album.name.clone())),
+            Droppable::AlbumWithSongs(album) => {
+                Some((Id::album(&album.base.id), album.base.name.clone()))
+            }
+            Droppable::Artist(artist) => Some((Id::artist(&artist.id), artist.name.clone())),
+            Droppable::ArtistWithAlbums(artist) => {
+                Some((Id::artist(&artist.base.id), artist.base.name.clone()))
+            }
+            Droppable::Playlist(list) => {
+                Some((Id::playlist(&list.base.id), list.base.name.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn folder() -> anyhow::Result<std::path::PathBuf> {
+        let folder = Settings::cache_dir()?.join(OFFLINE_FOLDER);
+        std::fs::create_dir_all(&folder)?;
+        Ok(folder)
+    }
+
+    /// returns a file:// uri when the song is stored locally
+    pub fn local_uri(&self, song_id: &str) -> Option<String> {
+        let file = self.files.get(song_id)?;
+        let path = Self::folder().ok()?.join(&file.file_name);
+        if !path.exists() {
+            tracing::warn!("offline file {path:?} is missing");
+            return None;
+        }
+        url::Url::from_file_path(path).ok().map(String::from)
+    }
+
+    /// returns the path of a sidecar .lrc file for the song when it exists
+    pub fn lyrics_file(&self, song_id: &str) -> Option<std::path::PathBuf> {
+        let folder = Self::folder().ok()?;
+        let path = match self.files.get(song_id) {
+            Some(file) => folder.join(&file.file_name).with_extension("lrc"),
+            None => folder.join(format!(
+                "{}.lrc",
+                song_id.replace(std::path::MAIN_SEPARATOR, "_")
+            )),
+        };
+        path.exists().then_some(path)
+    }
+
+    pub fn is_pinned(&self, id: &Id) -> bool {
+        let id = id.serialize();
+        self.pins.iter().any(|pin| pin.id == id)
+    }
+
+    pub fn pins(&self) -> &[Pin] {
+        &self.pins
+    }
+
+    /// size of all stored songs in bytes
+    pub fn size(&self) -> u64 {
+        self.files.values().map(|file| file.size).sum()
+    }
+
+    /// size of the stored songs of a pin in bytes
+    pub fn pin_size(&self, pin: &Pin) -> u64 {
+        pin.songs
+            .iter()
+            .filter_map(|id| self.files.get(id))
+            .map(|file| file.size)
+            .sum()
+    }
+
+    /// adds or updates a pin and returns the songs that still need to be downloaded
+    pub fn pin(
+        &mut self,
+        id: &Id,
+        name: impl Into<String>,
+        songs: Vec<submarine::data::Child>,
+    ) -> Vec<submarine::data::Child> {
+        let pin = Pin {
+            id: id.serialize(),
+            name: name.into(),
+            songs: songs.iter().map(|song| song.id.clone()).collect(),
+        };
+        match self.pins.iter_mut().find(|old| old.id == pin.id) {
+            Some(old) => *old = pin,
+            None => self.pins.push(pin),
+        }
+
+        songs
+            .into_iter()
+            .filter(|song| !self.files.contains_key(&song.id))
+            .collect()
+    }
+
+    /// records a downloaded song; the file is deleted when no pin needs it anymore,
+    /// e.g. when it was unpinned while downloading
+    pub fn add_file(
+        &mut self,
+        song_id: impl Into<String>,
+        file_name: String,
+        size: u64,
+    ) -> anyhow::Result<()> {
+        let song_id = song_id.into();
+        if !self.pins.iter().any(|pin| pin.songs.contains(&song_id)) {
+            let path = Self::folder()?.join(file_name);
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+
+        self.files.insert(song_id, OfflineFile { file_name, size });
+        Ok(())
+    }
+
+    /// removes the pin and all files that no other pin needs
+    pub fn unpin(&mut self, id: &Id) -> anyhow::Result<()> {
+        let id = id.serialize();
+        self.pins.retain(|pin| pin.id != id);
+        self.remove_unused_files()
+    }
+
+    /// removes the files of songs that no pin needs
+    pub fn remove_unused_files(&mut self) -> anyhow::Result<()> {
+        let folder = Self::folder()?;
+        let unused: Vec<String> = self
+            .files
+            .keys()
+            .filter(|song| !self.pins.iter().any(|pin| pin.songs.contains(song)))
+            .cloned()
+            .collect();
+        for song in unused {
+            if let Some(file) = self.files.remove(&song) {
+                let path = folder.join(file.file_name);
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// downloads songs into offline folder, every finished song is send back to App
+    pub fn download(
+        sender: relm4::component::AsyncComponentSender<App>,
+        name: String,
+        songs: Vec<submarine::data::Child>,
+    ) {
+        let client = Client::get().unwrap();
+        let folder = match Self::folder() {
+            Ok(folder) => folder,
+            Err(e) => {
+                sender.input(AppIn::DisplayToast(format!(
+                    "could not create offline folder: {e}"
+                )));
+                return;
+            }
+        };
+
+        // new thread for downloading files on
+        tokio::spawn(async move {
+            let mut failed = 0;
+            for song in songs {
+                let buffer = match client.download(&song.id).await {
+                    Ok(buffer) => buffer,
+                    Err(e) => {
+                        tracing::error!("offline download of {} failed: {e}", song.title);
+                        failed += 1;
+                        continue;
+                    }
+                };
+
+                let file_name = format!(
+                    "{}.{}",
+                    song.id.replace(std::path::MAIN_SEPARATOR, "_"),
+                    song.suffix.clone().unwrap_or_default()
+                );
+                let path = folder.join(&file_name);
+                let result =
+                    std::fs::File::create(&path).and_then(|mut file| file.write_all(&buffer));
+                if let Err(e) = result {
+                    tracing::error!("saving offline file {path:?} failed: {e}");
+                    failed += 1;
+                    continue;
+                }
+                sender.input(AppIn::OfflineFileStored(
+                    song.id,
+                    file_name,
+                    buffer.len() as u64,
+                ));
+            }
+
+            match failed {
+                0 => sender.input(AppIn::DisplayToast(format!(
+                    "{}: {name}",
+                    gettext("Available offline")
+                ))),
+                failed => sender.input(AppIn::DisplayToast(format!(
+                    "{failed} {} {name}",
+                    gettext("songs could not be stored offline for")
+                ))),
+            }
+        });
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let mut cache = vec![];
+        let mut serializer = rmp_serde::Serializer::new(&mut cache);
+        self.serialize(&mut serializer)?;
+
+        let cache_path = Settings::cache_dir()?.join(OFFLINE_INFOS);
+        std::fs::write(cache_path, cache)?;
+
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> anyhow::Result<()> {
+        let cache_path = Settings::cache_dir()?.join(OFFLINE_INFOS);
+        let content = std::fs::read(cache_path)?;
+        let mut reader = content.as_slice();
+        let mut deserializer = rmp_serde::Deserializer::new(&mut reader);
+        let result = Self::deserialize(&mut deserializer)?;
+
+        self.pins = result.pins;
+        self.files = result.files;
+        tracing::info!("count of offline songs {}", self.files.len());
+        Ok(())
+    }
+
+    /// removes all pins and stored songs
+    pub fn delete_cache(&mut self) -> anyhow::Result<()> {
+        self.pins.clear();
+        self.files.clear();
+
+        let folder = Self::folder()?;
+        std::fs::remove_dir_all(folder)?;
+        self.save()?;
+        Ok(())
+    }
+}
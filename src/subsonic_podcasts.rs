@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+const PODCAST_INFOS: &str = "Podcast-Infos";
+/// episodes that are this close to their end count as finished
+const FINISHED_MARGIN_MS: i64 = 10_000;
+
+/// Remembers how far podcast episodes were listened to
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct SubsonicPodcasts {
+    /// stream id of episode to its position in ms
+    positions: HashMap<String, i64>,
+}
+
+impl SubsonicPodcasts {
+    /// returns the stream of an episode as song, so it can be played through the queue
+    ///
+    /// returns None when the episode is not downloaded on the server
+    pub fn playable(episode: &submarine::data::PodcastEpisode) -> Option<submarine::data::Child> {
+        let stream_id = episode.stream_id.as_ref()?;
+        let mut child = episode.child.clone();
+        child.id = stream_id.clone();
+        Some(child)
+    }
+
+    /// marks the song as episode, so its position gets remembered
+    pub fn add_episode(&mut self, id: impl Into<String>) {
+        self.positions.entry(id.into()).or_default();
+    }
+
+    pub fn is_episode(&self, id: &str) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    /// returns the position in ms the episode was stopped at
+    pub fn position(&self, id: &str) -> Option<i64> {
+        self.positions.get(id).copied().filter(|pos| *pos > 0)
+    }
+
+    /// updates the position of an episode; finished episodes start from the beginning again
+    pub fn set_position(&mut self, id: &str, position: i64, length: i64) {
+        let Some(stored) = self.positions.get_mut(id) else {
+            return;
+        };
+        *stored = match position + FINISHED_MARGIN_MS >= length {
+            true => 0,
+            false => position,
+        };
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let mut cache = vec![];
+        let mut serializer = rmp_serde::Serializer::new(&mut cache);
+        self.serialize(&mut serializer)?;
+
+        let cache_path = Settings::cache_dir()?.join(PODCAST_INFOS);
+        std::fs::write(cache_path, cache)?;
+
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> anyhow::Result<()> {
+        let cache_path = Settings::cache_dir()?.join(PODCAST_INFOS);
+        let content = std::fs::read(cache_path)?;
+        let mut reader = content.as_slice();
+        let mut deserializer = rmp_serde::Deserializer::new(&mut reader);
+        let result = Self::deserialize(&mut deserializer)?;
+
+        self.positions = result.positions;
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+const PENDING_SCROBBLES: &str = "Pending-Scrobbles";
+/// maximum number of scrobbles submitted with one request
+const BATCH_SIZE: usize = 50;
+/// delay before the first retry, it doubles with every failed retry
+const RETRY_DELAY_SECS: u64 = 30;
+const MAX_RETRY_DELAY_SECS: u64 = 30 * 60;
+
+/// a play that could not be scrobbled to the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingScrobble {
+    pub song: submarine::data::Child,
+    /// time of the play in ms since epoch
+    pub time: i64,
+}
+
+/// Keeps scrobbles that failed, so they can be submitted when the server is reachable again
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct SubsonicScrobbles {
+    /// the oldest first
+    pending: Vec<PendingScrobble>,
+    /// number of failed retries since the last successful one
+    #[serde(skip)]
+    failed_retries: u32,
+    /// size of the next batch, it shrinks while searching a scrobble the server refuses
+    #[serde(skip)]
+    batch_size: Option<usize>,
+}
+
+impl SubsonicScrobbles {
+    pub fn pending(&self) -> &Vec<PendingScrobble> {
+        &self.pending
+    }
+
+    pub fn push(&mut self, scrobble: PendingScrobble) -> anyhow::Result<()> {
+        self.pending.push(scrobble);
+        self.save()
+    }
+
+    /// returns the oldest scrobbles that fit into one request
+    pub fn batch(&self) -> Vec<PendingScrobble> {
+        self.pending
+            .iter()
+            .take(self.batch_size.unwrap_or(BATCH_SIZE))
+            .cloned()
+            .collect()
+    }
+
+    /// handles a batch the server refused, the dropped scrobble still needs to be saved
+    ///
+    /// the batch is split in halves until the refused scrobble is found, which is dropped
+    /// and returned, as it would block all following scrobbles
+    pub fn refused(&mut self, batch: &[PendingScrobble]) -> Option<PendingScrobble> {
+        match batch {
+            [scrobble] => {
+                self.batch_size = None;
+                self.remove(batch);
+                Some(scrobble.clone())
+            }
+            batch => {
+                self.batch_size = Some((batch.len() / 2).max(1));
+                None
+            }
+        }
+    }
+
+    /// whether the server refused a scrobble of the batch because it does not know the song
+    ///
+    /// every other error, e.g. a wrong login or scrobbling being disabled, may be temporary,
+    /// so the batch is kept
+    pub fn is_refusal(error: &submarine::SubsonicError) -> bool {
+        match error {
+            // Subsonic error 70: the requested data was not found
+            submarine::SubsonicError::Server(e) => {
+                let e = format!("{e:?}").to_lowercase();
+                e.contains("code: 70") || e.contains("not found")
+            }
+            _ => false,
+        }
+    }
+
+    /// removes submitted scrobbles and resets the retry delay
+    pub fn remove_submitted(&mut self, submitted: &[PendingScrobble]) -> anyhow::Result<()> {
+        self.remove(submitted);
+        self.save()
+    }
+
+    fn remove(&mut self, submitted: &[PendingScrobble]) {
+        self.pending
+            .retain(|scrobble| !submitted.contains(scrobble));
+        self.failed_retries = 0;
+        if self.pending.is_empty() {
+            self.batch_size = None;
+        }
+    }
+
+    /// returns the delay before the next retry, which grows with every failed retry
+    pub fn retry_failed(&mut self) -> std::time::Duration {
+        let delay = RETRY_DELAY_SECS
+            .saturating_mul(2u64.saturating_pow(self.failed_retries))
+            .min(MAX_RETRY_DELAY_SECS);
+        self.failed_retries = self.failed_retries.saturating_add(1);
+        std::time::Duration::from_secs(delay)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let mut cache = vec![];
+        let mut serializer = rmp_serde::Serializer::new(&mut cache);
+        self.serialize(&mut serializer)?;
+
+        let cache_path = Settings::cache_dir()?.join(PENDING_SCROBBLES);
+        std::fs::write(cache_path, cache)?;
+
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> anyhow::Result<()> {
+        let cache_path = Settings::cache_dir()?.join(PENDING_SCROBBLES);
+        let content = std::fs::read(cache_path)?;
+        let mut reader = content.as_slice();
+        let mut deserializer = rmp_serde::Deserializer::new(&mut reader);
+        let result = Self::deserialize(&mut deserializer)?;
+
+        self.pending = result.pending;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrobble(id: &str) -> PendingScrobble {
+        PendingScrobble {
+            song: submarine::data::Child {
+                id: String::from(id),
+                ..Default::default()
+            },
+            time: 0,
+        }
+    }
+
+    #[test]
+    fn refused_scrobble_is_searched_and_dropped() {
+        let mut scrobbles = SubsonicScrobbles {
+            pending: ["a", "b", "c", "d", "e"].map(scrobble).to_vec(),
+            ..Default::default()
+        };
+
+        // the server refuses every batch containing c
+        let mut dropped = None;
+        while dropped.is_none() {
+            let batch = scrobbles.batch();
+            if batch.contains(&scrobble("c")) {
+                dropped = scrobbles.refused(&batch);
+            } else {
+                scrobbles.remove(&batch);
+            }
+        }
+
+        assert_eq!(dropped, Some(scrobble("c")));
+        assert_eq!(scrobbles.pending(), &["d", "e"].map(scrobble).to_vec());
+        assert_eq!(scrobbles.batch().len(), 2);
+    }
+
+    #[test]
+    fn retry_delay_grows_until_limit() {
+        let mut scrobbles = SubsonicScrobbles::default();
+        let delays: Vec<u64> = (0..10)
+            .map(|_| scrobbles.retry_failed().as_secs())
+            .collect();
+
+        assert_eq!(delays[..4], [30, 60, 120, 240]);
+        assert_eq!(delays[9], MAX_RETRY_DELAY_SECS);
+        assert!(delays.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
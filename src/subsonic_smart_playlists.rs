@@ -0,0 +1,196 @@
+use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{client::Client, common::filter::Filter, settings::Settings};
+
+const SMART_PLAYLISTS: &str = "Smart-Playlists";
+
+/// order of the songs of a smart playlist, applied before the limit
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SmartSort {
+    #[default]
+    Title,
+    Artist,
+    Album,
+    Year,
+    RecentlyAdded,
+    MostPlayed,
+    Rating,
+    Random,
+}
+
+impl SmartSort {
+    /// all variants in the order they are shown in a dropdown
+    pub const ALL: [SmartSort; 8] = [
+        SmartSort::Title,
+        SmartSort::Artist,
+        SmartSort::Album,
+        SmartSort::Year,
+        SmartSort::RecentlyAdded,
+        SmartSort::MostPlayed,
+        SmartSort::Rating,
+        SmartSort::Random,
+    ];
+
+    pub fn translate(&self) -> String {
+        use gettextrs::gettext;
+
+        match self {
+            Self::Title => gettext("Title"),
+            Self::Artist => gettext("Artist"),
+            Self::Album => gettext("Album"),
+            Self::Year => gettext("Year"),
+            Self::RecentlyAdded => gettext("Recently added"),
+            Self::MostPlayed => gettext("Most played"),
+            Self::Rating => gettext("Rating"),
+            Self::Random => gettext("Random"),
+        }
+    }
+}
+
+/// playlist that is defined by filters and evaluated against the local cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartPlaylist {
+    pub name: String,
+    pub filters: Vec<Filter>,
+    #[serde(default)]
+    pub sort: SmartSort,
+    /// maximum number of songs; None for all matching songs
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// whether the result is mirrored to a playlist on the server
+    #[serde(default)]
+    pub mirror: bool,
+    /// id of the mirrored playlist on the server
+    #[serde(default)]
+    pub mirror_id: Option<String>,
+}
+
+impl SmartPlaylist {
+    /// returns the songs matching all filters, sorted and limited
+    pub fn evaluate(&self, tracks: &[submarine::data::Child]) -> Vec<submarine::data::Child> {
+        let mut songs: Vec<submarine::data::Child> = tracks
+            .iter()
+            .filter(|track| self.filters.iter().all(|filter| filter.match_track(track)))
+            .cloned()
+            .collect();
+
+        match self.sort {
+            SmartSort::Title => songs.sort_by(|a, b| a.title.cmp(&b.title)),
+            SmartSort::Artist => songs.sort_by(|a, b| a.artist.cmp(&b.artist)),
+            SmartSort::Album => songs.sort_by(|a, b| {
+                a.album
+                    .cmp(&b.album)
+                    .then(a.disc_number.cmp(&b.disc_number))
+                    .then(a.track.cmp(&b.track))
+            }),
+            SmartSort::Year => songs.sort_by(|a, b| a.year.cmp(&b.year)),
+            SmartSort::RecentlyAdded => songs.sort_by(|a, b| b.created.cmp(&a.created)),
+            SmartSort::MostPlayed => songs.sort_by(|a, b| b.play_count.cmp(&a.play_count)),
+            SmartSort::Rating => songs.sort_by(|a, b| b.user_rating.cmp(&a.user_rating)),
+            SmartSort::Random => songs.shuffle(&mut rand::rng()),
+        }
+
+        if let Some(limit) = self.limit {
+            songs.truncate(limit);
+        }
+        songs
+    }
+
+    /// replaces the content of the mirrored server playlist with given songs
+    ///
+    /// creates the server playlist when it does not exist (anymore)
+    pub async fn mirror(
+        &mut self,
+        songs: &[submarine::data::Child],
+    ) -> anyhow::Result<submarine::data::PlaylistWithSongs> {
+        let client = Client::get().unwrap();
+        let ids: Vec<String> = songs.iter().map(|song| song.id.clone()).collect();
+
+        let existing = match &self.mirror_id {
+            Some(id) => client.get_playlist(id).await.ok(),
+            None => None,
+        };
+        let Some(existing) = existing else {
+            let list = client.create_playlist(&self.name, ids).await?;
+            self.mirror_id = Some(list.base.id.clone());
+            return Ok(list);
+        };
+
+        // remove old content and add the new songs
+        let indices: Vec<i64> = (0..existing.entry.len() as i64).collect();
+        client
+            .update_playlist(
+                &existing.base.id,
+                Some(self.name.clone()),
+                None::<String>,
+                None,
+                Vec::<String>::new(),
+                indices,
+            )
+            .await?;
+        client
+            .update_playlist(
+                &existing.base.id,
+                None::<String>,
+                None::<String>,
+                None,
+                ids,
+                vec![],
+            )
+            .await?;
+        Ok(client.get_playlist(&existing.base.id).await?)
+    }
+}
+
+/// Stores the smart playlists of the server profile
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct SubsonicSmartPlaylists {
+    lists: Vec<SmartPlaylist>,
+}
+
+impl SubsonicSmartPlaylists {
+    pub fn lists(&self) -> &Vec<SmartPlaylist> {
+        &self.lists
+    }
+
+    pub fn lists_mut(&mut self) -> &mut Vec<SmartPlaylist> {
+        &mut self.lists
+    }
+
+    pub fn push(&mut self, list: SmartPlaylist) {
+        self.lists.push(list);
+        self.save().expect("saving failed");
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<SmartPlaylist> {
+        if index >= self.lists.len() {
+            return None;
+        }
+        let list = self.lists.remove(index);
+        self.save().expect("saving failed");
+        Some(list)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let mut cache = vec![];
+        let mut serializer = rmp_serde::Serializer::new(&mut cache);
+        self.serialize(&mut serializer)?;
+
+        let cache_path = Settings::cache_dir()?.join(SMART_PLAYLISTS);
+        std::fs::write(cache_path, cache)?;
+
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> anyhow::Result<()> {
+        let cache_path = Settings::cache_dir()?.join(SMART_PLAYLISTS);
+        let content = std::fs::read(cache_path)?;
+        let mut reader = content.as_slice();
+        let mut deserializer = rmp_serde::Deserializer::new(&mut reader);
+        let result = Self::deserialize(&mut deserializer)?;
+
+        self.lists = result.lists;
+        Ok(())
+    }
+}